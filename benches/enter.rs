@@ -0,0 +1,48 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use nolife::{scope, BoxScope, SingleFamily};
+
+fn counter() -> BoxScope<SingleFamily<u64>> {
+    BoxScope::new_dyn(scope!({
+        let mut x = 0u64;
+        loop {
+            freeze!(&mut x);
+            x += 1;
+        }
+    }))
+}
+
+/// Steady-state cost of entering a scope that is already frozen.
+fn enter(c: &mut Criterion) {
+    let mut scope = counter();
+    c.bench_function("enter", |b| b.iter(|| scope.enter(|x| black_box(*x))));
+
+    let mut scope = BoxScope::<SingleFamily<u64>, _>::new(scope!({
+        let mut x = 0u64;
+        loop {
+            freeze!(&mut x);
+            x += 1;
+        }
+    }));
+    c.bench_function("enter_static_future", |b| {
+        b.iter(|| scope.enter(|x| black_box(*x)))
+    });
+
+    let mut scope = counter();
+    c.bench_function("try_enter", |b| {
+        b.iter(|| scope.try_enter(|x| black_box(*x)))
+    });
+}
+
+/// Cost of creating a scope and entering it until its first freeze.
+fn first_freeze(c: &mut Criterion) {
+    c.bench_function("first_freeze", |b| {
+        b.iter_batched(
+            counter,
+            |mut scope| scope.enter(|x| black_box(*x)),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(benches, enter, first_freeze);
+criterion_main!(benches);
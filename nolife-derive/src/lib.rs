@@ -0,0 +1,142 @@
+//! Derive macro for the [`Family`](https://docs.rs/nolife/latest/nolife/trait.Family.html) trait of `nolife`.
+//!
+//! This crate is re-exported by `nolife` when its `derive` feature is enabled, and should not be used directly.
+#![warn(missing_docs)]
+
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, spanned::Spanned, DeriveInput, GenericParam};
+
+/// Derives a helper type implementing `Family` for a type with exactly one lifetime.
+///
+/// For a type `View<'a>`, this declares the `ViewFamily` type with the same visibility as `View`,
+/// and implements `Family<'a>` for it with `View<'a>` as the `Family` associated type.
+///
+/// The type can be a struct or an enum. Variants of an enum may or may not borrow with the lifetime.
+///
+/// Type and const parameters of the type are also parameters of the helper type.
+/// Type parameters need to be `'static` for the helper type to implement `for<'a> Family<'a>`.
+///
+/// # Example
+///
+/// ```
+/// use nolife::{scope, BoxScope, Family};
+///
+/// #[derive(Family)]
+/// struct View<'a> {
+///     header: &'a mut u32,
+///     body: &'a [u8],
+/// }
+///
+/// let mut scope = BoxScope::<ViewFamily>::new_dyn(scope!({
+///     let mut header = 0;
+///     let body = vec![0, 1, 2];
+///     freeze_forever!(&mut View { header: &mut header, body: &body })
+/// }));
+///
+/// scope.enter(|view| assert_eq!(view.body.len(), 3));
+/// ```
+///
+/// # Errors
+///
+/// Types without a lifetime are rejected, use `SingleFamily` instead:
+///
+/// ```compile_fail
+/// #[derive(nolife::Family)]
+/// struct NoLifetime {
+///     x: u32,
+/// }
+/// ```
+///
+/// Types with several lifetimes are rejected:
+///
+/// ```compile_fail
+/// #[derive(nolife::Family)]
+/// struct TwoLifetimes<'a, 'b> {
+///     x: &'a u32,
+///     y: &'b u32,
+/// }
+/// ```
+#[proc_macro_derive(Family)]
+pub fn derive_family(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match family(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn family(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let DeriveInput {
+        vis,
+        ident,
+        generics,
+        ..
+    } = input;
+
+    let mut lifetimes = generics.lifetimes();
+    let lifetime =
+        match (lifetimes.next(), lifetimes.next()) {
+            (Some(lifetime), None) => lifetime.lifetime.clone(),
+            (None, _) => return Err(syn::Error::new(
+                ident.span(),
+                "`Family` can only be derived for types with exactly one lifetime, found none. \
+                 Use `nolife::SingleFamily` for `'static` types",
+            )),
+            (Some(_), Some(second)) => {
+                return Err(syn::Error::new(
+                    second.span(),
+                    "`Family` can only be derived for types with exactly one lifetime",
+                ))
+            }
+        };
+
+    let family = format_ident!("{}Family", ident);
+    let doc = format!(
+        "Helper type implementing `Family` for [`{ident}`], derived with `#[derive(Family)]`."
+    );
+
+    // the helper type mirrors the type and const parameters, without their bounds
+    let params: Vec<_> = generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Lifetime(_) => None,
+            GenericParam::Type(param) => {
+                let ident = &param.ident;
+                Some(quote!(#ident))
+            }
+            GenericParam::Const(param) => {
+                let ident = &param.ident;
+                let ty = &param.ty;
+                Some(quote!(const #ident: #ty))
+            }
+        })
+        .collect();
+    let type_params: Vec<_> = generics.type_params().map(|param| &param.ident).collect();
+    let args: Vec<_> = generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Lifetime(_) => None,
+            GenericParam::Type(param) => Some(&param.ident),
+            GenericParam::Const(param) => Some(&param.ident),
+        })
+        .collect();
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let predicates = where_clause.map(|where_clause| &where_clause.predicates);
+
+    Ok(quote! {
+        #[doc = #doc]
+        #vis struct #family<#(#params),*>(::core::marker::PhantomData<fn() -> (#(#type_params,)*)>);
+
+        impl #impl_generics ::nolife::Family<#lifetime> for #family<#(#args),*>
+        where
+            #ident #ty_generics: #lifetime,
+            #predicates
+        {
+            type Family = #ident #ty_generics;
+        }
+    })
+}
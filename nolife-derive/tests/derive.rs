@@ -0,0 +1,94 @@
+use nolife::{scope, BoxScope, Family};
+
+#[derive(Family)]
+struct View<'a> {
+    header: &'a mut u32,
+    body: &'a [u8],
+}
+
+#[derive(Family)]
+enum Event<'a, T: Clone> {
+    Data(&'a [T]),
+    Eof,
+}
+
+#[derive(Family)]
+enum Chunk<'a> {
+    Data(&'a mut [u8]),
+    Skipped { len: usize },
+    Eof,
+}
+
+#[derive(Family)]
+struct Window<'a, T, const N: usize>
+where
+    T: Copy,
+{
+    data: &'a [T; N],
+}
+
+#[test]
+fn derive_struct() {
+    let mut scope = BoxScope::<ViewFamily>::new_dyn(scope!({
+        let mut header = 0;
+        let body = vec![0, 1, 2];
+        loop {
+            freeze!(&mut View {
+                header: &mut header,
+                body: &body,
+            });
+        }
+    }));
+
+    scope.enter(|view| {
+        *view.header += 1;
+        assert_eq!(view.body, &[0, 1, 2]);
+    });
+    scope.enter(|view| assert_eq!(*view.header, 1));
+}
+
+#[test]
+fn derive_generic_enum() {
+    let mut scope = BoxScope::<EventFamily<u8>>::new_dyn(scope!({
+        let data = vec![0, 1, 2];
+        freeze!(&mut Event::Data(&data));
+        freeze_forever!(&mut Event::Eof)
+    }));
+
+    scope.enter(|event| assert!(matches!(event, Event::Data([0, 1, 2]))));
+    scope.enter(|event| assert!(matches!(event, Event::Eof)));
+}
+
+#[test]
+fn derive_const_generic() {
+    let mut scope = BoxScope::<WindowFamily<u8, 3>>::new_dyn(scope!({
+        let data = [0, 1, 2];
+        freeze_forever!(&mut Window { data: &data })
+    }));
+
+    scope.enter(|window| assert_eq!(window.data, &[0, 1, 2]));
+}
+
+#[test]
+fn derive_enum() {
+    let mut scope = BoxScope::<ChunkFamily>::new_dyn(scope!({
+        let mut buf = vec![0u8; 4];
+        for round in 0..2u8 {
+            freeze!(&mut Chunk::Data(&mut buf));
+            freeze!(&mut Chunk::Skipped {
+                len: usize::from(round)
+            });
+        }
+        freeze_forever!(&mut Chunk::Eof)
+    }));
+
+    scope.enter(|chunk| match chunk {
+        Chunk::Data(buf) => buf.fill(1),
+        _ => unreachable!(),
+    });
+    scope.enter(|chunk| assert!(matches!(chunk, Chunk::Skipped { len: 0 })));
+    scope.enter(|chunk| assert!(matches!(chunk, Chunk::Data([1, 1, 1, 1]))));
+    scope.enter(|chunk| assert!(matches!(chunk, Chunk::Skipped { len: 1 })));
+    scope.enter(|chunk| assert!(matches!(chunk, Chunk::Eof)));
+    scope.enter(|chunk| assert!(matches!(chunk, Chunk::Eof)));
+}
@@ -0,0 +1,77 @@
+//! A stable shim for custom allocators, used by [`crate::BoxScope::new_in`].
+//!
+//! The [`Allocator`] trait mirrors the unstable [`core::alloc::Allocator`](https://doc.rust-lang.org/core/alloc/trait.Allocator.html)
+//! trait, restricted to what nolife needs.
+
+use core::{alloc::Layout, ptr::NonNull};
+
+/// An implementation of `Allocator` can allocate and deallocate the memory of a [`crate::BoxScope`].
+///
+/// # Safety
+///
+/// - Memory blocks returned by [`Allocator::allocate`] must be valid for reads and writes of `layout.size()` bytes,
+///   be aligned to `layout.align()`, and remain valid until they are passed to [`Allocator::deallocate`]
+///   or the allocator is dropped.
+/// - Moving the allocator must not invalidate the memory blocks it returned.
+pub unsafe trait Allocator {
+    /// Attempts to allocate a block of memory fitting `layout`.
+    ///
+    /// `layout` always has a non-zero size.
+    ///
+    /// # Errors
+    ///
+    /// - [`AllocError`] if the memory cannot be allocated.
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError>;
+
+    /// Deallocates the memory referenced by `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// 1. `ptr` was returned by a call to [`Allocator::allocate`] on this allocator, and was not deallocated since.
+    /// 2. `layout` is the layout that was passed to that call to [`Allocator::allocate`].
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout);
+}
+
+// SAFETY: forwarding to the allocator behind the reference, which outlives the reference.
+unsafe impl<A: Allocator + ?Sized> Allocator for &A {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        (**self).allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        // SAFETY: forwarding the preconditions
+        unsafe { (**self).deallocate(ptr, layout) }
+    }
+}
+
+/// The global memory allocator.
+///
+/// This is the allocator used by [`crate::BoxScope::new`] and [`crate::BoxScope::new_dyn`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Global;
+
+// SAFETY: the global allocator satisfies these guarantees, and its blocks are not tied to the `Global` value.
+unsafe impl Allocator for Global {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        // SAFETY: `layout` has a non-zero size, as documented on `Allocator::allocate`.
+        NonNull::new(unsafe { alloc::alloc::alloc(layout) }).ok_or(AllocError)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        // SAFETY: `ptr` was allocated with `alloc::alloc::alloc` with the same layout by precondition (1) + (2).
+        unsafe { alloc::alloc::dealloc(ptr.as_ptr(), layout) }
+    }
+}
+
+/// Error returned by [`Allocator::allocate`] when the memory cannot be allocated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocError;
+
+impl core::fmt::Display for AllocError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("memory allocation failed")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AllocError {}
@@ -0,0 +1,156 @@
+use alloc::boxed::Box;
+use core::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+
+use tokio::sync::{mpsc, oneshot};
+
+use crate::{allocator::Allocator, panics, BoxScope, DynScope, Family, ScopeError};
+
+/// A request sent to the blocking task, entering the scope and sending the result back.
+type Request<T> = Box<dyn FnOnce(&mut dyn DynScope<T>) + Send>;
+
+/// The result of a request, panics of the passed function or of the scope are sent back to the caller.
+type Reply<O> = Result<Result<O, ScopeError>, Box<dyn core::any::Any + Send>>;
+
+/// A handle to a [`BoxScope`] moved into a blocking task of the Tokio runtime.
+///
+/// Created with [`BoxScope::spawn_blocking`].
+///
+/// Each call to [`BlockingScope::enter`] sends the passed function to the blocking task, which enters the scope
+/// and sends the extracted value back, so that running the underlying future never blocks the asynchronous tasks.
+/// Requests are served one at a time, in the order they were sent.
+///
+/// The handle can be cloned to enter the same scope from several tasks.
+/// The scope is dropped in the blocking task when all the handles are dropped.
+pub struct BlockingScope<T>
+where
+    T: for<'a> Family<'a> + 'static,
+{
+    sender: mpsc::UnboundedSender<Request<T>>,
+}
+
+impl<T, F: ?Sized, A> BoxScope<T, F, A>
+where
+    T: for<'a> Family<'a> + 'static,
+    F: Future + Send + 'static,
+    A: Allocator + Send + 'static,
+    for<'a> <T as Family<'a>>::Family: Send,
+{
+    /// Moves this scope into a blocking task of the current Tokio runtime, returning a handle to enter it
+    /// asynchronously.
+    ///
+    /// See [`BlockingScope`].
+    ///
+    /// # Panics
+    ///
+    /// - If called outside of a Tokio runtime.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, SingleFamily};
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let scope = BoxScope::<SingleFamily<u32>, _>::new_dyn_send(scope!({
+    ///     let mut x = 0u32;
+    ///     loop {
+    ///         freeze!(&mut x);
+    ///         x += 1;
+    ///     }
+    /// }));
+    ///
+    /// let scope = scope.spawn_blocking();
+    /// assert_eq!(scope.enter(|x| *x).await, 0);
+    /// assert_eq!(scope.enter(|x| *x * 10).await, 10);
+    /// # });
+    /// ```
+    pub fn spawn_blocking(self) -> BlockingScope<T> {
+        let (sender, mut receiver) = mpsc::unbounded_channel::<Request<T>>();
+        let mut scope = self;
+        tokio::task::spawn_blocking(move || {
+            while let Some(request) = receiver.blocking_recv() {
+                request(&mut scope);
+            }
+        });
+        BlockingScope { sender }
+    }
+}
+
+impl<T> BlockingScope<T>
+where
+    T: for<'a> Family<'a> + 'static,
+{
+    /// Enters the scope in its blocking task, returning the value extracted by `f` from the frozen data.
+    ///
+    /// # Panics
+    ///
+    /// - If `f` panics, the panic is resumed in the caller.
+    /// - If the underlying future panics, the panic is resumed in the caller, and the scope is then poisoned.
+    /// - In the same cases as [`BoxScope::enter`].
+    /// - If the Tokio runtime was shut down, which stops the blocking task.
+    pub async fn enter<Output, G>(&self, f: G) -> Output
+    where
+        G: for<'a, 'b> FnOnce(&'b mut <T as Family<'a>>::Family) -> Output + Send + 'static,
+        Output: Send + 'static,
+    {
+        match self.try_enter(f).await {
+            Ok(output) => output,
+            Err(err) => panics::scope_error(err),
+        }
+    }
+
+    /// Enters the scope in its blocking task, returning the value extracted by `f` from the frozen data.
+    ///
+    /// # Errors
+    ///
+    /// - In the same cases as [`BoxScope::try_enter`].
+    ///
+    /// # Panics
+    ///
+    /// - If `f` panics, the panic is resumed in the caller.
+    /// - If the underlying future panics, the panic is resumed in the caller, and the scope is then poisoned.
+    /// - If the Tokio runtime was shut down, which stops the blocking task.
+    pub async fn try_enter<Output, G>(&self, f: G) -> Result<Output, ScopeError>
+    where
+        G: for<'a, 'b> FnOnce(&'b mut <T as Family<'a>>::Family) -> Output + Send + 'static,
+        Output: Send + 'static,
+    {
+        let (reply_sender, reply) = oneshot::channel::<Reply<Output>>();
+        let request: Request<T> = Box::new(move |scope| {
+            // the scope is poisoned if its future panics, and the panic is resumed by the caller.
+            let output = panic::catch_unwind(AssertUnwindSafe(|| scope.try_enter(f)));
+            // the caller may have stopped waiting for the reply.
+            let _ = reply_sender.send(output);
+        });
+        if self.sender.send(request).is_err() {
+            panics::blocking_task_stopped();
+        }
+        match reply.await {
+            Ok(Ok(output)) => output,
+            Ok(Err(payload)) => panic::resume_unwind(payload),
+            Err(_) => panics::blocking_task_stopped(),
+        }
+    }
+}
+
+impl<T> Clone for BlockingScope<T>
+where
+    T: for<'a> Family<'a> + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+impl<T> core::fmt::Debug for BlockingScope<T>
+where
+    T: for<'a> Family<'a> + 'static,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BlockingScope")
+            .field("stopped", &self.sender.is_closed())
+            .finish_non_exhaustive()
+    }
+}
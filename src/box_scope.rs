@@ -1,41 +1,148 @@
-use alloc::boxed::Box;
 use core::{
+    alloc::Layout,
     future::Future,
     mem::{self, MaybeUninit},
-    ptr::NonNull,
+    ptr::{self, NonNull},
+    task::{Context, Poll},
 };
 
-use crate::{raw_scope::RawScope, Family, Never, TopScope};
+#[cfg(feature = "serde")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+use crate::TimedOut;
+use crate::{
+    allocator::{Allocator, Global},
+    panics,
+    raw_scope::RawScope,
+    scope::Scope,
+    waker, Completed, Family, Never, ResumeFamily, ScopeError, StepLimitExceeded, TagFamily,
+    TopScope,
+};
+
+/// The result of polling a scope: the output of the passed function, the output of the completed future, or an error.
+type EnterResult<Output, O> = Result<Result<Output, Completed<O>>, ScopeError>;
 
 /// A dynamic scope tied to a Box.
 ///
 /// This kind of scopes uses a dynamic allocation.
 /// In exchange, it is fully `'static` and can be moved after creation.
-#[repr(transparent)]
-pub struct BoxScope<T, F: ?Sized = dyn Future<Output = Never> + 'static>(
+///
+/// The underlying future of most scopes never completes, so its output is [`Never`].
+/// Scopes whose future completes with an output can be created with [`BoxScope::new_with_output`].
+///
+/// The memory is allocated with the global allocator, unless the scope is created with [`BoxScope::new_in`].
+///
+/// # Drop
+///
+/// Dropping a `BoxScope` drops its underlying future in place, then deallocates its memory.
+/// If the future is suspended at a freeze point, dropping it runs the destructors of its locals
+/// in the reverse order of their declaration, exactly as if the async block returned at the freeze point:
+/// data borrowed by the frozen value is therefore always dropped after the frozen value itself.
+/// Values moved into the scope are dropped after its locals, as they are owned by the future rather than declared in it.
+/// The allocator is dropped last, after the memory was deallocated.
+/// If dropping the future panics, the memory is still deallocated before the panic propagates.
+/// The pointer to the frozen data stored by the scope has no destructor, and is never read after the future is dropped.
+///
+/// The future is not dropped in place if it was already dropped by [`BoxScope::reset`] and could not be replaced.
+///
+/// # Unwind safety
+///
+/// A `BoxScope` is [`UnwindSafe`](core::panic::UnwindSafe) and [`RefUnwindSafe`](core::panic::RefUnwindSafe)
+/// when its allocator and the frozen data are, regardless of its future:
+/// a panic in the underlying future poisons the scope, so that the future is never polled again
+/// and its broken invariants cannot be observed.
+/// A panic in the function passed to [`BoxScope::enter`] does not poison the scope, hence the bound on the frozen data.
+pub struct BoxScope<T, F: ?Sized = dyn Future<Output = Never> + 'static, A = Global>(
     core::ptr::NonNull<RawScope<T, F>>,
+    A,
 )
 where
     T: for<'a> Family<'a>,
-    F: Future<Output = Never>;
+    F: Future,
+    A: Allocator;
+
+// SAFETY: a `BoxScope` uniquely owns its `RawScope`. Its `state` only ever points to data that is
+// either owned by the future or borrowed by the future, so sending the future to another thread
+// also sends the frozen data along with it.
+// The pointer is only dereferenced in `BoxScope::enter` and `BoxScope::try_enter`, that take `&mut self`,
+// so it is never accessed from two threads simultaneously.
+unsafe impl<T, F: ?Sized, A> Send for BoxScope<T, F, A>
+where
+    T: for<'a> Family<'a>,
+    F: Future + Send,
+    A: Allocator + Send,
+    for<'a> <T as Family<'a>>::Family: Send,
+{
+}
+
+// The future is poisoned when it panics, so it is never polled again after a panic.
+// The frozen data is exclusively borrowed by the functions passed to `enter`, that can panic without poisoning the scope.
+impl<T, F: ?Sized, A> core::panic::UnwindSafe for BoxScope<T, F, A>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator + core::panic::UnwindSafe,
+    for<'a> <T as Family<'a>>::Family: core::panic::UnwindSafe,
+{
+}
+
+impl<T, F: ?Sized, A> core::panic::RefUnwindSafe for BoxScope<T, F, A>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator + core::panic::RefUnwindSafe,
+    for<'a> <T as Family<'a>>::Family: core::panic::RefUnwindSafe,
+{
+}
+
+impl<T, F: ?Sized, A> core::fmt::Debug for BoxScope<T, F, A>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BoxScope")
+            .field("poisoned", &self.is_poisoned())
+            .field("active", &self.is_active())
+            .finish_non_exhaustive()
+    }
+}
 
-impl<T, F: ?Sized> Drop for BoxScope<T, F>
+impl<T, F: ?Sized, A> Drop for BoxScope<T, F, A>
 where
     T: for<'a> Family<'a>,
-    F: Future<Output = Never>,
+    F: Future,
+    A: Allocator,
 {
     fn drop(&mut self) {
-        // SAFETY: this `Box::from_raw` pairs with a `Box::into_raw`
-        // in the `new` constructor. The type `F` is not the same,
-        // but `MaybeUninit<F>` and `F` are repr(transparent)-compatible
-        // and RawScope is repr(C), so the Box frees the same memory.
-        // Furthermore, the `new` constructor ensured that F is properly
-        // initialized so it may be dropped.
-        //
-        // Finally, the drop order of implicitly first dropping self.0.state
-        // and THEN self.0.active_fut goes a bit against the typical self-referencing
-        // structs assumptions, however self.0.state is a pointer and has no drop glue.
-        drop(unsafe { Box::from_raw(self.0.as_ptr()) })
+        // SAFETY: the `new_with_output_in` constructor ensured that `self.0` is fully initialized.
+        let layout = Layout::for_value(unsafe { self.0.as_ref() });
+        struct Guard<'alloc, A: Allocator> {
+            ptr: NonNull<u8>,
+            layout: Layout,
+            alloc: &'alloc A,
+        }
+        // guard ensures the memory is freed even if dropping the future panics
+        let _dealloc_guard = Guard {
+            ptr: self.0.cast(),
+            layout,
+            alloc: &self.1,
+        };
+        impl<'alloc, A: Allocator> Drop for Guard<'alloc, A> {
+            fn drop(&mut self) {
+                // SAFETY: this deallocation pairs with the allocation in the `new_with_output_in`
+                // constructor. The type `F` is not the same, but `MaybeUninit<F>` and `F` are
+                // repr(transparent)-compatible and RawScope is repr(C), so the layout is the same.
+                // The guard is dropped once the future was dropped, or unwound out of its destructor,
+                // so the memory is no longer used.
+                unsafe { self.alloc.deallocate(self.ptr, self.layout) }
+            }
+        }
+        // SAFETY: the `new_with_output_in` constructor ensured that `self.0` is fully
+        // initialized so it may be dropped, and it is only deallocated afterwards, by the guard.
+        unsafe { RawScope::drop_in_place(self.0) };
     }
 }
 
@@ -50,6 +157,10 @@ where
     ///
     /// If the `Future` generic type can be inferred, it can be more efficient to use [`BoxScope::new`].
     ///
+    /// The future must be `'static`, as the default future type of a [`BoxScope`] is a `'static` trait object:
+    /// the scope cannot capture borrows of shorter-lived data, see the [counterexamples](crate::counterexamples).
+    /// Move the data into the scope instead, or use [`BoxScope::new`] to keep the lifetime of the borrow in the type.
+    ///
     /// # Panics
     ///
     /// - If `scope` panics.
@@ -58,46 +169,360 @@ where
         S::Future: 'static,
     {
         let this = mem::ManuallyDrop::new(BoxScope::new(scope));
-        Self(this.0)
+        Self(this.0, Global)
+    }
+
+    /// Ties the passed scope to the heap, aborting the process if its future panics.
+    ///
+    /// This function erased the `Future` generic type of the [`TopScope`], like [`BoxScope::new_dyn`].
+    ///
+    /// A panic unwinding out of the underlying future while it is polled, including while it freezes,
+    /// aborts the process instead of poisoning the scope.
+    /// This trades the ability to recover from such panics for the guarantee that the scope is never observed
+    /// after its future was left partially executed.
+    ///
+    /// Panics in the functions passed to [`BoxScope::enter`] unwind as usual, as the future is not running when they are called.
+    ///
+    /// # Panics
+    ///
+    /// - If `scope` panics.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, SingleFamily};
+    ///
+    /// let mut scope = BoxScope::<SingleFamily<u32>>::new_abort_on_panic(scope!({
+    ///     let mut x = 0u32;
+    ///     loop {
+    ///         freeze!(&mut x);
+    ///         x += 1;
+    ///     }
+    /// }));
+    /// assert_eq!(scope.enter(|x| *x), 0);
+    /// ```
+    pub fn new_abort_on_panic<S: TopScope<Family = T>>(scope: S) -> Self
+    where
+        S::Future: 'static,
+    {
+        let this = Self::new_dyn(scope);
+        // SAFETY:
+        // 1. `this.0` is fully initialized by the constructor.
+        // 2. `this` is not shared yet.
+        unsafe { RawScope::set_abort_on_panic(this.0) };
+        this
+    }
+
+    /// Ties the passed scope to the heap, and runs its future until it freezes for the first time.
+    ///
+    /// Contrary to [`BoxScope::new_dyn`], a panic while running the scope or its underlying future
+    /// until the first freeze is caught, and its payload is returned instead of a poisoned scope.
+    ///
+    /// The first call to [`BoxScope::enter`] then enters the data frozen in this first freeze,
+    /// without polling the underlying future. If the underlying future awaits for a future other than
+    /// the [`crate::FrozenFuture`] before freezing, the next call to [`BoxScope::enter`] polls it again as usual.
+    ///
+    /// # Errors
+    ///
+    /// - The panic payload if running the scope or its underlying future panicked before the first freeze.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, SingleFamily};
+    ///
+    /// let mut scope = BoxScope::<SingleFamily<u32>>::try_new(scope!({
+    ///     let mut x: u32 = "42".parse().unwrap();
+    ///     loop {
+    ///         freeze!(&mut x);
+    ///         x += 1;
+    ///     }
+    /// }))
+    /// .unwrap();
+    /// assert_eq!(scope.enter(|x| *x), 42);
+    /// assert_eq!(scope.enter(|x| *x), 43);
+    ///
+    /// let result = BoxScope::<SingleFamily<u32>>::try_new(scope!({
+    ///     let mut x: u32 = "not a number".parse().unwrap();
+    ///     freeze_forever!(&mut x)
+    /// }));
+    /// assert!(result.is_err());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn try_new<S: TopScope<Family = T>>(
+        scope: S,
+    ) -> Result<Self, Box<dyn core::any::Any + Send>>
+    where
+        S::Future: 'static,
+    {
+        // the scope is dropped without being observed if it panics, so it cannot be observed in a broken state.
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let this = Self::new_dyn(scope);
+            // the scope is entered as usual if the future did not freeze.
+            // SAFETY:
+            // 1. `this.0` is fully initialized by the constructor.
+            // 2. `this.0` is heap allocated and never moved.
+            // 3. `this` is not shared yet.
+            let _ = unsafe { RawScope::prime(this.0, &mut Context::from_waker(&waker::NOOP)) };
+            this
+        }))
+    }
+}
+
+impl<T> BoxScope<T, dyn Future<Output = Never> + Send + 'static>
+where
+    T: for<'a> Family<'a>,
+{
+    /// Ties the passed scope to the heap.
+    ///
+    /// This function erased the `Future` generic type of the [`TopScope`], like [`BoxScope::new_dyn`],
+    /// while retaining the fact that it is `Send`.
+    ///
+    /// # Panics
+    ///
+    /// - If `scope` panics.
+    pub fn new_dyn_send<S: TopScope<Family = T>>(scope: S) -> Self
+    where
+        S::Future: Send + 'static,
+    {
+        let this = mem::ManuallyDrop::new(BoxScope::new(scope));
+        Self(this.0, Global)
+    }
+}
+
+impl<T, O> BoxScope<T, dyn Future<Output = O> + 'static>
+where
+    T: for<'a> Family<'a>,
+{
+    /// Ties the passed scope, whose future completes with an output of type `O`, to the heap.
+    ///
+    /// This function erased the `Future` generic type of the [`Scope`], like [`BoxScope::new_dyn`].
+    /// See [`BoxScope::new_with_output`] for more information on scopes with an output.
+    ///
+    /// # Panics
+    ///
+    /// - If `scope` panics.
+    pub fn new_dyn_with_output<S: Scope<Family = T, Output = O>>(scope: S) -> Self
+    where
+        S::Future: 'static,
+    {
+        let this = mem::ManuallyDrop::new(BoxScope::new_with_output(scope));
+        Self(this.0, Global)
+    }
+}
+
+impl<T, A> BoxScope<T, dyn Future<Output = Never> + 'static, A>
+where
+    T: for<'a> Family<'a>,
+    A: Allocator,
+{
+    /// Ties the passed scope to memory allocated in `alloc`.
+    ///
+    /// This function erased the `Future` generic type of the [`TopScope`], like [`BoxScope::new_dyn`].
+    /// See [`BoxScope::new_in`] for more information on custom allocators.
+    ///
+    /// # Panics
+    ///
+    /// - If `scope` panics.
+    /// - If the memory cannot be allocated.
+    pub fn new_dyn_in<S: TopScope<Family = T>>(scope: S, alloc: A) -> Self
+    where
+        S::Future: 'static,
+    {
+        let this = mem::ManuallyDrop::new(BoxScope::new_in(scope, alloc));
+        // SAFETY: `this` is never dropped, so the allocator is moved out of it exactly once.
+        Self(this.0, unsafe { ptr::read(&this.1) })
     }
 }
 
+/// Ties the passed scope to the heap, and passes it to `body`.
+///
+/// Like [`std::thread::scope`], this is meant for scopes used "here and now": the scope is dropped when `body` returns,
+/// and the reference passed to `body` cannot escape it.
+/// Contrary to [`crate::stack_scope`], the scope is allocated, and its future may complete with an output,
+/// see [`BoxScope::new_with_output`].
+///
+/// The scope is passed as a [`scope!`](crate::scope!) rather than as a closure taking a [`crate::TimeCapsule`],
+/// as only the macro checks that the scope freezes at its top level.
+///
+/// # Panics
+///
+/// - If `scope` panics.
+/// - If `body` panics.
+///
+/// # Example
+///
+/// ```
+/// use nolife::{scope, scope_fn, SingleFamily};
+///
+/// let values = scope_fn(
+///     scope!(SingleFamily<u32>, {
+///         for mut x in 0..3 {
+///             freeze!(&mut x);
+///         }
+///         "done"
+///     }),
+///     |scope| {
+///         let mut values = Vec::new();
+///         while let Ok(x) = scope.enter_or_complete(|x| *x) {
+///             values.push(x);
+///         }
+///         values
+///     },
+/// );
+/// assert_eq!(values, [0, 1, 2]);
+/// ```
+pub fn scope_fn<T, S, Output>(
+    scope: S,
+    body: impl FnOnce(&mut BoxScope<T, S::Future>) -> Output,
+) -> Output
+where
+    T: for<'a> Family<'a>,
+    S: Scope<Family = T>,
+{
+    body(&mut BoxScope::new_with_output(scope))
+}
+
 impl<T, F> BoxScope<T, F>
 where
     T: for<'a> Family<'a>,
-    F: Future<Output = Never>,
+    F: Future,
 {
     /// Ties the passed scope to the heap.
     ///
     /// This function retains the `Future` generic type from the [`TopScope`].
     /// To store the [`BoxScope`] in a struct, it can be easier to use [`BoxScope::new_dyn`].
     ///
+    /// The future does not need to be `'static`: if it borrows data from its environment, the resulting scope
+    /// is bound by the lifetime of the borrow, and cannot outlive the borrowed data.
+    ///
     /// # Panics
     ///
     /// - If `scope` panics.
     pub fn new<S: TopScope<Family = T, Future = F>>(scope: S) -> BoxScope<T, F> {
-        let raw_scope = Box::new(RawScope::<T, F>::new_uninit());
-        let raw_scope: *mut RawScope<T, MaybeUninit<F>> = Box::into_raw(raw_scope);
-        struct Guard<Sc> {
+        Self::new_with_output(scope)
+    }
+
+    /// Ties the passed scope, whose future completes with an output, to the heap.
+    ///
+    /// Contrary to a [`TopScope`], the future of `scope` can complete after freezing the scope a few times.
+    /// Use [`Self::enter_or_complete`] to retrieve its output.
+    ///
+    /// Once the future has completed, it is never polled again:
+    ///
+    /// - The first call to [`Self::enter_or_complete`] that observes the completion returns the output.
+    /// - Any subsequent call to [`Self::enter`] or [`Self::enter_or_complete`] panics,
+    ///   and any subsequent call to [`Self::try_enter`] returns [`ScopeError::Completed`].
+    ///
+    /// # Panics
+    ///
+    /// - If `scope` panics.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, Completed, SingleFamily};
+    ///
+    /// let mut scope = BoxScope::<SingleFamily<u32>, _>::new_with_output(scope!({
+    ///     let mut sum = 0;
+    ///     for mut x in 0..3 {
+    ///         freeze!(&mut x);
+    ///         sum += x;
+    ///     }
+    ///     sum
+    /// }));
+    ///
+    /// assert_eq!(scope.enter_or_complete(|x| *x), Ok(0));
+    /// assert_eq!(scope.enter_or_complete(|x| *x), Ok(1));
+    /// assert_eq!(scope.enter_or_complete(|x| *x), Ok(2));
+    /// assert_eq!(scope.enter_or_complete(|x| *x), Err(Completed(3)));
+    /// ```
+    pub fn new_with_output<S: Scope<Family = T, Future = F>>(scope: S) -> BoxScope<T, F> {
+        Self::new_with_output_in(scope, Global)
+    }
+}
+
+impl<T, F, A> BoxScope<T, F, A>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+{
+    /// Ties the passed scope to memory allocated in `alloc`.
+    ///
+    /// This is the same as [`BoxScope::new`], except that the memory is allocated with `alloc` rather than the global allocator.
+    /// The scope keeps the allocator around to free the memory when dropped.
+    ///
+    /// # Panics
+    ///
+    /// - If `scope` panics.
+    /// - If the memory cannot be allocated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{allocator::Global, scope, BoxScope, SingleFamily};
+    ///
+    /// let mut scope = BoxScope::<SingleFamily<u32>, _, _>::new_in(
+    ///     scope!({ freeze_forever!(&mut 42) }),
+    ///     &Global,
+    /// );
+    /// assert_eq!(scope.enter(|x| *x), 42);
+    /// ```
+    pub fn new_in<S: TopScope<Family = T, Future = F>>(scope: S, alloc: A) -> BoxScope<T, F, A> {
+        Self::new_with_output_in(scope, alloc)
+    }
+
+    /// Ties the passed scope, whose future completes with an output, to memory allocated in `alloc`.
+    ///
+    /// See [`BoxScope::new_with_output`] and [`BoxScope::new_in`].
+    ///
+    /// # Panics
+    ///
+    /// - If `scope` panics.
+    /// - If the memory cannot be allocated.
+    pub fn new_with_output_in<S: Scope<Family = T, Future = F>>(
+        scope: S,
+        alloc: A,
+    ) -> BoxScope<T, F, A> {
+        let layout = Layout::new::<RawScope<T, MaybeUninit<F>>>();
+        let raw_scope: *mut RawScope<T, MaybeUninit<F>> = match alloc.allocate(layout) {
+            Ok(raw_scope) => raw_scope.cast().as_ptr(),
+            Err(_) => alloc::alloc::handle_alloc_error(layout),
+        };
+        // SAFETY: `raw_scope` was allocated with the layout of a `RawScope<T, MaybeUninit<F>>`.
+        unsafe {
+            raw_scope.write(RawScope::<T, F>::new_uninit());
+        }
+        struct Guard<'alloc, Sc, A: Allocator> {
             raw_scope: *mut Sc,
+            alloc: &'alloc A,
         }
-        // guard ensures Box is freed on panic (i.e. if scope.run panics)
-        let panic_guard = Guard { raw_scope };
-        impl<Sc> Drop for Guard<Sc> {
+        // guard ensures the memory is freed on panic (i.e. if scope.run panics)
+        let panic_guard = Guard {
+            raw_scope,
+            alloc: &alloc,
+        };
+        impl<'alloc, Sc, A: Allocator> Drop for Guard<'alloc, Sc, A> {
             fn drop(&mut self) {
                 // SAFETY: defuse below makes sure this only happens on panic,
                 // in this case, self.raw_scope is still in the same uninitialized state
-                // and not otherwise being cleaned up, so this `Box::from_raw` pairs with
-                // `Box::into_raw` above
-                drop(unsafe { Box::from_raw(self.raw_scope) })
+                // and not otherwise being cleaned up, so this deallocation pairs with
+                // the allocation above. `RawScope<T, MaybeUninit<F>>` has no drop glue.
+                unsafe {
+                    self.alloc.deallocate(
+                        NonNull::new_unchecked(self.raw_scope).cast(),
+                        Layout::new::<Sc>(),
+                    )
+                }
             }
         }
 
         let raw_scope: *mut RawScope<T, F> = raw_scope.cast();
 
         // SAFETY:
-        // 1. `raw_scope` allocated by the `Box` so is valid memory, although the future is not yet initialized
-        // 2. `raw_scope` was created from a valid `RawScope::<T, MaybeUninit<F>>`, so `state` is fully initialized.
+        // 1. `raw_scope` allocated by `alloc` so is valid memory, although the future is not yet initialized
+        // 2. `raw_scope` was initialized with a valid `RawScope::<T, MaybeUninit<F>>`, so `state` is fully initialized.
         //
         // Note: as a post-condition of `RawScope`, `raw_scope` is fully initialized.
         unsafe {
@@ -107,23 +532,181 @@ where
         mem::forget(panic_guard); // defuse guard
                                   // (guard field has no drop glue, so this does not leak anything, it just skips the above `Drop` impl)
 
-        // SAFETY: `raw_scope` allocated by the `Box` so is non-null.
-        BoxScope(unsafe { NonNull::new_unchecked(raw_scope) })
+        // SAFETY: `raw_scope` allocated by `alloc` so is non-null.
+        BoxScope(unsafe { NonNull::new_unchecked(raw_scope) }, alloc)
+    }
+}
+
+impl<T, F, A> BoxScope<T, F, A>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+{
+    /// Replaces the underlying future with the future of `scope`, reusing the allocation of this scope.
+    ///
+    /// The current future is dropped, and the scope is no longer poisoned, completed nor cancelled.
+    /// This makes it possible to reuse a scope, or to recover a scope after its future panicked.
+    ///
+    /// `scope` must produce the same `Future` type as the scope that created this one,
+    /// which is typically the case for scopes returned by the same function.
+    /// This is never the case for scopes whose `Future` type is erased, such as those created with [`BoxScope::new_dyn`]:
+    /// these scopes need to be replaced with a new scope, reallocating memory.
+    ///
+    /// # Panics
+    ///
+    /// - If dropping the current future panics.
+    /// - If `scope` panics.
+    ///
+    /// In both cases, the scope is left poisoned, and can still be reset.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, SingleFamily, TopScope};
+    ///
+    /// fn counter(start: u32) -> impl TopScope<Family = SingleFamily<u32>> {
+    ///     scope!({
+    ///         let mut x = start;
+    ///         loop {
+    ///             freeze!(&mut x);
+    ///             x += 1;
+    ///         }
+    ///     })
+    /// }
+    ///
+    /// let mut scope = BoxScope::new(counter(0));
+    /// assert_eq!(scope.enter(|x| *x), 0);
+    /// assert_eq!(scope.enter(|x| *x), 1);
+    ///
+    /// scope.reset(counter(10));
+    /// assert_eq!(scope.enter(|x| *x), 10);
+    /// ```
+    pub fn reset<S: Scope<Family = T, Future = F>>(&mut self, scope: S) {
+        // SAFETY:
+        // 1. `self.0` is valid as a post-condition of `new`.
+        // 2. The object pointed to by `self.0` did not move and won't before deallocation.
+        // 3. `BoxScope::reset` takes an exclusive reference, so no reference to the frozen value can exist.
+        unsafe { RawScope::reset(self.0, scope) }
     }
 }
 
-impl<T, F: ?Sized> BoxScope<T, F>
+impl<T, F: ?Sized, A> BoxScope<T, F, A>
 where
     T: for<'a> Family<'a>,
-    F: Future<Output = Never>,
+    F: Future,
+    A: Allocator,
 {
+    /// Returns the pointer to the underlying raw scope.
+    pub(crate) fn raw_scope(&self) -> NonNull<RawScope<T, F>> {
+        self.0
+    }
+
+    /// Returns a reference to the allocator of the scope.
+    pub fn allocator(&self) -> &A {
+        &self.1
+    }
+
     /// Enters the scope, making it possible to access the data frozen inside of the scope.
     ///
+    /// Each call polls the underlying future exactly once, running it from its previous freeze to its next one.
+    ///
+    /// The passed function is a [`FnOnce`], called at most once, so it can move its captures out through its output.
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, SingleFamily};
+    ///
+    /// let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+    ///     freeze_forever!(&mut 1)
+    /// }));
+    ///
+    /// let label = String::from("value");
+    /// let (label, value) = scope.enter(move |x| (label, *x));
+    /// assert_eq!((label.as_str(), value), ("value", 1));
+    /// ```
+    ///
+    /// # Borrowing in the output
+    ///
+    /// The output of the passed function can borrow:
+    ///
+    /// - Data provided by the caller, for as long as the caller keeps it alive, regardless of the scope.
+    /// - The frozen data, for the lifetime `'borrow` only: the scope then stays borrowed while the output is in use.
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, RefFamily};
+    ///
+    /// fn find<'ret>(scope: &mut BoxScope<RefFamily<str>>, words: &'ret [String]) -> Option<&'ret str> {
+    ///     scope.enter(|line| words.iter().map(String::as_str).find(|word| word == line))
+    /// }
+    ///
+    /// let mut scope = BoxScope::<RefFamily<str>>::new_dyn(scope!({
+    ///     let line = String::from("world");
+    ///     freeze_forever!(&mut line.as_str())
+    /// }));
+    /// let words = vec![String::from("hello"), String::from("world")];
+    /// let found = find(&mut scope, &words);
+    /// // `found` borrows `words`, not the scope
+    /// drop(scope);
+    /// assert_eq!(found, Some("world"));
+    /// ```
+    ///
+    /// Mixing both in an output that should outlive the scope is rejected at compile time,
+    /// see the [counterexamples](crate::counterexamples).
+    ///
+    /// # Owned results
+    ///
+    /// Values built from the frozen data, such as boxed trait objects or iterators over cloned data,
+    /// do not borrow the scope and can be returned as any other output. The type of the output is inferred
+    /// from the passed function, so converting the output to a trait object requires annotating the binding
+    /// or the return type of the function:
+    ///
+    /// ```
+    /// use std::fmt::Display;
+    /// use nolife::{scope, BoxScope, RefFamily};
+    ///
+    /// let mut scope = BoxScope::<RefFamily<str>>::new_dyn(scope!({
+    ///     let line = String::from("a b c");
+    ///     freeze_forever!(&mut line.as_str())
+    /// }));
+    ///
+    /// let shown: Box<dyn Display> = scope.enter(|line| Box::new(line.to_uppercase()));
+    /// let words = scope.enter(|line| line.split(' ').map(String::from).collect::<Vec<_>>().into_iter());
+    /// drop(scope);
+    /// assert_eq!(shown.to_string(), "A B C");
+    /// assert_eq!(words.rev().collect::<Vec<_>>(), ["c", "b", "a"]);
+    /// ```
+    ///
     /// # Panics
     ///
     /// - If the passed function panics.
     /// - If the underlying future panics.
+    /// - If the underlying future panicked in a previous call to [`Self::enter`] or [`Self::try_enter`].
     /// - If the underlying future awaits for a future other than the [`crate::FrozenFuture`].
+    /// - If the underlying future completed, see [`Self::enter_or_complete`].
+    /// - If the underlying future froze its data with [`crate::TimeCapsule::freeze_ref`], see [`Self::enter_ref`].
+    ///
+    /// See [`Self::try_enter`] for a version of this function that reports errors with the underlying future
+    /// instead of panicking.
+    ///
+    /// # Nesting
+    ///
+    /// Distinct scopes can be entered from the function passed to `enter`, and their frozen data used together.
+    /// Entering the same scope again from the passed function is rejected at compile time, as it is borrowed
+    /// exclusively for the duration of the call.
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, SingleFamily};
+    ///
+    /// let mut numbers = BoxScope::<SingleFamily<Vec<u32>>>::new_dyn(scope!({
+    ///     freeze_forever!(&mut vec![1, 2, 3])
+    /// }));
+    /// let mut total = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+    ///     freeze_forever!(&mut 0)
+    /// }));
+    ///
+    /// numbers.enter(|numbers| total.enter(|total| *total = numbers.iter().sum()));
+    /// assert_eq!(total.enter(|total| *total), 6);
+    /// ```
     pub fn enter<'borrow, Output, G>(&'borrow mut self, f: G) -> Output
     where
         G: for<'a> FnOnce(&'borrow mut <T as Family<'a>>::Family) -> Output,
@@ -134,4 +717,1101 @@ where
         // 3. `BoxScope::enter` takes an exclusive reference and the reference passed to `f` cannot escape `f`.
         unsafe { RawScope::enter(self.0, f) }
     }
+
+    /// Enters the scope, making it possible to access the data frozen inside of the scope,
+    /// and resumes the scope with the value returned by `f`.
+    ///
+    /// This is meant for scopes that froze with [`crate::TimeCapsule::freeze_yield`], which then resolves to the returned value.
+    /// If the scope froze otherwise, the returned value is dropped. See [`ResumeFamily`] for an example.
+    ///
+    /// # Panics
+    ///
+    /// - If the passed function panics.
+    /// - If the underlying future panics.
+    /// - If the underlying future panicked in a previous call.
+    /// - If the underlying future awaits for a future other than the [`crate::FrozenFuture`] or [`crate::FreezeYieldFuture`].
+    /// - If the underlying future completed, see [`Self::enter_or_complete`].
+    /// - If the underlying future froze its data with [`crate::TimeCapsule::freeze_ref`].
+    pub fn enter_yield<'borrow, G>(&'borrow mut self, f: G)
+    where
+        T: ResumeFamily,
+        G: for<'a> FnOnce(&'borrow mut <T as Family<'a>>::Family) -> T::Resume,
+    {
+        // SAFETY:
+        // 1. `self.0` is valid as a post-condition of `new`.
+        // 2. The object pointed to by `self.0` did not move and won't before deallocation.
+        // 3. `BoxScope::enter_yield` takes an exclusive reference and the reference passed to `f` cannot escape `f`.
+        unsafe { RawScope::enter_yield(self.0, f) }
+    }
+
+    /// Enters the scope, making it possible to access the data frozen inside of the scope and the tag it reported.
+    ///
+    /// See [`crate::TagFamily`] and [`crate::TimeCapsule::freeze_tagged`].
+    ///
+    /// # Panics
+    ///
+    /// - If the underlying future did not freeze with [`crate::TimeCapsule::freeze_tagged`], so that no tag is available.
+    /// - In the same cases as [`Self::enter`].
+    pub fn enter_tagged<'borrow, Output, G>(&'borrow mut self, f: G) -> Output
+    where
+        T: TagFamily,
+        T::Tag: 'borrow,
+        G: for<'a> FnOnce(&'borrow mut <T as Family<'a>>::Family, &'borrow T::Tag) -> Output,
+    {
+        // SAFETY:
+        // 1. `self.0` is valid as a post-condition of `new`.
+        // 2. The object pointed to by `self.0` did not move and won't before deallocation.
+        // 3. `BoxScope::enter_tagged` takes an exclusive reference and the references passed to `f` cannot escape `f`.
+        unsafe { RawScope::enter_tagged(self.0, f) }
+    }
+
+    /// Enters the scope if the frozen data satisfies `pred`, returning `None` without entering it otherwise.
+    ///
+    /// `pred` sees the data that the next call to [`Self::enter`] would access: if the underlying future was not already
+    /// polled to its next freeze, it is polled once, as by `enter`. If `pred` returns `true`, `f` is called on the same data,
+    /// without polling the future again. Otherwise, the data remains frozen, and the next call to `enter_if`,
+    /// [`Self::enter`] or one of its variants accesses it again without polling the underlying future.
+    ///
+    /// # Panics
+    ///
+    /// - If `pred` panics. The data remains frozen, as when it returns `false`.
+    /// - In the same cases as [`Self::enter`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, SingleFamily};
+    ///
+    /// let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+    ///     let mut x = 0u32;
+    ///     loop {
+    ///         freeze!(&mut x);
+    ///         x += 1;
+    ///     }
+    /// }));
+    ///
+    /// assert_eq!(scope.enter_if(|x| *x % 2 == 1, |x| *x), None);
+    /// // the underlying future did not advance
+    /// assert_eq!(scope.enter_if(|x| *x % 2 == 0, |x| *x), Some(0));
+    /// assert_eq!(scope.enter_if(|x| *x % 2 == 1, |x| *x), Some(1));
+    /// ```
+    pub fn enter_if<'borrow, Output, P, G>(&'borrow mut self, pred: P, f: G) -> Option<Output>
+    where
+        P: for<'a> FnOnce(&<T as Family<'a>>::Family) -> bool,
+        G: for<'a> FnOnce(&'borrow mut <T as Family<'a>>::Family) -> Output,
+    {
+        // SAFETY:
+        // 1. `self.0` is valid as a post-condition of `new`.
+        // 2. The object pointed to by `self.0` did not move and won't before deallocation.
+        // 3. `BoxScope::enter_if` takes an exclusive reference, and `pred` cannot retain the frozen data.
+        unsafe {
+            if !RawScope::is_primed(self.0) {
+                if let Err(err) = RawScope::prime(self.0, &mut Context::from_waker(&waker::NOOP)) {
+                    panics::scope_error(err);
+                }
+            }
+            if RawScope::inspect_primed(self.0, pred) != Some(true) {
+                return None;
+            }
+        }
+        // the scope is primed, so this does not poll the underlying future again.
+        Some(self.enter(f))
+    }
+
+    /// Drives the underlying future to its next freeze without accessing the frozen data,
+    /// so that the next call to [`Self::enter`] or one of its variants accesses it without polling the future again.
+    ///
+    /// If the data of a previous call to `step` or [`Self::enter_if`] was not accessed since, it is skipped.
+    ///
+    /// # Errors
+    ///
+    /// - [`ScopeError::Completed`] if the underlying future completes instead of freezing, dropping its output.
+    ///   The scope then behaves as any completed scope.
+    /// - [`ScopeError::Poisoned`] if the underlying future panicked in a previous call, without polling it.
+    /// - [`ScopeError::Cancelled`] if the scope was [cancelled](Self::cancel).
+    /// - [`ScopeError::NotFrozen`] if the underlying future awaits for a future other than the [`crate::FrozenFuture`].
+    ///   The next call polls the future again.
+    ///
+    /// A future that froze with [`crate::TimeCapsule::freeze_ref`] is stepped as usual, and its data can be read
+    /// with [`Self::enter_ref`].
+    ///
+    /// # Panics
+    ///
+    /// - If the underlying future panics. The scope is then poisoned, and [`Self::is_poisoned`] returns `true`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, ScopeError, SingleFamily};
+    ///
+    /// let mut scope = BoxScope::<SingleFamily<u32>, _>::new_with_output(scope!({
+    ///     for mut x in 0..3u32 {
+    ///         freeze!(&mut x);
+    ///     }
+    /// }));
+    ///
+    /// assert_eq!(scope.step(), Ok(()));
+    /// assert_eq!(scope.step(), Ok(()));
+    /// // the future did not advance when entering
+    /// assert_eq!(scope.enter(|x| *x), 1);
+    /// assert_eq!(scope.step(), Ok(()));
+    /// assert_eq!(scope.step(), Err(ScopeError::Completed));
+    /// assert!(!scope.is_poisoned());
+    /// ```
+    pub fn step(&mut self) -> Result<(), ScopeError> {
+        // SAFETY:
+        // 1. `self.0` is valid as a post-condition of `new`.
+        // 2. The object pointed to by `self.0` did not move and won't before deallocation.
+        // 3. `BoxScope::step` takes an exclusive reference.
+        unsafe { RawScope::step(self.0, &mut Context::from_waker(&waker::NOOP)) }
+    }
+
+    /// Enters the scope, calling `on_first` if the frozen data comes from the first freeze of the underlying future,
+    /// and `on_subsequent` otherwise.
+    ///
+    /// The first freeze is the one reached by the first successful call to [`Self::enter`] or one of its variants,
+    /// after any setup the underlying future performs before freezing, or by [`Self::try_new`].
+    /// After a [reset](Self::reset), the first freeze of the new future is dispatched to `on_first` again.
+    ///
+    /// # Panics
+    ///
+    /// - In the same cases as [`Self::enter`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, SingleFamily};
+    ///
+    /// let mut scope = BoxScope::<SingleFamily<Vec<u32>>>::new_dyn(scope!({
+    ///     let mut values = Vec::new();
+    ///     loop {
+    ///         freeze!(&mut values);
+    ///         values.push(values.len() as u32);
+    ///     }
+    /// }));
+    ///
+    /// let enter = |scope: &mut BoxScope<SingleFamily<Vec<u32>>>| {
+    ///     scope.enter_or_init(
+    ///         |values| {
+    ///             values.extend([10, 20]);
+    ///             None
+    ///         },
+    ///         |values| Some(values.clone()),
+    ///     )
+    /// };
+    /// assert_eq!(enter(&mut scope), None);
+    /// assert_eq!(enter(&mut scope), Some(vec![10, 20, 2]));
+    /// ```
+    pub fn enter_or_init<'borrow, Output, I, G>(
+        &'borrow mut self,
+        on_first: I,
+        on_subsequent: G,
+    ) -> Output
+    where
+        I: for<'a> FnOnce(&'borrow mut <T as Family<'a>>::Family) -> Output,
+        G: for<'a> FnOnce(&'borrow mut <T as Family<'a>>::Family) -> Output,
+    {
+        // SAFETY:
+        // 1. `self.0` is valid as a post-condition of `new`.
+        // 2. `BoxScope::enter` and `BoxScope::try_enter` take an exclusive reference.
+        let first = unsafe {
+            if RawScope::is_primed(self.0) {
+                RawScope::freeze_count(self.0) == 1
+            } else {
+                RawScope::freeze_count(self.0) == 0
+            }
+        };
+        self.enter(|t| if first { on_first(t) } else { on_subsequent(t) })
+    }
+
+    /// Enters the scope, passing `arg` along with the data frozen inside of the scope to `f`.
+    ///
+    /// This is the same as [`Self::enter`], except that `arg` is moved into `f`, which can be more convenient
+    /// than capturing it in a closure.
+    ///
+    /// # Panics
+    ///
+    /// - If the passed function panics.
+    /// - If the underlying future panics.
+    /// - If the underlying future panicked in a previous call to [`Self::enter`] or [`Self::try_enter`].
+    /// - If the underlying future awaits for a future other than the [`crate::FrozenFuture`].
+    /// - If the underlying future completed, see [`Self::enter_or_complete`].
+    /// - If the underlying future froze its data with [`crate::TimeCapsule::freeze_ref`], see [`Self::enter_ref`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, SingleFamily};
+    ///
+    /// let mut scope = BoxScope::<SingleFamily<Vec<String>>>::new_dyn(scope!({
+    ///     let mut lines = Vec::new();
+    ///     freeze_forever!(&mut lines)
+    /// }));
+    ///
+    /// for line in ["Hello", "World"] {
+    ///     scope.enter_with(String::from(line), Vec::push);
+    /// }
+    /// assert_eq!(scope.enter(|lines| lines.join(" ")), "Hello World");
+    /// ```
+    pub fn enter_with<'borrow, Arg, Output, G>(&'borrow mut self, arg: Arg, f: G) -> Output
+    where
+        G: for<'a> FnOnce(&'borrow mut <T as Family<'a>>::Family, Arg) -> Output,
+    {
+        self.enter(|frozen| f(frozen, arg))
+    }
+
+    /// Enters the scope, passing the frozen data and a caller-provided arena to `f`.
+    ///
+    /// The output of `f` can borrow from the arena for `'arena`, but not from the frozen data.
+    /// This allows `f` to copy parts of the frozen data into the arena, for instance a bump allocator,
+    /// and to return references to the copies, that remain valid after the scope is entered again or dropped.
+    ///
+    /// # Panics
+    ///
+    /// - Same as [`Self::enter`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use core::cell::OnceCell;
+    /// use nolife::{scope, BoxScope, SingleFamily};
+    ///
+    /// let mut scope = BoxScope::<SingleFamily<String>>::new_dyn(scope!({
+    ///     let mut line = String::new();
+    ///     loop {
+    ///         line.push('a');
+    ///         freeze!(&mut line);
+    ///     }
+    /// }));
+    ///
+    /// let arena: [OnceCell<String>; 2] = Default::default();
+    /// let first = scope.enter_into(&arena[0], |line, slot| slot.get_or_init(|| line.clone()));
+    /// let second = scope.enter_into(&arena[1], |line, slot| slot.get_or_init(|| line.clone()));
+    /// drop(scope);
+    /// assert_eq!((first.as_str(), second.as_str()), ("a", "aa"));
+    /// ```
+    pub fn enter_into<'arena, Arena, Output, G>(&mut self, arena: &'arena Arena, f: G) -> Output
+    where
+        Arena: ?Sized,
+        G: for<'a, 'b> FnOnce(&'b mut <T as Family<'a>>::Family, &'arena Arena) -> Output,
+    {
+        self.enter(|frozen| f(frozen, arena))
+    }
+
+    /// Enters the scope, making it possible to read the data frozen inside of the scope.
+    ///
+    /// This is the same as [`Self::enter`], except that the passed function only gets a shared reference to the frozen data.
+    /// In exchange, it also accepts data frozen behind a shared reference with [`crate::TimeCapsule::freeze_ref`].
+    ///
+    /// # Panics
+    ///
+    /// - If the passed function panics.
+    /// - If the underlying future panics.
+    /// - If the underlying future panicked in a previous call to [`Self::enter`] or [`Self::try_enter`].
+    /// - If the underlying future awaits for a future other than the [`crate::FrozenFuture`].
+    /// - If the underlying future completed, see [`Self::enter_or_complete`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, SingleFamily};
+    ///
+    /// let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+    ///     let mut x = 0u32;
+    ///     loop {
+    ///         freeze_ref!(&x);
+    ///         x += 1;
+    ///     }
+    /// }));
+    ///
+    /// assert_eq!(scope.enter_ref(|x| *x), 0);
+    /// assert_eq!(scope.enter_ref(|x| *x), 1);
+    /// ```
+    pub fn enter_ref<'borrow, Output, G>(&'borrow mut self, f: G) -> Output
+    where
+        G: for<'a> FnOnce(&'borrow <T as Family<'a>>::Family) -> Output,
+    {
+        // SAFETY:
+        // 1. `self.0` is valid as a post-condition of `new`.
+        // 2. The object pointed to by `self.0` did not move and won't before deallocation.
+        // 3. `BoxScope::enter_ref` takes an exclusive reference and the reference passed to `f` cannot escape `f`.
+        unsafe { RawScope::enter_ref(self.0, f) }
+    }
+
+    /// Enters the scope to read the data frozen inside of the scope, extracting an output from it.
+    ///
+    /// This is the same as [`Self::enter_ref`]: the passed function only gets a shared reference to the frozen data,
+    /// signaling that the scope is only inspected. Like any call entering the scope,
+    /// it polls the underlying future once before calling `extract`.
+    ///
+    /// # Panics
+    ///
+    /// - Same as [`Self::enter_ref`].
+    pub fn peek<'borrow, Output, G>(&'borrow mut self, extract: G) -> Output
+    where
+        G: for<'a> FnOnce(&'borrow <T as Family<'a>>::Family) -> Output,
+    {
+        self.enter_ref(extract)
+    }
+
+    /// Enters the scope and returns an owned copy of the data frozen inside of the scope.
+    ///
+    /// This is only available when the frozen data does not borrow from the scope,
+    /// that is when the family does not depend on its lifetime, such as a [`crate::SingleFamily`].
+    ///
+    /// # Panics
+    ///
+    /// - Same as [`Self::enter_ref`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, SingleFamily};
+    ///
+    /// let mut scope = BoxScope::<SingleFamily<Vec<u32>>>::new_dyn(scope!({
+    ///     let mut v = Vec::new();
+    ///     loop {
+    ///         freeze!(&mut v);
+    ///         v.push(v.len() as u32);
+    ///     }
+    /// }));
+    ///
+    /// assert!(scope.peek_clone().is_empty());
+    /// assert_eq!(scope.peek_clone(), [0]);
+    /// assert_eq!(scope.peek(|v| v.len()), 2);
+    /// ```
+    pub fn peek_clone<X>(&mut self) -> X
+    where
+        T: for<'a> Family<'a, Family = X>,
+        X: Clone,
+    {
+        self.enter_ref(|frozen| frozen.clone())
+    }
+
+    /// Enters the scope and serializes the data frozen inside of the scope as JSON.
+    ///
+    /// Like [`Self::peek`], this polls the underlying future once, then serializes the frozen data
+    /// through a shared reference. The frozen data must be serializable for any lifetime of the scope.
+    ///
+    /// # Panics
+    ///
+    /// - If serializing the frozen data fails, for instance if it contains a map whose keys are not strings.
+    /// - Same as [`Self::enter_ref`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, RefFamily};
+    ///
+    /// let mut scope = BoxScope::<RefFamily<[u32]>>::new_dyn(scope!({
+    ///     let mut v = vec![1];
+    ///     loop {
+    ///         freeze_ref!(&v.as_slice());
+    ///         v.push(v.len() as u32 + 1);
+    ///     }
+    /// }));
+    ///
+    /// assert_eq!(scope.snapshot(), b"[1]");
+    /// assert_eq!(scope.snapshot(), b"[1,2]");
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn snapshot(&mut self) -> Vec<u8>
+    where
+        for<'a> <T as Family<'a>>::Family: serde::Serialize,
+    {
+        self.enter_ref(|frozen| match serde_json::to_vec(frozen) {
+            Ok(bytes) => bytes,
+            Err(err) => panics::serialization_failed(err),
+        })
+    }
+
+    /// Enters the scope, making it possible to access the data frozen inside of the scope.
+    ///
+    /// Unlike [`Self::enter`], errors with the underlying future are returned rather than causing a panic.
+    /// In particular, once the underlying future panicked, this function always returns [`ScopeError::Poisoned`]
+    /// without polling the future again.
+    ///
+    /// # Errors
+    ///
+    /// - [`ScopeError::Poisoned`] if the underlying future panicked in a previous call.
+    /// - [`ScopeError::NotFrozen`] if the underlying future awaits for a future other than the [`crate::FrozenFuture`].
+    /// - [`ScopeError::Completed`] if the underlying future completed, either during this call or during a previous one.
+    ///   The output of the future is dropped, use [`Self::enter_or_complete`] to retrieve it.
+    /// - [`ScopeError::FrozenShared`] if the underlying future froze its data with [`crate::TimeCapsule::freeze_ref`].
+    ///   Use [`Self::enter_ref`] to access it.
+    ///
+    /// # Panics
+    ///
+    /// - If the passed function panics.
+    /// - If the underlying future panics. The scope is then poisoned.
+    pub fn try_enter<'borrow, Output, G>(&'borrow mut self, f: G) -> Result<Output, ScopeError>
+    where
+        G: for<'a> FnOnce(&'borrow mut <T as Family<'a>>::Family) -> Output,
+    {
+        // SAFETY:
+        // 1. `self.0` is valid as a post-condition of `new`.
+        // 2. The object pointed to by `self.0` did not move and won't before deallocation.
+        // 3. `BoxScope::try_enter` takes an exclusive reference and the reference passed to `f` cannot escape `f`.
+        unsafe { RawScope::try_enter(self.0, f) }
+    }
+
+    /// Enters the scope, catching any panic of the passed function or of the underlying future.
+    ///
+    /// Contrary to [`Self::enter`], a panic of the passed function poisons the scope too,
+    /// so that the frozen data cannot be observed after it was left in an inconsistent state.
+    /// Once poisoned, the scope can no longer be entered, and [`Self::try_enter`] returns [`ScopeError::Poisoned`].
+    ///
+    /// Errors with the underlying future, that make [`Self::enter`] panic, are returned as with [`Self::try_enter`],
+    /// without poisoning the scope: the passed function did not run, so the frozen data was not touched.
+    /// For instance, after [`ScopeError::FrozenShared`], the data frozen behind a shared reference can still be read
+    /// with [`Self::enter_ref`].
+    ///
+    /// # Errors
+    ///
+    /// - The panic payload if the passed function or the underlying future panicked. The scope is then poisoned.
+    ///   The outer `Err` only ever holds such payloads, so that it can be passed to [`std::panic::resume_unwind`].
+    /// - `Ok(Err(err))` if the scope could not be entered, in the same cases as [`Self::try_enter`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, ScopeError, SingleFamily};
+    ///
+    /// let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+    ///     let mut x = 0u32;
+    ///     loop {
+    ///         freeze!(&mut x);
+    ///         x += 1;
+    ///     }
+    /// }));
+    ///
+    /// assert_eq!(scope.catch_enter(|x| *x).unwrap(), Ok(0));
+    /// assert!(scope.catch_enter(|_| panic!("oops")).is_err());
+    /// assert_eq!(scope.catch_enter(|x| *x).unwrap(), Err(ScopeError::Poisoned));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn catch_enter<'borrow, Output, G>(
+        &'borrow mut self,
+        f: G,
+    ) -> Result<Result<Output, ScopeError>, Box<dyn core::any::Any + Send>>
+    where
+        G: for<'a> FnOnce(&'borrow mut <T as Family<'a>>::Family) -> Output,
+    {
+        let raw_scope = self.0;
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            // SAFETY:
+            // 1. `self.0` is valid as a post-condition of `new`.
+            // 2. The object pointed to by `self.0` did not move and won't before deallocation.
+            // 3. `BoxScope::catch_enter` takes an exclusive reference and the reference passed to `f` cannot escape `f`.
+            unsafe { RawScope::try_enter(raw_scope, f) }
+        }));
+        // an error means that `f` did not run, so the frozen data is left as is.
+        result.inspect_err(|_| {
+            // the scope is poisoned on panic, so it cannot be observed in a broken state.
+            // SAFETY:
+            // 1. `self.0` is valid as a post-condition of `new`.
+            // 2. The scope was exited while unwinding, and `self` is borrowed exclusively.
+            unsafe { RawScope::poison(raw_scope) }
+        })
+    }
+
+    /// Enters the scope, polling the underlying future at most `max_polls` times until it freezes.
+    ///
+    /// Contrary to [`Self::enter`], awaiting for a future other than the [`crate::FrozenFuture`] does not panic:
+    /// the underlying future is polled again, until it freezes or `max_polls` polls were made.
+    /// This turns scopes spinning on futures that never make progress into a recoverable error.
+    ///
+    /// The budget bounds the number of polls, not the time spent in each of them:
+    /// a scope looping forever without awaiting still hangs this function.
+    ///
+    /// When the budget is exceeded, the passed function is not called, and the underlying future is resumed
+    /// where it stopped by the next call to enter the scope.
+    ///
+    /// # Errors
+    ///
+    /// - [`StepLimitExceeded`] if the underlying future did not freeze after `max_polls` polls.
+    ///
+    /// # Panics
+    ///
+    /// - If the passed function panics.
+    /// - If the underlying future panics.
+    /// - If the underlying future panicked in a previous call.
+    /// - If the underlying future completed, see [`Self::enter_or_complete`].
+    /// - If the underlying future froze its data with [`crate::TimeCapsule::freeze_ref`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, SingleFamily, StepLimitExceeded};
+    /// # use core::{future::Future, pin::Pin, task::{Context, Poll}};
+    /// #
+    /// # /// A future that is always pending.
+    /// # struct Pending;
+    /// # impl Future for Pending {
+    /// #     type Output = ();
+    /// #     fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+    /// #         Poll::Pending
+    /// #     }
+    /// # }
+    ///
+    /// let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+    ///     freeze!(&mut 0);
+    ///     Pending.await;
+    ///     freeze_forever!(&mut 1)
+    /// }));
+    ///
+    /// assert_eq!(scope.enter_bounded(1, |x| *x), Ok(0));
+    /// assert_eq!(scope.enter_bounded(100, |x| *x), Err(StepLimitExceeded));
+    /// ```
+    pub fn enter_bounded<'borrow, Output, G>(
+        &'borrow mut self,
+        max_polls: usize,
+        f: G,
+    ) -> Result<Output, StepLimitExceeded>
+    where
+        G: for<'a> FnOnce(&'borrow mut <T as Family<'a>>::Family) -> Output,
+    {
+        let mut cx = Context::from_waker(&waker::NOOP);
+        let mut f = Some(f);
+        for _ in 0..max_polls {
+            // SAFETY: if `f` is called, this function returns and the scope is borrowed for `'borrow`,
+            // so it is not accessed for `'borrow`.
+            let result = unsafe {
+                self.enter_or_complete_with(&mut cx, |frozen| {
+                    // `f` is only taken when the scope froze, which ends the loop
+                    let Some(f) = f.take() else {
+                        panics::entered_twice()
+                    };
+                    f(frozen)
+                })
+            };
+            match result {
+                Ok(Ok(output)) => return Ok(output),
+                Ok(Err(Completed(_))) => panics::scope_error(ScopeError::Completed),
+                Err(ScopeError::NotFrozen) => continue,
+                Err(err) => panics::scope_error(err),
+            }
+        }
+        Err(StepLimitExceeded)
+    }
+
+    /// Enters the scope, unless `deadline` passes before the underlying future freezes.
+    ///
+    /// Like [`Self::enter_bounded`], awaiting for a future other than the [`crate::FrozenFuture`] does not panic:
+    /// the underlying future is polled again, until it freezes or `deadline` passes.
+    ///
+    /// The deadline is checked before each poll, and after the poll that froze the scope. If the deadline passed
+    /// by the time the underlying future froze, the passed function is not called, and the frozen data is kept for
+    /// the next call to enter the scope, that accesses it without polling the underlying future.
+    ///
+    /// The deadline is only checked between polls: the code of the underlying future runs until its next `.await`,
+    /// so a scope that blocks or loops without awaiting cannot be interrupted and still delays this function.
+    ///
+    /// # Errors
+    ///
+    /// - [`TimedOut`] if `deadline` passed before the underlying future froze.
+    ///
+    /// # Panics
+    ///
+    /// - In the same cases as [`Self::enter_bounded`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::{Duration, Instant};
+    /// use nolife::{scope, BoxScope, SingleFamily, TimedOut};
+    ///
+    /// let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+    ///     freeze!(&mut 0);
+    ///     // some long computation
+    ///     std::thread::sleep(Duration::from_millis(20));
+    ///     freeze_forever!(&mut 1)
+    /// }));
+    ///
+    /// assert_eq!(scope.enter_until(Instant::now() + Duration::from_secs(60), |x| *x), Ok(0));
+    /// assert_eq!(scope.enter_until(Instant::now() + Duration::from_millis(1), |x| *x), Err(TimedOut));
+    /// // the data frozen after the deadline is still available
+    /// assert_eq!(scope.enter(|x| *x), 1);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn enter_until<'borrow, Output, G>(
+        &'borrow mut self,
+        deadline: std::time::Instant,
+        f: G,
+    ) -> Result<Output, TimedOut>
+    where
+        G: for<'a> FnOnce(&'borrow mut <T as Family<'a>>::Family) -> Output,
+    {
+        let mut cx = Context::from_waker(&waker::NOOP);
+        loop {
+            if std::time::Instant::now() >= deadline {
+                return Err(TimedOut);
+            }
+            // SAFETY:
+            // 1. `self.0` is valid as a post-condition of `new`.
+            // 2. The object pointed to by `self.0` did not move and won't before deallocation.
+            // 3. `BoxScope::enter_until` takes an exclusive reference.
+            match unsafe { RawScope::prime(self.0, &mut cx) } {
+                Ok(()) => break,
+                Err(ScopeError::NotFrozen) => continue,
+                Err(err) => panics::scope_error(err),
+            }
+        }
+        if std::time::Instant::now() >= deadline {
+            return Err(TimedOut);
+        }
+        // the scope is primed, so this does not poll the underlying future again.
+        Ok(self.enter(f))
+    }
+
+    /// Enters the scope, making it possible to access the data frozen inside of the scope,
+    /// or returns the output of the underlying future if it completes instead of freezing the scope.
+    ///
+    /// The passed function is not called if the underlying future completes.
+    /// See [`Self::new_with_output`] for the semantics of a completed scope.
+    ///
+    /// # Panics
+    ///
+    /// - If the passed function panics.
+    /// - If the underlying future panics.
+    /// - If the underlying future panicked in a previous call.
+    /// - If the underlying future awaits for a future other than the [`crate::FrozenFuture`].
+    /// - If the underlying future completed during a previous call.
+    pub fn enter_or_complete<'borrow, Output, G>(
+        &'borrow mut self,
+        f: G,
+    ) -> Result<Output, Completed<F::Output>>
+    where
+        G: for<'a> FnOnce(&'borrow mut <T as Family<'a>>::Family) -> Output,
+    {
+        // SAFETY:
+        // 1. `self.0` is valid as a post-condition of `new`.
+        // 2. The object pointed to by `self.0` did not move and won't before deallocation.
+        // 3. `BoxScope::enter_or_complete` takes an exclusive reference and the reference passed to `f` cannot escape `f`.
+        match unsafe { RawScope::enter_or_complete(self.0, f) } {
+            Ok(output) => output,
+            Err(err) => panics::scope_error(err),
+        }
+    }
+
+    /// Polls the underlying future once with the passed context, calling `f` on the frozen data if it freezes.
+    ///
+    /// This is the low-level primitive for custom executors and adapters: contrary to [`Self::enter`],
+    /// the underlying future is polled with the waker of `cx`, and awaiting for a future other than the
+    /// [`crate::FrozenFuture`] is reported as [`Poll::Pending`] instead of panicking.
+    ///
+    /// Each call resumes the underlying future from its last freeze, with the following transitions:
+    ///
+    /// - The future freezes again: `f` is called on the frozen data, and its output is returned
+    ///   as `Poll::Ready(Ok(Ok(output)))`.
+    /// - The future awaits for another future: `f` is dropped without being called, and `Poll::Pending` is returned.
+    ///   The waker of `cx` is woken up when the scope can make progress, and the next call resumes the future
+    ///   where it stopped, without going through a freeze.
+    /// - The future completes: its output is returned as `Poll::Ready(Ok(Err(Completed(output))))`.
+    ///   Subsequent calls return `Poll::Ready(Err(ScopeError::Completed))`.
+    /// - The future panics: the scope is poisoned and the panic is propagated.
+    ///   Subsequent calls return `Poll::Ready(Err(ScopeError::Poisoned))`.
+    /// - The future froze its data with [`crate::TimeCapsule::freeze_ref`]: `f` is not called,
+    ///   and `Poll::Ready(Err(ScopeError::FrozenShared))` is returned.
+    ///
+    /// Once the future completed, panicked or was cancelled, the scope is [terminated](Self::is_terminated):
+    /// like a fused future, it can be polled any number of times past that point, always returning
+    /// `Poll::Ready(Err(_))` without polling the underlying future.
+    ///
+    /// # Errors
+    ///
+    /// - [`ScopeError::Poisoned`] if the underlying future panicked in a previous call.
+    /// - [`ScopeError::Completed`] if the underlying future completed during a previous call.
+    /// - [`ScopeError::FrozenShared`] if the underlying future froze its data with [`crate::TimeCapsule::freeze_ref`].
+    ///   The frozen data is kept for the next call, so that [`Self::enter_ref`] can still read it.
+    ///
+    /// # Panics
+    ///
+    /// - If the passed function panics.
+    /// - If the underlying future panics.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use core::task::{Context, Poll, Waker};
+    /// use nolife::{scope, BoxScope, Completed, SingleFamily};
+    ///
+    /// let mut scope = BoxScope::<SingleFamily<u32>, _>::new_with_output(scope!({
+    ///     let mut x = 0;
+    ///     freeze!(&mut x);
+    ///     x + 1
+    /// }));
+    ///
+    /// let mut cx = Context::from_waker(Waker::noop());
+    /// assert_eq!(scope.poll_enter(&mut cx, |x| *x += 41), Poll::Ready(Ok(Ok(()))));
+    /// assert_eq!(scope.poll_enter(&mut cx, |x| *x), Poll::Ready(Ok(Err(Completed(42)))));
+    /// ```
+    pub fn poll_enter<'borrow, Output, G>(
+        &'borrow mut self,
+        cx: &mut Context<'_>,
+        f: G,
+    ) -> Poll<EnterResult<Output, F::Output>>
+    where
+        G: for<'a> FnOnce(&'borrow mut <T as Family<'a>>::Family) -> Output,
+    {
+        // SAFETY: `BoxScope::poll_enter` borrows the scope for `'borrow`, so it is not accessed for `'borrow`.
+        match unsafe { self.enter_or_complete_with(cx, f) } {
+            Err(ScopeError::NotFrozen) => Poll::Pending,
+            result => Poll::Ready(result),
+        }
+    }
+
+    /// Same as [`Self::enter_or_complete`], but polls the future with the passed context
+    /// and reports errors instead of panicking.
+    ///
+    /// [`ScopeError::NotFrozen`] means that the future is waiting on another future, that will wake the waker of `cx` up.
+    ///
+    /// # Safety
+    ///
+    /// 1. If `f` is called, the scope is not accessed for `'borrow`.
+    pub(crate) unsafe fn enter_or_complete_with<'borrow, Output, G>(
+        &mut self,
+        cx: &mut Context<'_>,
+        f: G,
+    ) -> EnterResult<Output, F::Output>
+    where
+        G: for<'a> FnOnce(&'borrow mut <T as Family<'a>>::Family) -> Output,
+    {
+        // SAFETY:
+        // 1. `self.0` is valid as a post-condition of `new`.
+        // 2. The object pointed to by `self.0` did not move and won't before deallocation.
+        // 3. `BoxScope::enter_or_complete_with` takes an exclusive reference, the reference passed to `f` cannot escape `f`,
+        //    and the scope is not accessed for `'borrow` by precondition (1).
+        unsafe { RawScope::enter_or_complete_with(self.0, cx, f) }
+    }
+
+    /// Drives the underlying future to completion, returning its output.
+    ///
+    /// The scope is repeatedly entered without accessing the frozen data, until the underlying future completes.
+    /// It is then dropped.
+    ///
+    /// Returns `None` if the underlying future panicked or completed in a previous call,
+    /// as its output is then no longer available.
+    ///
+    /// Most scopes never complete, in particular the scopes whose output is [`Never`], such as the ones created by
+    /// [`Self::new`]: this function then loops forever. Use [`Self::terminate`] to drop such scopes.
+    ///
+    /// # Panics
+    ///
+    /// - If the underlying future panics.
+    /// - If the underlying future awaits for a future other than the [`crate::FrozenFuture`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, SingleFamily};
+    ///
+    /// let mut scope = BoxScope::<SingleFamily<u32>, _>::new_with_output(scope!({
+    ///     let mut sum = 0;
+    ///     for mut x in 0..4 {
+    ///         freeze!(&mut x);
+    ///         sum += x;
+    ///     }
+    ///     sum
+    /// }));
+    ///
+    /// scope.enter(|x| *x = 10);
+    /// assert_eq!(scope.finish(), Some(16));
+    /// ```
+    pub fn finish(self) -> Option<F::Output> {
+        loop {
+            // SAFETY:
+            // 1. `self.0` is valid as a post-condition of `new`.
+            // 2. The object pointed to by `self.0` did not move and won't before deallocation.
+            // 3. `BoxScope::finish` takes ownership of the scope and does not access the frozen data.
+            match unsafe {
+                RawScope::poll_or_complete(self.0, &mut Context::from_waker(&waker::NOOP))
+            } {
+                Ok(Ok(())) => continue,
+                Ok(Err(Completed(output))) => return Some(output),
+                Err(ScopeError::Poisoned | ScopeError::Completed | ScopeError::Cancelled) => {
+                    return None
+                }
+                Err(err @ (ScopeError::NotFrozen | ScopeError::FrozenShared)) => {
+                    panics::scope_error(err)
+                }
+            }
+        }
+    }
+
+    /// Drops the underlying future now, running the destructors of its locals, without dropping the scope.
+    ///
+    /// This deterministically releases the resources held by the future, such as files or sockets,
+    /// while keeping the scope itself. Contrary to [`BoxScope::reset`], the future is not replaced:
+    /// the scope is cancelled, and [`Self::try_enter`] returns [`ScopeError::Cancelled`] from then on.
+    ///
+    /// Cancelling a scope that was already cancelled does nothing.
+    ///
+    /// # Panics
+    ///
+    /// - If dropping the underlying future panics. The scope is then poisoned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, ScopeError, SingleFamily};
+    ///
+    /// let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+    ///     let mut x = 0u32;
+    ///     loop {
+    ///         freeze!(&mut x);
+    ///         x += 1;
+    ///     }
+    /// }));
+    ///
+    /// assert_eq!(scope.enter(|x| *x), 0);
+    /// scope.cancel();
+    /// assert_eq!(scope.try_enter(|x| *x), Err(ScopeError::Cancelled));
+    /// ```
+    pub fn cancel(&mut self) {
+        // SAFETY:
+        // 1. `self.0` is valid as a post-condition of `new`.
+        // 2. The object pointed to by `self.0` did not move and won't before deallocation.
+        // 3. `BoxScope::cancel` takes an exclusive reference, so no reference to the frozen value can exist.
+        unsafe { RawScope::cancel(self.0) }
+    }
+
+    /// Drops the underlying future without driving it to completion, running the destructors of its locals.
+    ///
+    /// This is the same as dropping the scope.
+    pub fn terminate(self) {
+        drop(self)
+    }
+
+    /// Whether the underlying future panicked, in which case the scope can no longer be entered.
+    ///
+    /// Once a scope is poisoned, [`Self::enter`] always panics and [`Self::try_enter`] always returns [`ScopeError::Poisoned`].
+    ///
+    /// This function does not poll the underlying future.
+    pub fn is_poisoned(&self) -> bool {
+        // SAFETY:
+        // 1. `self.0` is valid as a post-condition of `new`.
+        // 2. `BoxScope::enter` and `BoxScope::try_enter` take an exclusive reference.
+        unsafe { RawScope::is_poisoned(self.0) }
+    }
+
+    /// Whether the underlying future reached a terminal state, in which case it is never polled again.
+    ///
+    /// A scope is terminated once its underlying future panicked, completed, or was [cancelled](Self::cancel).
+    /// Like a fused future, a terminated scope can be polled safely:
+    /// [`Self::try_enter`] and [`Self::poll_enter`] then return the corresponding [`ScopeError`] without polling
+    /// the underlying future. A terminated scope stays terminated until it is [reset](Self::reset).
+    ///
+    /// This function does not poll the underlying future.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, Completed, ScopeError, SingleFamily};
+    ///
+    /// let mut scope = BoxScope::<SingleFamily<u32>, _>::new_with_output(scope!({
+    ///     freeze!(&mut 0);
+    ///     1
+    /// }));
+    /// assert_eq!(scope.enter_or_complete(|x| *x), Ok(0));
+    /// assert!(!scope.is_terminated());
+    /// assert_eq!(scope.enter_or_complete(|x| *x), Err(Completed(1)));
+    /// assert!(scope.is_terminated());
+    /// assert_eq!(scope.try_enter(|x| *x), Err(ScopeError::Completed));
+    /// ```
+    pub fn is_terminated(&self) -> bool {
+        // SAFETY:
+        // 1. `self.0` is valid as a post-condition of `new`.
+        // 2. `BoxScope::enter` and `BoxScope::try_enter` take an exclusive reference.
+        unsafe { RawScope::is_terminated(self.0) }
+    }
+
+    /// Whether the underlying future is currently frozen, with its data available to the next call to [`Self::enter`].
+    ///
+    /// This is `false` until the scope is entered for the first time, as the underlying future only freezes when polled.
+    /// It is also `false` after the scope is poisoned, or when the last call to [`Self::try_enter`] returned [`ScopeError::NotFrozen`].
+    ///
+    /// This function does not poll the underlying future.
+    pub fn is_active(&self) -> bool {
+        // SAFETY:
+        // 1. `self.0` is valid as a post-condition of `new`.
+        // 2. `BoxScope::enter` and `BoxScope::try_enter` take an exclusive reference.
+        unsafe { RawScope::is_active(self.0) }
+    }
+
+    /// The number of times the underlying future was replaced by [`Self::reset`] or dropped by [`Self::cancel`].
+    ///
+    /// Values extracted from the scope can be tagged with the generation at the time they were extracted,
+    /// so that a cache of such values can detect that they come from a previous future, and are stale.
+    ///
+    /// The adapters of this crate, such as [`crate::ScopeIter`], [`crate::ScopeGuard`] or [`crate::MapScope`],
+    /// borrow or own the scope: the scope cannot be reset while they are in use, so they never need to check
+    /// the generation.
+    ///
+    /// This function does not poll the underlying future.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, SingleFamily, TopScope};
+    ///
+    /// fn counter(start: u32) -> impl TopScope<Family = SingleFamily<u32>> {
+    ///     scope!({
+    ///         let mut x = start;
+    ///         loop {
+    ///             freeze!(&mut x);
+    ///             x += 1;
+    ///         }
+    ///     })
+    /// }
+    ///
+    /// let mut scope = BoxScope::new(counter(0));
+    /// let cached = (scope.generation(), scope.enter(|x| *x));
+    ///
+    /// scope.reset(counter(10));
+    /// assert_ne!(cached.0, scope.generation());
+    /// ```
+    pub fn generation(&self) -> u64 {
+        // SAFETY:
+        // 1. `self.0` is valid as a post-condition of `new`.
+        // 2. `BoxScope::enter` and `BoxScope::try_enter` take an exclusive reference.
+        unsafe { RawScope::generation(self.0) }
+    }
+
+    /// Whether the underlying future froze at least once, since the scope was created or last [reset](Self::reset).
+    ///
+    /// Contrary to [`Self::is_active`], this stays `true` once the scope froze, even if it was poisoned or completed
+    /// afterwards. In particular, this is `false` while the underlying future did not reach its first freeze yet,
+    /// for instance because it awaits for setup futures through [`Self::enter_async`].
+    ///
+    /// This function does not poll the underlying future.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, SingleFamily};
+    ///
+    /// let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+    ///     freeze_forever!(&mut 0)
+    /// }));
+    /// assert!(!scope.has_frozen());
+    /// scope.enter(|_| ());
+    /// assert!(scope.has_frozen());
+    /// ```
+    pub fn has_frozen(&self) -> bool {
+        // SAFETY:
+        // 1. `self.0` is valid as a post-condition of `new`.
+        // 2. `BoxScope::enter` and `BoxScope::try_enter` take an exclusive reference.
+        unsafe { RawScope::has_frozen(self.0) }
+    }
+
+    /// The size of the underlying future, in bytes.
+    ///
+    /// This is the size of the locals that the future holds across its `.await`s, including the frozen data,
+    /// and is known even when the type of the future is erased, as with [`BoxScope::new_dyn`].
+    /// The allocation of the scope holds the future along with a fixed-size state, so that pools can group scopes
+    /// by this size. The size does not change when the scope is entered, only when it is [reset](Self::reset).
+    ///
+    /// This function does not poll the underlying future.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, RefFamily};
+    ///
+    /// let scope = BoxScope::<RefFamily<[u8]>>::new_dyn(scope!({
+    ///     let buffer = [0u8; 1024];
+    ///     freeze_forever!(&mut &buffer[..])
+    /// }));
+    /// assert!(scope.future_size() >= 1024);
+    /// ```
+    pub fn future_size(&self) -> usize {
+        // SAFETY:
+        // 1. `self.0` is valid as a post-condition of `new`.
+        // 2. `BoxScope::enter` and `BoxScope::try_enter` take an exclusive reference.
+        unsafe { RawScope::future_size(self.0) }
+    }
+}
+
+/// Awaiting a scope drives its underlying future to completion, resolving to its output.
+///
+/// This allows scopes created with [`BoxScope::new_with_output`] to be awaited once they were stepped through
+/// with [`BoxScope::enter`], for instance to join or select their completion with other futures.
+///
+/// Each poll resumes the underlying future at most until its next freeze.
+/// When the underlying future freezes, the frozen data is not accessed: the scope wakes the waker of the context
+/// up and returns [`Poll::Pending`], so that the executor polls it again without the scope starving other futures.
+/// When the underlying future awaits for another future, the scope returns [`Poll::Pending`] until that future
+/// wakes it up, like [`BoxScope::poll_enter`].
+///
+/// Scopes whose output is [`Never`], such as the ones created by [`BoxScope::new`], never resolve.
+///
+/// # Panics
+///
+/// When polled, the scope panics:
+///
+/// - If the underlying future panics.
+/// - If the underlying future panicked, completed or was cancelled in a previous call.
+///
+/// # Example
+///
+/// ```
+/// use core::{future::Future, pin::Pin, task::{Context, Poll, Waker}};
+/// use nolife::{scope, BoxScope, SingleFamily};
+///
+/// let mut scope = BoxScope::<SingleFamily<u32>, _>::new_with_output(scope!({
+///     let mut sum = 0;
+///     for mut x in 0..3 {
+///         freeze!(&mut x);
+///         sum += x;
+///     }
+///     sum
+/// }));
+///
+/// scope.enter(|x| *x = 10);
+///
+/// let mut cx = Context::from_waker(Waker::noop());
+/// // the two remaining freezes are skipped, one per poll
+/// assert_eq!(Pin::new(&mut scope).poll(&mut cx), Poll::Pending);
+/// assert_eq!(Pin::new(&mut scope).poll(&mut cx), Poll::Pending);
+/// assert_eq!(Pin::new(&mut scope).poll(&mut cx), Poll::Ready(13));
+/// ```
+impl<T, F: ?Sized, A> Future for BoxScope<T, F, A>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+{
+    type Output = F::Output;
+
+    fn poll(self: core::pin::Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: the underlying future is pinned by the allocation, no field of the `BoxScope` is structurally pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        // SAFETY:
+        // 1. `this.0` is valid as a post-condition of `new`.
+        // 2. The object pointed to by `this.0` did not move and won't before deallocation.
+        // 3. The scope is borrowed exclusively, and the frozen data is not accessed.
+        match unsafe { RawScope::poll_or_complete(this.0, cx) } {
+            Ok(Ok(())) => {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+            Ok(Err(Completed(output))) => Poll::Ready(output),
+            // the underlying future is waiting on another future, that will wake `cx` up.
+            Err(ScopeError::NotFrozen) => Poll::Pending,
+            Err(err) => panics::scope_error(err),
+        }
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<T, F: ?Sized, A> futures_core::FusedFuture for BoxScope<T, F, A>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+{
+    fn is_terminated(&self) -> bool {
+        BoxScope::is_terminated(self)
+    }
 }
@@ -0,0 +1,76 @@
+use core::{
+    future::Future,
+    ops::{Coroutine, CoroutineState},
+    pin::pin,
+};
+
+use crate::{BoxScope, SingleFamily};
+
+impl<Y> BoxScope<SingleFamily<Y>>
+where
+    Y: 'static,
+{
+    /// Ties a scope running the passed coroutine to the heap, freezing the scope at each `yield`.
+    ///
+    /// This allows writing the scope with the coroutine syntax of nightly Rust rather than with [`crate::scope!`].
+    ///
+    /// # Mapping
+    ///
+    /// - Each call to [`BoxScope::enter`] or one of its variants resumes the coroutine once, with `()`.
+    ///   The coroutine is first resumed by the first call, not by this function.
+    /// - A value yielded by the coroutine is frozen until the next call, that drops it before resuming the coroutine.
+    ///   The function passed to `enter` accesses it mutably, and its output is returned to the caller of `enter`.
+    /// - The value returned by the coroutine is the output of the scope, see [`BoxScope::enter_or_complete`].
+    ///
+    /// A coroutine cannot yield references to its own locals, so the yielded values are owned.
+    /// Coroutines taking a resume argument other than `()` are not supported: use a [`crate::scope!`] freezing with
+    /// [`crate::TimeCapsule::freeze_yield`] to pass values back to the scope.
+    ///
+    /// # Panics
+    ///
+    /// - If the coroutine panics when it is resumed, poisoning the scope.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// #![feature(coroutines, yield_expr)]
+    /// use nolife::{BoxScope, Completed};
+    ///
+    /// let mut scope = BoxScope::from_coroutine(
+    ///     #[coroutine]
+    ///     || {
+    ///         for word in ["Intel", "the", "Beagle"] {
+    ///             yield String::from(word);
+    ///         }
+    ///         3
+    ///     },
+    /// );
+    ///
+    /// assert_eq!(scope.enter(|word| word.len()), 5);
+    /// scope.enter(|word| word.make_ascii_uppercase());
+    /// assert_eq!(scope.enter_or_complete(|word| word.clone()), Ok(String::from("Beagle")));
+    /// assert_eq!(scope.enter_or_complete(|word| word.clone()), Err(Completed(3)));
+    /// ```
+    pub fn from_coroutine<C>(
+        coroutine: C,
+    ) -> BoxScope<SingleFamily<Y>, impl Future<Output = C::Return>>
+    where
+        C: Coroutine<Yield = Y>,
+    {
+        BoxScope::new_with_output(crate::scope!(SingleFamily<Y>, {
+            let mut coroutine = pin!(coroutine);
+            loop {
+                match coroutine.as_mut().resume(()) {
+                    CoroutineState::Yielded(mut value) => {
+                        freeze!(&mut value);
+                    }
+                    CoroutineState::Complete(output) => break output,
+                }
+            }
+        }))
+    }
+}
+
+// The coroutine syntax is rejected before `cfg` is evaluated, so the test module is only parsed in test builds.
+#[cfg(test)]
+mod test;
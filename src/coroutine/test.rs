@@ -0,0 +1,28 @@
+use crate::{BoxScope, Completed};
+
+#[test]
+fn from_coroutine() {
+    use alloc::vec::Vec;
+
+    let mut scope = BoxScope::from_coroutine(
+        #[coroutine]
+        || {
+            let mut values = Vec::new();
+            for x in 0..3u32 {
+                values.push(x);
+                yield x;
+            }
+            values
+        },
+    );
+
+    // the coroutine is only resumed when the scope is entered
+    assert!(!scope.has_frozen());
+    assert_eq!(scope.enter(|x| *x), 0);
+    scope.enter(|x| *x += 10);
+    assert_eq!(scope.enter_or_complete(|x| *x), Ok(2));
+    assert_eq!(
+        scope.enter_or_complete(|x| *x),
+        Err(Completed(alloc::vec![0, 1, 2]))
+    );
+}
@@ -386,3 +386,426 @@
 //!     scope.enter(|x| assert_eq!(*x, 16));
 //! }
 //! ```
+//!
+//! # Escaping a stack scope
+//!
+//! ```compile_fail
+//! use nolife::{scope, stack_scope, SingleFamily};
+//!
+//! fn escaping_stack_scope() {
+//!     let escaped = stack_scope::<SingleFamily<u32>, _, _>(scope!({ freeze_forever!(&mut 0) }), |scope| scope);
+//! }
+//! ```
+//!
+//! # Escaping a stack scope through a capture
+//!
+//! ```compile_fail,E0521
+//! use nolife::{scope, stack_scope, SingleFamily};
+//!
+//! fn escaping_stack_scope() {
+//!     let mut escaped = None;
+//!     stack_scope::<SingleFamily<u32>, _, _>(scope!({ freeze_forever!(&mut 0) }), |scope| {
+//!         escaped = Some(scope);
+//!     });
+//!     escaped.unwrap().enter(|x| *x += 1);
+//! }
+//! ```
+//!
+//! # Sending a scope with a non-`Send` future
+//!
+//! No error code is checked, as nightly reports a non-`Send` async block without one.
+//!
+//! ```compile_fail
+//! use nolife::{scope, BoxScope, SingleFamily};
+//! use std::rc::Rc;
+//!
+//! fn non_send_future() {
+//!     let scope = BoxScope::<SingleFamily<u32>, _>::new(scope!({
+//!         let rc = Rc::new(0u32);
+//!         let mut x = *rc;
+//!         freeze_forever!(&mut x)
+//!     }));
+//!
+//!     fn assert_send<S: Send>(_: &S) {}
+//!     assert_send(&scope);
+//! }
+//! ```
+//!
+//! # Sending a scope with a non-`Send` frozen value
+//!
+//! The future is asserted to be `Send`, so that only the frozen `&Rc<u32>` makes the scope non-`Send`.
+//!
+//! ```compile_fail,E0277
+//! use nolife::{scope::new_scope, BoxScope, Family, TimeCapsule};
+//! use std::{
+//!     future::Future,
+//!     pin::Pin,
+//!     rc::Rc,
+//!     task::{Context, Poll},
+//! };
+//!
+//! struct RcFamily;
+//!
+//! impl<'a> Family<'a> for RcFamily {
+//!     type Family = &'a Rc<u32>;
+//! }
+//!
+//! struct AssertSend<F>(F);
+//! unsafe impl<F> Send for AssertSend<F> {}
+//! impl<F: Future> Future for AssertSend<F> {
+//!     type Output = F::Output;
+//!     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+//!         unsafe { self.map_unchecked_mut(|this| &mut this.0) }.poll(cx)
+//!     }
+//! }
+//!
+//! fn non_send_frozen_value() {
+//!     let scope = unsafe {
+//!         new_scope(|mut time_capsule: TimeCapsule<RcFamily>| {
+//!             AssertSend(async move {
+//!                 let rc = Rc::new(0u32);
+//!                 loop {
+//!                     time_capsule.freeze(&mut &rc).await;
+//!                 }
+//!             })
+//!         })
+//!     };
+//!     let mut scope = BoxScope::<RcFamily, _>::new(scope);
+//!
+//!     std::thread::spawn(move || scope.enter(|x| ***x));
+//! }
+//! ```
+//!
+//! # Mutating data frozen with `freeze_ref`
+//!
+//! ```compile_fail,E0594
+//! use nolife::{scope, BoxScope, SingleFamily};
+//!
+//! fn mutate_frozen_ref() {
+//!     let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+//!         let mut x = 0u32;
+//!         loop {
+//!             freeze_ref!(&x);
+//!             x += 1;
+//!         }
+//!     }));
+//!
+//!     scope.enter_ref(|x| *x += 1);
+//! }
+//! ```
+//!
+//! # Resuming a scope while `enter_async` holds its frozen data
+//!
+//! ```compile_fail,E0499
+//! use nolife::{scope, BoxScope, SingleFamily};
+//!
+//! async fn resume_while_entered() {
+//!     let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+//!         let mut x = 0u32;
+//!         loop {
+//!             freeze!(&mut x);
+//!             x += 1;
+//!         }
+//!     }));
+//!
+//!     let fut = scope.enter_async(|x| async move { *x });
+//!     scope.enter(|x| *x);
+//!     fut.await;
+//! }
+//! ```
+//!
+//! # Resuming a scope while a `ScopeGuard` lives
+//!
+//! ```compile_fail,E0499
+//! use nolife::{scope, BoxScope, SingleFamily};
+//!
+//! fn resume_while_guarded() {
+//!     let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+//!         let mut x = 0u32;
+//!         loop {
+//!             freeze!(&mut x);
+//!             x += 1;
+//!         }
+//!     }));
+//!
+//!     let guard = scope.enter_guard();
+//!     scope.enter(|x| *x);
+//!     assert_eq!(*guard, 0);
+//! }
+//! ```
+//!
+//! # Keeping a `ScopeGuard` after dropping its scope
+//!
+//! ```compile_fail,E0505
+//! use nolife::{scope, BoxScope, SingleFamily};
+//!
+//! fn guard_outlives_scope() {
+//!     let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+//!         freeze_forever!(&mut 0)
+//!     }));
+//!
+//!     let guard = scope.enter_guard();
+//!     drop(scope);
+//!     assert_eq!(*guard, 0);
+//! }
+//! ```
+//!
+//! # Swapping data with different lifetimes in a `Family2`
+//!
+//! ```compile_fail
+//! use nolife::{scope, BoxScope, Family2, TwoLifetimes};
+//!
+//! struct Pair<'a, 'b> {
+//!     x: &'a mut u32,
+//!     y: &'b mut u32,
+//! }
+//!
+//! struct PairFamily;
+//! impl<'a, 'b> Family2<'a, 'b> for PairFamily {
+//!     type Family = Pair<'a, 'b>;
+//! }
+//!
+//! fn swap_lifetimes() {
+//!     let mut scope = BoxScope::<TwoLifetimes<PairFamily>>::new_dyn(scope!({
+//!         let mut x = 0;
+//!         let mut y = 1;
+//!         let mut pair = Pair { x: &mut x, y: &mut y };
+//!         loop {
+//!             freeze2!(&mut pair);
+//!         }
+//!     }));
+//!
+//!     scope.enter(|pair| pair.with(|pair| std::mem::swap(&mut pair.x, &mut pair.y)));
+//! }
+//! ```
+//!
+//! # Entering a scope from its own `enter`
+//!
+//! ```compile_fail,E0499
+//! use nolife::{scope, BoxScope, SingleFamily};
+//!
+//! fn nested_enter() {
+//!     let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+//!         let mut x = 0u32;
+//!         loop {
+//!             freeze!(&mut x);
+//!             x += 1;
+//!         }
+//!     }));
+//!
+//!     scope.enter(|outer| scope.enter(|inner| *outer + *inner));
+//! }
+//! ```
+//!
+//! # Storing a future that doesn't fit in an `InlineScope`
+//!
+//! ```compile_fail,E0080
+//! use nolife::{scope, InlineScope, SingleFamily};
+//!
+//! let scope = InlineScope::<SingleFamily<u32>, 8>::new(scope!({
+//!     let mut x = [0u32; 64];
+//!     freeze_forever!(&mut x[0])
+//! }));
+//! ```
+//!
+//! # Forgetting to await a freeze
+//!
+//! ```compile_fail
+//! #![deny(unused_must_use)]
+//! use nolife::{Never, SingleFamily, TimeCapsule};
+//!
+//! async fn forgets_await(mut time_capsule: TimeCapsule<SingleFamily<u32>>) -> Never {
+//!     let mut x = 0u32;
+//!     loop {
+//!         time_capsule.freeze(&mut x);
+//!         x += 1;
+//!     }
+//! }
+//! ```
+//!
+//! # Keeping a reference returned by `enter` after dropping the scope
+//!
+//! The output of `enter` can borrow the frozen data, but such a reference keeps the scope borrowed,
+//! so the scope cannot be dropped while the reference is used.
+//!
+//! ```compile_fail,E0505
+//! use nolife::{scope, BoxScope, RefFamily};
+//!
+//! fn escape_after_drop() {
+//!     let mut scope = BoxScope::<RefFamily<str>>::new_dyn(scope!({
+//!         let line = String::from("frozen");
+//!         loop {
+//!             freeze!(&mut line.as_str());
+//!         }
+//!     }));
+//!
+//!     let line: &str = scope.enter(|line| *line);
+//!     drop(scope);
+//!     println!("{line}");
+//! }
+//! ```
+//!
+//! # Keeping a reference returned by `enter` while entering again
+//!
+//! Entering the scope again resumes the underlying future, which could mutate or drop the frozen data,
+//! so no reference returned by a previous `enter` can be used across the call.
+//!
+//! ```compile_fail,E0499
+//! use nolife::{scope, BoxScope, SingleFamily};
+//!
+//! fn escape_across_enter() {
+//!     let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+//!         let mut x = 0u32;
+//!         loop {
+//!             freeze!(&mut x);
+//!             x += 1;
+//!         }
+//!     }));
+//!
+//!     let x: &mut u32 = scope.enter(|x| x);
+//!     scope.enter(|_| ());
+//!     assert_eq!(*x, 0);
+//! }
+//! ```
+//!
+//! # Returning the frozen data from `enter_into`
+//!
+//! The output of `enter_into` can only borrow from the arena, never from the frozen data.
+//!
+//! ```compile_fail
+//! use core::cell::OnceCell;
+//! use nolife::{scope, BoxScope, SingleFamily};
+//!
+//! fn escape_arena() {
+//!     let mut scope = BoxScope::<SingleFamily<String>>::new_dyn(scope!({
+//!         let mut line = String::from("frozen");
+//!         freeze_forever!(&mut line)
+//!     }));
+//!
+//!     let arena: OnceCell<String> = OnceCell::new();
+//!     let line: &String = scope.enter_into(&arena, |line, _| &*line);
+//!     println!("{line}");
+//! }
+//! ```
+//!
+//! # Resetting a scope while iterating over it
+//!
+//! Adapters such as `ScopeIter` borrow the scope, so they can never step a future that replaced the one they started with.
+//!
+//! ```compile_fail,E0499
+//! use nolife::{scope, BoxScope, SingleFamily, TopScope};
+//!
+//! fn counter(start: u32) -> impl TopScope<Family = SingleFamily<u32>> {
+//!     scope!({
+//!         let mut x = start;
+//!         loop {
+//!             freeze!(&mut x);
+//!             x += 1;
+//!         }
+//!     })
+//! }
+//!
+//! fn reset_while_iterating() {
+//!     let mut scope = BoxScope::new(counter(0));
+//!     let mut iter = scope.iter(|x| *x);
+//!     iter.next();
+//!     scope.reset(counter(10));
+//!     iter.next();
+//! }
+//! ```
+//!
+//! # Returning the scope passed to the body of `scope_fn`
+//!
+//! The scope is dropped when the body returns, so the reference passed to the body cannot escape it.
+//!
+//! ```compile_fail
+//! use nolife::{scope, scope_fn, SingleFamily};
+//!
+//! fn escape_body() {
+//!     let scope = scope_fn(
+//!         scope!(SingleFamily<u32>, {
+//!             freeze_forever!(&mut 0)
+//!         }),
+//!         |scope| scope,
+//!     );
+//!     scope.enter(|x| *x);
+//! }
+//! ```
+//!
+//! # Returning frozen data as data borrowed from the caller
+//!
+//! The output of `enter` can borrow data provided by the caller for as long as the caller keeps it alive,
+//! but frozen data can only be borrowed for as long as the scope is borrowed.
+//!
+//! ```compile_fail,E0621
+//! use nolife::{BoxScope, RefFamily};
+//!
+//! fn find_or_line<'ret>(scope: &mut BoxScope<RefFamily<str>>, words: &'ret [String]) -> &'ret str {
+//!     scope.enter(|line| {
+//!         words
+//!             .iter()
+//!             .map(String::as_str)
+//!             .find(|word| word == line)
+//!             .unwrap_or(*line)
+//!     })
+//! }
+//! ```
+//!
+//! # Capturing a short-lived borrow in a `'static` scope
+//!
+//! `BoxScope::new_dyn` erases the future into a `'static` trait object, so the scope cannot borrow local data.
+//!
+//! ```compile_fail,E0597
+//! use nolife::{scope, BoxScope, SingleFamily};
+//!
+//! fn counter_from_local() -> BoxScope<SingleFamily<u32>> {
+//!     let start = 42u32;
+//!     let start = &start;
+//!     BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+//!         let mut x = *start;
+//!         freeze_forever!(&mut x)
+//!     }))
+//! }
+//! ```
+//!
+//! # Keeping a scope after the data it borrows is dropped
+//!
+//! `BoxScope::new` keeps the lifetime of the future in the type of the scope, which cannot outlive the borrowed data.
+//!
+//! ```compile_fail,E0597
+//! use nolife::{scope, BoxScope, SingleFamily};
+//!
+//! fn scope_outlives_local() {
+//!     let mut scope = {
+//!         let start = 42u32;
+//!         let start = &start;
+//!         BoxScope::new(scope!(SingleFamily<u32>, {
+//!             let mut x = *start;
+//!             freeze_forever!(&mut x)
+//!         }))
+//!     };
+//!     scope.enter(|x| *x);
+//! }
+//! ```
+//!
+//! # Keeping the `'static` data of a `StaticFamily` while entering again
+//!
+//! The data frozen with `freeze_static` lives for `'static`, but `enter` only lends it for the borrow of the scope,
+//! so that the scope cannot resume while the data is still used.
+//!
+//! ```compile_fail,E0499
+//! use nolife::{scope, BoxScope, SingleFamily, StaticFamily};
+//!
+//! fn keep_static() {
+//!     let mut scope = BoxScope::<StaticFamily<SingleFamily<u32>>>::new_dyn(scope!({
+//!         let mut x: &'static mut u32 = Box::leak(Box::new(0));
+//!         loop {
+//!             x = freeze_static!(x);
+//!             *x += 1;
+//!         }
+//!     }));
+//!     let kept = scope.enter(|x| &mut **x);
+//!     scope.enter(|x| **x += 1);
+//!     *kept += 1;
+//! }
+//! ```
@@ -0,0 +1,144 @@
+use alloc::boxed::Box;
+use core::future::Future;
+
+use crate::{allocator::Allocator, panics, BoxScope, Family, ScopeError};
+
+/// An object-safe interface to enter scopes of the same family, regardless of their future and allocator.
+///
+/// Scopes with different futures or allocators have distinct types, even when they share their family.
+/// Boxing them as `Box<dyn DynScope<T>>`, for instance with [`BoxScope::into_dyn`], allows storing them together.
+///
+/// The methods of the trait take the passed function as a `&mut dyn FnMut` to remain object-safe.
+/// Use [`enter`](#method.enter) and [`try_enter`](#method.try_enter) on `dyn DynScope<T>`
+/// to pass a `FnOnce` returning an output instead.
+///
+/// # Example
+///
+/// ```
+/// use nolife::{scope, BoxScope, DynScope, SingleFamily};
+///
+/// let counter = BoxScope::new(scope!({
+///     let mut x = 0u32;
+///     loop {
+///         freeze!(&mut x);
+///         x += 1;
+///     }
+/// }));
+/// let constant = BoxScope::new(scope!({
+///     freeze_forever!(&mut 42u32)
+/// }));
+///
+/// let mut scopes: Vec<Box<dyn DynScope<SingleFamily<u32>>>> = vec![counter.into_dyn(), constant.into_dyn()];
+///
+/// let values: Vec<u32> = scopes.iter_mut().map(|scope| scope.enter(|x| *x)).collect();
+/// assert_eq!(values, [0, 42]);
+/// ```
+pub trait DynScope<T>
+where
+    T: for<'a> Family<'a> + 'static,
+{
+    /// Enters the scope, calling `f` on the data frozen inside of the scope.
+    ///
+    /// See [`BoxScope::try_enter`].
+    ///
+    /// # Errors
+    ///
+    /// - The [`ScopeError`] preventing the access to the frozen data, `f` is then not called.
+    ///
+    /// # Panics
+    ///
+    /// - If the passed function panics.
+    /// - If the underlying future panics. The scope is then poisoned.
+    fn try_enter_dyn(
+        &mut self,
+        f: &mut dyn for<'a, 'b> FnMut(&'b mut <T as Family<'a>>::Family),
+    ) -> Result<(), ScopeError>;
+
+    /// Whether the underlying future panicked, in which case the scope can no longer be entered.
+    ///
+    /// See [`BoxScope::is_poisoned`].
+    fn is_poisoned(&self) -> bool;
+}
+
+impl<T, F: ?Sized, A> DynScope<T> for BoxScope<T, F, A>
+where
+    T: for<'a> Family<'a> + 'static,
+    F: Future,
+    A: Allocator,
+{
+    fn try_enter_dyn(
+        &mut self,
+        f: &mut dyn for<'a, 'b> FnMut(&'b mut <T as Family<'a>>::Family),
+    ) -> Result<(), ScopeError> {
+        self.try_enter(|frozen| f(frozen))
+    }
+
+    fn is_poisoned(&self) -> bool {
+        BoxScope::is_poisoned(self)
+    }
+}
+
+impl<T> dyn DynScope<T> + '_
+where
+    T: for<'a> Family<'a> + 'static,
+{
+    /// Enters the scope, making it possible to access the data frozen inside of the scope.
+    ///
+    /// # Panics
+    ///
+    /// - If the passed function panics.
+    /// - In the same cases as [`BoxScope::enter`].
+    pub fn enter<Output, G>(&mut self, f: G) -> Output
+    where
+        G: for<'a, 'b> FnOnce(&'b mut <T as Family<'a>>::Family) -> Output,
+    {
+        match self.try_enter(f) {
+            Ok(output) => output,
+            Err(err) => panics::scope_error(err),
+        }
+    }
+
+    /// Enters the scope, making it possible to access the data frozen inside of the scope.
+    ///
+    /// # Errors
+    ///
+    /// - In the same cases as [`BoxScope::try_enter`].
+    ///
+    /// # Panics
+    ///
+    /// - If the passed function panics.
+    /// - If the underlying future panics. The scope is then poisoned.
+    pub fn try_enter<Output, G>(&mut self, f: G) -> Result<Output, ScopeError>
+    where
+        G: for<'a, 'b> FnOnce(&'b mut <T as Family<'a>>::Family) -> Output,
+    {
+        let mut f = Some(f);
+        let mut output = None;
+        self.try_enter_dyn(&mut |frozen| {
+            // `f` is only called once, as the scope calls the passed function at most once per call.
+            if let Some(f) = f.take() {
+                output = Some(f(frozen));
+            }
+        })?;
+        // the passed function is always called when entering succeeds
+        let Some(output) = output else {
+            panics::entered_without_calling()
+        };
+        Ok(output)
+    }
+}
+
+impl<T, F: ?Sized, A> BoxScope<T, F, A>
+where
+    T: for<'a> Family<'a> + 'static,
+    F: Future + 'static,
+    A: Allocator + 'static,
+{
+    /// Boxes the scope as a [`DynScope`], erasing the types of its future and allocator.
+    ///
+    /// This allows storing scopes of the same family but with different futures together,
+    /// see [`DynScope`].
+    pub fn into_dyn(self) -> Box<dyn DynScope<T>> {
+        Box::new(self)
+    }
+}
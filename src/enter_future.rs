@@ -0,0 +1,262 @@
+use core::{
+    future::Future,
+    mem::{self, ManuallyDrop},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::{
+    allocator::{Allocator, Global},
+    panics, BoxScope, Completed, Family, ScopeError,
+};
+
+/// A function passed to [`BoxScope::enter_async`], returning a future that borrows the data frozen inside of the scope.
+///
+/// This is implemented for any `FnOnce(Arg) -> Fut` where `Fut` is a [`Future`], including async closures.
+/// `Arg` is the reference to the frozen data, `&'borrow mut <T as Family<'a>>::Family`.
+///
+/// Contrary to a bound on `FnOnce` with a single future type, the type of the returned future may depend on
+/// the lifetime `'a` of the frozen data, so that the future can hold the frozen reference across its `.await`s,
+/// for instance when the family is a reference such as [`crate::MutFamily`].
+/// [`BoxScope::enter_async`] requires its output not to depend on `'a`, so the frozen data cannot escape the future.
+pub trait EnterAsyncFn<Arg>: FnOnce(Arg) -> Self::Future {
+    /// The future returned by the function.
+    type Future: Future;
+}
+
+impl<Arg, G, Fut> EnterAsyncFn<Arg> for G
+where
+    G: FnOnce(Arg) -> Fut,
+    Fut: Future,
+{
+    type Future = Fut;
+}
+
+/// The future returned by the passed function, with the lifetime of the frozen data erased.
+///
+/// The future is only polled and dropped while the scope is borrowed by the [`EnterFuture`], so the frozen data
+/// outlives it regardless of its actual lifetime, the same way [`crate::raw_scope::RawScope`] stores the frozen data.
+type ErasedFuture<'borrow, T, G> =
+    <G as EnterAsyncFn<&'borrow mut <T as Family<'static>>::Family>>::Future;
+
+/// The future returned by [`BoxScope::enter_async`].
+pub struct EnterFuture<'borrow, T, F: ?Sized, G, A = Global>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+    G: for<'a> EnterAsyncFn<&'borrow mut <T as Family<'a>>::Family>,
+{
+    scope: &'borrow mut BoxScope<T, F, A>,
+    state: EnterState<G, ErasedFuture<'borrow, T, G>>,
+}
+
+enum EnterState<G, Fut> {
+    /// Waiting for the underlying future to freeze.
+    Entering(G),
+    /// Running the future returned by the passed function. This field is structurally pinned.
+    Running(Fut),
+    /// The future returned by the passed function completed, or the passed function panicked.
+    Done,
+}
+
+impl<T, F: ?Sized, A> BoxScope<T, F, A>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+{
+    /// Enters the scope asynchronously, making it possible to access the data frozen inside of the scope.
+    ///
+    /// Contrary to [`Self::enter`], the underlying future is polled with the context of the caller's executor.
+    /// This allows the scope to await arbitrary futures between two freezes, such as asynchronous I/O:
+    /// when the underlying future is pending on a future other than the [`crate::FrozenFuture`],
+    /// the returned future is pending too, and is woken up by the caller's executor when the scope can make progress.
+    ///
+    /// The passed function is called once the underlying future freezes, and returns a future that is then awaited.
+    /// This future can borrow the frozen data and await while holding it: the scope is borrowed for as long as
+    /// the returned future exists, so the underlying future cannot resume until the future returned by
+    /// the passed function completed and the returned future is dropped.
+    ///
+    /// The type of the future returned by the passed function may depend on the lifetime of the frozen data,
+    /// see [`EnterAsyncFn`]. This allows async closures to hold references frozen with families such as
+    /// [`crate::MutFamily`] for the whole execution of the future. A closure returning an `async move` block
+    /// can only do so when the family does not borrow, such as [`crate::SingleFamily`], as the type of the block
+    /// cannot depend on the lifetime of the argument of the closure.
+    ///
+    /// # Panics
+    ///
+    /// When polled, the returned future panics:
+    ///
+    /// - If the passed function or the future it returned panics.
+    /// - If the underlying future panics.
+    /// - If the underlying future panicked in a previous call.
+    /// - If the underlying future completed, see [`Self::enter_or_complete`].
+    /// - If the underlying future froze its data with [`crate::TimeCapsule::freeze_ref`].
+    /// - If polled again after completion.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, SingleFamily};
+    /// # use core::{future::Future, pin::Pin, task::{Context, Poll}};
+    /// #
+    /// # /// A future that is pending once before it is ready.
+    /// # struct YieldNow(bool);
+    /// # impl Future for YieldNow {
+    /// #     type Output = ();
+    /// #     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+    /// #         if self.0 {
+    /// #             return Poll::Ready(());
+    /// #         }
+    /// #         self.0 = true;
+    /// #         cx.waker().wake_by_ref();
+    /// #         Poll::Pending
+    /// #     }
+    /// # }
+    /// # async fn some_async_io() -> u32 {
+    /// #     YieldNow(false).await;
+    /// #     1
+    /// # }
+    ///
+    /// async fn read_all() {
+    ///     let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+    ///         let mut x = 0;
+    ///         loop {
+    ///             freeze!(&mut x);
+    ///             x += some_async_io().await;
+    ///         }
+    ///     }));
+    ///
+    ///     assert_eq!(scope.enter_async(|x| async move { *x }).await, 0);
+    ///     let x = scope
+    ///         .enter_async(|x| async move {
+    ///             *x += some_async_io().await;
+    ///             *x
+    ///         })
+    ///         .await;
+    ///     assert_eq!(x, 2);
+    /// }
+    /// ```
+    ///
+    /// The returned future can keep frozen references across its `.await`s:
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, MutFamily};
+    ///
+    /// async fn increment(x: &mut u32) {
+    ///     *x += 1;
+    /// }
+    ///
+    /// async fn read_all() {
+    ///     let mut scope = BoxScope::<MutFamily<u32>>::new_dyn(scope!({
+    ///         let mut x = 0;
+    ///         loop {
+    ///             freeze!(&mut &mut x);
+    ///         }
+    ///     }));
+    ///
+    ///     scope
+    ///         .enter_async(async |x: &mut &mut u32| {
+    ///             increment(x).await;
+    ///             increment(x).await;
+    ///         })
+    ///         .await;
+    ///     assert_eq!(scope.enter(|x| **x), 2);
+    /// }
+    /// # let _ = read_all();
+    /// ```
+    pub fn enter_async<'borrow, Output, G>(
+        &'borrow mut self,
+        f: G,
+    ) -> EnterFuture<'borrow, T, F, G, A>
+    where
+        G: for<'a> EnterAsyncFn<
+            &'borrow mut <T as Family<'a>>::Family,
+            Future: Future<Output = Output>,
+        >,
+    {
+        EnterFuture {
+            scope: self,
+            state: EnterState::Entering(f),
+        }
+    }
+}
+
+impl<'borrow, T, F: ?Sized, G, A, Output> Future for EnterFuture<'borrow, T, F, G, A>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+    G: for<'a> EnterAsyncFn<
+        &'borrow mut <T as Family<'a>>::Family,
+        Future: Future<Output = Output>,
+    >,
+{
+    type Output = Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: only `EnterState::Running` is structurally pinned, and it is never moved out of.
+        let this = unsafe { self.get_unchecked_mut() };
+        loop {
+            match &mut this.state {
+                EnterState::Entering(_) => {
+                    let EnterState::Entering(f) = mem::replace(&mut this.state, EnterState::Done)
+                    else {
+                        panics::enter_future_state_changed()
+                    };
+                    let mut f = Some(f);
+                    // SAFETY: if `f` is called, `self.state` is never `Entering` again so the scope is never accessed
+                    // again through `self`. `self.scope` borrows the scope for `'borrow`, so it is not accessed otherwise either.
+                    let result = unsafe {
+                        this.scope.enter_or_complete_with(cx, |frozen| {
+                            // `f` is only taken if the scope froze
+                            let Some(f) = f.take() else {
+                                panics::entered_twice()
+                            };
+                            erase_future::<T, G>(f(frozen))
+                        })
+                    };
+                    match result {
+                        Ok(Ok(fut)) => this.state = EnterState::Running(fut),
+                        // the underlying future is waiting on another future, that will wake `cx` up.
+                        Err(ScopeError::NotFrozen) => {
+                            let Some(f) = f else {
+                                panics::entered_without_freezing()
+                            };
+                            this.state = EnterState::Entering(f);
+                            return Poll::Pending;
+                        }
+                        Ok(Err(Completed(_))) => panics::scope_error(ScopeError::Completed),
+                        Err(err) => panics::scope_error(err),
+                    }
+                }
+                EnterState::Running(fut) => {
+                    // SAFETY: `fut` is structurally pinned, see above.
+                    let fut = unsafe { Pin::new_unchecked(fut) };
+                    let Poll::Ready(output) = fut.poll(cx) else {
+                        return Poll::Pending;
+                    };
+                    this.state = EnterState::Done;
+                    return Poll::Ready(output);
+                }
+                EnterState::Done => panics::enter_future_polled_after_completion(),
+            }
+        }
+    }
+}
+
+/// Erases the lifetime of the frozen data from the type of the future returned by the passed function.
+fn erase_future<'a, 'borrow, T, G>(
+    fut: <G as EnterAsyncFn<&'borrow mut <T as Family<'a>>::Family>>::Future,
+) -> ErasedFuture<'borrow, T, G>
+where
+    T: for<'b> Family<'b>,
+    G: for<'b> EnterAsyncFn<&'borrow mut <T as Family<'b>>::Family>,
+{
+    let fut = ManuallyDrop::new(fut);
+    // SAFETY: `G` implements `EnterAsyncFn` for all lifetimes with a single impl, so the two future types only differ
+    // by their lifetime, and have the same layout. The future is only polled and dropped while the scope
+    // is borrowed by the `EnterFuture`, during which the frozen data is valid, see `ErasedFuture`.
+    unsafe { mem::transmute_copy(&*fut) }
+}
@@ -0,0 +1,79 @@
+//! Ready-made families for common borrowed types of the standard library.
+//!
+//! These families save you from declaring your own helper types with [`crate::family!`] for the most common cases.
+//!
+//! # Example
+//!
+//! ```
+//! use nolife::{families::StrFamily, scope, BoxScope};
+//!
+//! let mut scope = BoxScope::<StrFamily>::new_dyn(scope!({
+//!     let s = String::from("Intel the Beagle");
+//!     freeze_forever!(&mut s.as_str())
+//! }));
+//!
+//! assert_eq!(scope.enter(|s| s.len()), 16);
+//! ```
+
+use alloc::{string::String, vec::Vec};
+
+crate::family! {
+    /// Mutably borrows a [`Vec`].
+    pub VecFamily<'a, T: 'static> = &'a mut Vec<T>;
+    /// Mutably borrows a [`String`].
+    pub StringFamily<'a> = &'a mut String;
+    /// Borrows a slice.
+    pub SliceFamily<'a, T: 'static> = &'a [T];
+    /// Mutably borrows a slice.
+    pub SliceMutFamily<'a, T: 'static> = &'a mut [T];
+    /// Borrows some bytes.
+    pub BytesFamily<'a> = &'a [u8];
+    /// Borrows a string slice.
+    pub StrFamily<'a> = &'a str;
+    /// Mutably borrows a callback, that the caller of [`BoxScope::enter`](crate::BoxScope::enter) can invoke.
+    ///
+    /// ```
+    /// use nolife::{families::DynFnMutFamily, scope, BoxScope};
+    ///
+    /// let mut scope = BoxScope::<DynFnMutFamily>::new_dyn(scope!({
+    ///     let mut clicks = 0;
+    ///     loop {
+    ///         let mut on_click: &mut dyn FnMut() = &mut || clicks += 1;
+    ///         freeze!(&mut on_click);
+    ///         println!("clicked {clicks} times");
+    ///     }
+    /// }));
+    ///
+    /// scope.enter(|on_click| on_click());
+    /// ```
+    pub DynFnMutFamily<'a> = &'a mut (dyn FnMut() + 'a);
+}
+
+/// Mutably borrows an array of `N` elements.
+///
+/// # Example
+///
+/// ```
+/// use nolife::{families::ArrayFamily, scope, BoxScope};
+///
+/// let mut scope = BoxScope::<ArrayFamily<u8, 4>>::new_dyn(scope!({
+///     let mut buffer = [0; 4];
+///     loop {
+///         freeze!(&mut &mut buffer);
+///         buffer.rotate_left(1);
+///     }
+/// }));
+///
+/// scope.enter(|buffer| buffer[0] = 1);
+/// assert_eq!(scope.enter(|buffer| **buffer), [0, 0, 0, 1]);
+/// ```
+pub struct ArrayFamily<T: 'static, const N: usize>(core::marker::PhantomData<fn() -> T>);
+impl<'a, T: 'static, const N: usize> crate::Family<'a> for ArrayFamily<T, N> {
+    type Family = &'a mut [T; N];
+}
+
+#[cfg(feature = "std")]
+crate::family! {
+    /// Mutably borrows a [`HashMap`](std::collections::HashMap).
+    pub HashMapFamily<'a, K: 'static, V: 'static> = &'a mut std::collections::HashMap<K, V>;
+}
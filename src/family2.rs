@@ -0,0 +1,156 @@
+use core::{marker::PhantomData, mem, ptr::NonNull};
+
+use crate::{Family, TimeCapsule};
+
+/// Describes a family of types containing two independent lifetimes.
+///
+/// This is the two-lifetime counterpart of [`Family`], for borrowed types such as
+/// `struct Pair<'a, 'b> { x: &'a mut A, y: &'b mut B }`, that cannot be expressed with a single lifetime
+/// without collapsing both lifetimes into one.
+///
+/// A `Family2` is used through the [`TwoLifetimes`] adapter: a scope of family `TwoLifetimes<MyFamily2>`
+/// freezes its data with the `freeze2!` macro, and its frozen data is then accessed through [`Frozen2::with`].
+///
+/// Similarly to [`Family`], the helper type must implement `Family2` for all pairs of lifetimes,
+/// which is expressed by the higher-ranked bound `T: for<'a, 'b> Family2<'a, 'b>`.
+///
+/// # Example
+///
+/// ```
+/// use nolife::{scope, BoxScope, Family2, TwoLifetimes};
+///
+/// struct Pair<'a, 'b> {
+///     x: &'a mut String,
+///     y: &'b mut Vec<u32>,
+/// }
+///
+/// struct PairFamily;
+/// impl<'a, 'b> Family2<'a, 'b> for PairFamily {
+///     type Family = Pair<'a, 'b>;
+/// }
+///
+/// let mut scope = BoxScope::<TwoLifetimes<PairFamily>>::new_dyn(scope!({
+///     let mut x = String::from("hello");
+///     let mut y = vec![1, 2, 3];
+///     let mut pair = Pair { x: &mut x, y: &mut y };
+///     loop {
+///         freeze2!(&mut pair);
+///         pair.y.push(pair.x.len() as u32);
+///     }
+/// }));
+///
+/// scope.enter(|pair| pair.with(|pair| pair.x.push('!')));
+/// let y = scope.enter(|pair| pair.with(|pair| pair.y.clone()));
+/// assert_eq!(y, [1, 2, 3, 6]);
+/// ```
+pub trait Family2<'a, 'b> {
+    /// An instance with lifetimes `'a` and `'b` of the family.
+    ///
+    /// Like [`Family::Family`], it can be unsized.
+    type Family: ?Sized;
+}
+
+/// Adapter implementing [`Family`] for a [`Family2`].
+///
+/// The family of `TwoLifetimes<T>` is [`Frozen2`], that gives access to the data frozen with the `freeze2!` macro
+/// while keeping both of its lifetimes independent.
+pub struct TwoLifetimes<T>(PhantomData<fn() -> T>);
+
+impl<'a, T> Family<'a> for TwoLifetimes<T>
+where
+    T: for<'x, 'y> Family2<'x, 'y> + 'static,
+{
+    type Family = Frozen2<'a, T>;
+}
+
+/// Handle to the data frozen in a scope of family [`TwoLifetimes`].
+///
+/// The handle only gives access to the data through [`Self::with`] and [`Self::with_ref`], whose function
+/// must accept the data for any pair of lifetimes. This prevents swapping parts of the data that have
+/// different lifetimes, as it could leave the scope with dangling references once it resumes.
+pub struct Frozen2<'a, T>
+where
+    T: for<'x, 'y> Family2<'x, 'y>,
+{
+    // Like `State`, the lifetimes are erased: they cannot be observed by the higher-ranked functions
+    // passed to `with` and `with_ref`.
+    ptr: NonNull<<T as Family2<'static, 'static>>::Family>,
+    marker: PhantomData<&'a mut ()>,
+}
+
+// SAFETY: `Frozen2` is equivalent to an exclusive reference to the frozen data.
+unsafe impl<'a, T> Send for Frozen2<'a, T>
+where
+    T: for<'x, 'y> Family2<'x, 'y>,
+    for<'x, 'y> <T as Family2<'x, 'y>>::Family: Send,
+{
+}
+
+// SAFETY: `Frozen2` is equivalent to an exclusive reference to the frozen data.
+unsafe impl<'a, T> Sync for Frozen2<'a, T>
+where
+    T: for<'x, 'y> Family2<'x, 'y>,
+    for<'x, 'y> <T as Family2<'x, 'y>>::Family: Sync,
+{
+}
+
+impl<'a, T> Frozen2<'a, T>
+where
+    T: for<'x, 'y> Family2<'x, 'y>,
+{
+    /// Accesses the frozen data.
+    pub fn with<Output, G>(&mut self, f: G) -> Output
+    where
+        G: for<'x, 'y> FnOnce(&mut <T as Family2<'x, 'y>>::Family) -> Output,
+    {
+        // SAFETY:
+        // - the pointer comes from an exclusive reference in `TimeCapsule::freeze2`, that lives as long as `self`.
+        // - aliasing: `self` is borrowed exclusively, and the reference cannot escape `f`.
+        // - lifetimes: `f` accepts the data for any pair of lifetimes, so it cannot rely on them being `'static`.
+        f(unsafe { self.ptr.as_mut() })
+    }
+
+    /// Reads the frozen data.
+    pub fn with_ref<Output, G>(&self, f: G) -> Output
+    where
+        G: for<'x, 'y> FnOnce(&<T as Family2<'x, 'y>>::Family) -> Output,
+    {
+        // SAFETY: see `Frozen2::with`, with `self` borrowed shared.
+        f(unsafe { self.ptr.as_ref() })
+    }
+}
+
+impl<'a, T> core::fmt::Debug for Frozen2<'a, T>
+where
+    T: for<'x, 'y> Family2<'x, 'y>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Frozen2").finish_non_exhaustive()
+    }
+}
+
+impl<T> TimeCapsule<TwoLifetimes<T>>
+where
+    T: for<'x, 'y> Family2<'x, 'y> + 'static,
+{
+    /// Freeze a scope, making data with two independent lifetimes available to the outside.
+    ///
+    /// Once a scope is frozen, its borrowed data can be accessed through [`crate::BoxScope::enter`]
+    /// and [`Frozen2::with`].
+    pub async fn freeze2<'a, 'b, 'c>(&'a mut self, t: &'a mut <T as Family2<'b, 'c>>::Family)
+    where
+        'b: 'a,
+        'c: 'a,
+    {
+        let ptr = NonNull::from(t);
+        // SAFETY: `T` implements `Family2` for all pairs of lifetimes, so the two pointees only differ by their
+        // lifetimes, and the pointers have the same layout and metadata. `NonNull::cast` would drop the metadata
+        // of unsized families.
+        let ptr: NonNull<<T as Family2<'static, 'static>>::Family> = unsafe { mem::transmute(ptr) };
+        let mut frozen = Frozen2::<'a, T> {
+            ptr,
+            marker: PhantomData,
+        };
+        self.freeze(&mut frozen).await
+    }
+}
@@ -0,0 +1,289 @@
+use core::{
+    future::Future,
+    marker::{PhantomData, PhantomPinned},
+    mem::{self, MaybeUninit},
+    pin::Pin,
+    ptr::{addr_of, addr_of_mut, NonNull},
+};
+
+use crate::{panics, raw_scope::RawScope, Family, Never, ScopeError, TopScope};
+
+/// The maximal alignment of the scopes stored in an [`InlineScope`].
+const ALIGN: usize = 16;
+
+/// The opened scope, with the type of its future erased.
+type DynRawScope<T> = RawScope<T, dyn Future<Output = Never>>;
+
+/// Storage for either the unopened scope, or the opened `RawScope`.
+#[repr(C, align(16))]
+struct Buffer<const N: usize>([MaybeUninit<u8>; N]);
+
+/// What the buffer of an [`InlineScope`] currently holds.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Contents {
+    /// The `TopScope` passed to [`InlineScope::new`], not yet run.
+    Unopened,
+    /// A fully initialized `RawScope`.
+    Opened,
+    /// Running the scope panicked: the buffer holds no future, and must not be dropped.
+    Vacant,
+}
+
+/// A scope stored inline, without allocating.
+///
+/// The future of the scope is stored in an inline buffer of `N` bytes, so that this kind of scopes doesn't allocate.
+/// Like [`crate::BoxScope`], the type of the future is erased, so an `InlineScope` can be stored in a struct
+/// and returned from functions.
+///
+/// In exchange, the scope must be pinned before it can be entered, as the future borrows from the scope itself.
+/// The future is only created when the scope is entered for the first time, so that it is created in place.
+///
+/// Creating an `InlineScope` whose scope or future doesn't fit in `N` bytes, or has an alignment greater than 16,
+/// fails to compile.
+///
+/// # Example
+///
+/// ```
+/// use core::pin::pin;
+/// use nolife::{scope, InlineScope, SingleFamily};
+///
+/// let mut scope = pin!(InlineScope::<SingleFamily<u32>, 128>::new(scope!({
+///     let mut x = 0u32;
+///     loop {
+///         freeze!(&mut x);
+///         x += 1;
+///     }
+/// })));
+///
+/// assert_eq!(scope.as_mut().enter(|x| *x), 0);
+/// assert_eq!(scope.as_mut().enter(|x| *x), 1);
+/// ```
+pub struct InlineScope<T, const N: usize>
+where
+    T: for<'a> Family<'a>,
+{
+    buffer: Buffer<N>,
+    contents: Contents,
+    /// Reads the scope from the buffer, and writes the opened `RawScope` in its place.
+    open: unsafe fn(NonNull<u8>),
+    /// Drops the unopened scope in the buffer.
+    drop_unopened: unsafe fn(NonNull<u8>),
+    /// Attaches the vtable of the future to a pointer to the buffer.
+    raw_scope: fn(NonNull<u8>) -> NonNull<DynRawScope<T>>,
+    // the future is stored in the buffer, that it borrows once it is opened
+    _pinned: PhantomPinned,
+    // the erased future is not necessarily `Send` nor `Sync`
+    _marker: PhantomData<*mut ()>,
+}
+
+/// # Safety
+///
+/// 1. `buffer` points to a properly aligned buffer that can hold an `S` and a `RawScope<T, S::Future>`,
+///    and currently holds an `S`.
+/// 2. `buffer` verifies the guarantees of `Pin`.
+///
+/// # Post-condition
+///
+/// 1. `buffer` holds a fully initialized `RawScope<T, S::Future>`, unless this function panics.
+unsafe fn open<T, S>(buffer: NonNull<u8>)
+where
+    T: for<'a> Family<'a>,
+    S: TopScope<Family = T>,
+{
+    // SAFETY: precondition (1), the scope is moved out of the buffer, which is overwritten below.
+    let scope = unsafe { buffer.cast::<S>().read() };
+    let raw_scope = buffer.cast::<RawScope<T, MaybeUninit<S::Future>>>();
+    // SAFETY: precondition (1)
+    unsafe { raw_scope.write(RawScope::new_uninit()) };
+    // SAFETY:
+    // 1. `raw_scope` points to the buffer so is valid memory, although the future is not yet initialized
+    // 2. `raw_scope` was initialized from a valid `RawScope::<T, MaybeUninit<F>>`, so `state` is fully initialized.
+    unsafe { RawScope::open(raw_scope.cast::<RawScope<T, S::Future>>().as_ptr(), scope) }
+}
+
+/// # Safety
+///
+/// 1. `buffer` points to a properly aligned buffer that currently holds an `S`, which is not used afterwards.
+unsafe fn drop_unopened<S>(buffer: NonNull<u8>) {
+    // SAFETY: precondition (1)
+    unsafe { buffer.cast::<S>().drop_in_place() }
+}
+
+fn raw_scope<T, F>(buffer: NonNull<u8>) -> NonNull<DynRawScope<T>>
+where
+    T: for<'a> Family<'a>,
+    F: Future<Output = Never> + 'static,
+{
+    let raw_scope: NonNull<RawScope<T, F>> = buffer.cast();
+    raw_scope
+}
+
+impl<T, const N: usize> InlineScope<T, N>
+where
+    T: for<'a> Family<'a>,
+{
+    /// Stores the passed scope inline.
+    ///
+    /// The future of the scope is created when the scope is entered for the first time.
+    ///
+    /// # Compile errors
+    ///
+    /// - If `S` or its future, including the state of the scope, doesn't fit in `N` bytes.
+    /// - If `S` or its future has an alignment greater than 16.
+    pub fn new<S: TopScope<Family = T>>(scope: S) -> Self
+    where
+        S::Future: 'static,
+    {
+        const {
+            assert!(
+                mem::size_of::<S>() <= N && mem::size_of::<RawScope<T, S::Future>>() <= N,
+                "nolife: the scope doesn't fit in the buffer of the `InlineScope`"
+            );
+            assert!(
+                mem::align_of::<S>() <= ALIGN && mem::align_of::<RawScope<T, S::Future>>() <= ALIGN,
+                "nolife: the scope is too aligned for the buffer of the `InlineScope`"
+            );
+        }
+
+        let mut buffer = Buffer([MaybeUninit::uninit(); N]);
+        // SAFETY: the buffer can hold an `S` as checked above.
+        unsafe { buffer.0.as_mut_ptr().cast::<S>().write(scope) };
+        Self {
+            buffer,
+            contents: Contents::Unopened,
+            open: open::<T, S>,
+            drop_unopened: drop_unopened::<S>,
+            raw_scope: raw_scope::<T, S::Future>,
+            _pinned: PhantomPinned,
+            _marker: PhantomData,
+        }
+    }
+
+    fn buffer(&mut self) -> NonNull<u8> {
+        // no reference to the buffer is created, as this would invalidate the pointers of the future to its own state.
+        // SAFETY: `self` is a reference so is non-null.
+        unsafe { NonNull::new_unchecked(addr_of_mut!(self.buffer).cast()) }
+    }
+
+    /// Returns the opened scope, opening it if needed.
+    ///
+    /// # Safety
+    ///
+    /// 1. `self` verifies the guarantees of `Pin`.
+    unsafe fn opened(&mut self) -> Result<NonNull<DynRawScope<T>>, ScopeError> {
+        match self.contents {
+            Contents::Unopened => {
+                let buffer = self.buffer();
+                // the buffer is vacant until it is opened, in case running the scope panics
+                self.contents = Contents::Vacant;
+                // SAFETY:
+                // 1. `self.open` was created for the scope in the buffer in `InlineScope::new`,
+                //    which checked that the buffer can hold it and its `RawScope`.
+                // 2. precondition (1)
+                unsafe { (self.open)(buffer) };
+                self.contents = Contents::Opened;
+                Ok((self.raw_scope)(buffer))
+            }
+            Contents::Opened => Ok((self.raw_scope)(self.buffer())),
+            Contents::Vacant => Err(ScopeError::Poisoned),
+        }
+    }
+
+    /// Enters the scope, making it possible to access the data frozen inside of the scope.
+    ///
+    /// # Panics
+    ///
+    /// - If the passed function panics.
+    /// - If running the scope or its underlying future panics.
+    /// - If running the scope or its underlying future panicked in a previous call to [`Self::enter`] or [`Self::try_enter`].
+    /// - If the underlying future awaits for a future other than the [`crate::FrozenFuture`].
+    /// - If the underlying future froze its data with [`crate::TimeCapsule::freeze_ref`].
+    pub fn enter<'borrow, Output, G>(self: Pin<&'borrow mut Self>, f: G) -> Output
+    where
+        G: for<'a> FnOnce(&'borrow mut <T as Family<'a>>::Family) -> Output,
+    {
+        match self.try_enter(f) {
+            Ok(output) => output,
+            Err(err) => panics::scope_error(err),
+        }
+    }
+
+    /// Enters the scope, making it possible to access the data frozen inside of the scope.
+    ///
+    /// This is the equivalent of [`crate::BoxScope::try_enter`] for inline scopes.
+    ///
+    /// # Errors
+    ///
+    /// - [`ScopeError::Poisoned`] if running the scope or its underlying future panicked in a previous call.
+    /// - [`ScopeError::NotFrozen`] if the underlying future awaits for a future other than the [`crate::FrozenFuture`].
+    /// - [`ScopeError::FrozenShared`] if the underlying future froze its data with [`crate::TimeCapsule::freeze_ref`].
+    ///
+    /// # Panics
+    ///
+    /// - If the passed function panics.
+    /// - If running the scope or its underlying future panics. The scope is then poisoned.
+    pub fn try_enter<'borrow, Output, G>(
+        self: Pin<&'borrow mut Self>,
+        f: G,
+    ) -> Result<Output, ScopeError>
+    where
+        G: for<'a> FnOnce(&'borrow mut <T as Family<'a>>::Family) -> Output,
+    {
+        // SAFETY: the scope is not moved out of the pin.
+        let this = unsafe { self.get_unchecked_mut() };
+        // SAFETY: `this` comes from a `Pin`.
+        let raw_scope = unsafe { this.opened() }?;
+        // SAFETY:
+        // 1. `raw_scope` is valid as a post-condition of `open`.
+        // 2. The object pointed to by `raw_scope` is in the buffer, which is pinned and dropped in place.
+        // 3. `InlineScope::try_enter` takes an exclusive reference and the reference passed to `f` cannot escape `f`.
+        unsafe { RawScope::try_enter(raw_scope, f) }
+    }
+
+    /// Whether running the scope or its underlying future panicked, in which case the scope can no longer be entered.
+    ///
+    /// This function does not poll the underlying future.
+    pub fn is_poisoned(&self) -> bool {
+        match self.contents {
+            Contents::Unopened => false,
+            // SAFETY:
+            // 1. the buffer holds a valid `RawScope` when opened.
+            // 2. `InlineScope::enter` and `InlineScope::try_enter` take an exclusive reference.
+            // 3. `RawScope::is_poisoned` only reads through the pointer, and `self` is a reference so is non-null.
+            Contents::Opened => unsafe {
+                let buffer = NonNull::new_unchecked(addr_of!(self.buffer).cast_mut().cast());
+                RawScope::is_poisoned((self.raw_scope)(buffer))
+            },
+            Contents::Vacant => true,
+        }
+    }
+}
+
+impl<T, const N: usize> Drop for InlineScope<T, N>
+where
+    T: for<'a> Family<'a>,
+{
+    fn drop(&mut self) {
+        let buffer = self.buffer();
+        match self.contents {
+            // SAFETY: the buffer holds the scope, that is not used afterwards.
+            Contents::Unopened => unsafe { (self.drop_unopened)(buffer) },
+            // SAFETY:
+            // 1. the buffer holds a valid `RawScope` when opened.
+            // 2. the buffer is not used afterwards.
+            Contents::Opened => unsafe { RawScope::drop_in_place((self.raw_scope)(buffer)) },
+            Contents::Vacant => {}
+        }
+    }
+}
+
+impl<T, const N: usize> core::fmt::Debug for InlineScope<T, N>
+where
+    T: for<'a> Family<'a>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("InlineScope")
+            .field("opened", &(self.contents == Contents::Opened))
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,148 @@
+use core::{future::Future, iter::FusedIterator};
+
+use crate::{
+    allocator::{Allocator, Global},
+    panics, BoxScope, Family, ScopeError,
+};
+
+/// An [`Iterator`] of owned values extracted from the data frozen inside of a [`BoxScope`].
+///
+/// Created with [`BoxScope::iter`].
+pub struct ScopeIter<'scope, T, F: ?Sized, G, A = Global>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+{
+    scope: &'scope mut BoxScope<T, F, A>,
+    extract: G,
+    terminated: bool,
+}
+
+impl<T, F: ?Sized, A> BoxScope<T, F, A>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+{
+    /// Returns an [`Iterator`] yielding the values extracted by `extract` from the frozen data.
+    ///
+    /// Each call to [`Iterator::next`] enters the scope exactly once, that is, it runs the underlying future
+    /// until it freezes again, and then calls `extract` on the frozen data.
+    ///
+    /// The iterator is infinite for scopes whose future never completes.
+    /// Otherwise, it returns `None` when the underlying future completes, see [`BoxScope::new_with_output`].
+    /// The output of the future is dropped. The iterator also returns `None` if the underlying future panicked.
+    ///
+    /// # Panics
+    ///
+    /// - If `extract` panics.
+    /// - If the underlying future panics.
+    /// - If the underlying future awaits for a future other than the [`crate::FrozenFuture`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, SingleFamily};
+    ///
+    /// let mut scope = BoxScope::<SingleFamily<u32>, _>::new_with_output(scope!({
+    ///     for mut x in 0..5 {
+    ///         freeze!(&mut x);
+    ///     }
+    /// }));
+    ///
+    /// let evens: Vec<_> = scope.iter(|x| *x).filter(|x| x % 2 == 0).collect();
+    /// assert_eq!(evens, [0, 2, 4]);
+    /// ```
+    pub fn iter<O, G>(&mut self, extract: G) -> ScopeIter<'_, T, F, G, A>
+    where
+        G: for<'a> FnMut(&mut <T as Family<'a>>::Family) -> O,
+    {
+        ScopeIter {
+            scope: self,
+            extract,
+            terminated: false,
+        }
+    }
+
+    /// Enters the scope `n + 1` times, returning the value extracted by `extract` from the data frozen last.
+    ///
+    /// As with [`Iterator::nth`], `nth(0)` extracts the value of the next freeze, and the values of the `n`
+    /// previous freezes are skipped without accessing the frozen data. This is the same as
+    /// `self.iter(extract).nth(n)`, except that `extract` is only called once, with a shared reference.
+    ///
+    /// Returns `None` if the underlying future completes before reaching the value, in which case its output is dropped,
+    /// or if the underlying future panicked.
+    ///
+    /// # Panics
+    ///
+    /// - Same as [`BoxScope::iter`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, SingleFamily};
+    ///
+    /// let mut scope = BoxScope::<SingleFamily<u32>, _>::new_with_output(scope!({
+    ///     for mut x in 0..5 {
+    ///         freeze!(&mut x);
+    ///     }
+    /// }));
+    ///
+    /// assert_eq!(scope.nth(0, |x| *x), Some(0));
+    /// assert_eq!(scope.nth(2, |x| *x), Some(3));
+    /// assert_eq!(scope.nth(1, |x| *x), None);
+    /// ```
+    pub fn nth<O, G>(&mut self, n: usize, extract: G) -> Option<O>
+    where
+        G: for<'a> FnOnce(&<T as Family<'a>>::Family) -> O,
+    {
+        for _ in 0..n {
+            self.iter(|_| ()).next()?;
+        }
+        match self.try_enter(|frozen| extract(frozen)) {
+            Ok(item) => Some(item),
+            Err(ScopeError::Completed | ScopeError::Poisoned | ScopeError::Cancelled) => None,
+            Err(err @ (ScopeError::NotFrozen | ScopeError::FrozenShared)) => {
+                panics::scope_error(err)
+            }
+        }
+    }
+}
+
+impl<'scope, T, F: ?Sized, G, A, O> Iterator for ScopeIter<'scope, T, F, G, A>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+    G: for<'a> FnMut(&mut <T as Family<'a>>::Family) -> O,
+{
+    type Item = O;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.terminated {
+            return None;
+        }
+
+        let extract = &mut self.extract;
+        match self.scope.try_enter(|frozen| extract(frozen)) {
+            Ok(item) => Some(item),
+            Err(ScopeError::Completed | ScopeError::Poisoned | ScopeError::Cancelled) => {
+                self.terminated = true;
+                None
+            }
+            Err(err @ (ScopeError::NotFrozen | ScopeError::FrozenShared)) => {
+                panics::scope_error(err)
+            }
+        }
+    }
+}
+
+impl<'scope, T, F: ?Sized, G, A, O> FusedIterator for ScopeIter<'scope, T, F, G, A>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+    G: for<'a> FnMut(&mut <T as Family<'a>>::Family) -> O,
+{
+}
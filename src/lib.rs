@@ -10,22 +10,72 @@
     html_logo_url = "https://raw.githubusercontent.com/dureuill/nolife/main/assets/nolife-tr.png?raw=true"
 )]
 #![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "nightly", feature(coroutine_trait))]
+#![cfg_attr(all(test, feature = "nightly"), feature(coroutines, yield_expr))]
 extern crate alloc;
 
+pub mod allocator;
+#[cfg(feature = "tokio")]
+mod blocking_scope;
 mod box_scope;
+#[cfg(feature = "nightly")]
+mod coroutine;
 #[cfg(not(miri))]
 pub mod counterexamples;
+mod dyn_scope;
+mod enter_future;
+#[cfg(feature = "families")]
+pub mod families;
+mod family2;
+mod inline_scope;
+mod iter;
+mod map_output_scope;
+mod map_scope;
+mod panics;
 mod raw_scope;
+mod rc_scope;
+mod reusable_scope;
 pub mod scope;
+mod scope_builder;
+mod scope_guard;
+mod stack_scope;
+#[cfg(feature = "futures")]
+mod stream;
+#[cfg(feature = "std")]
+mod sync_scope;
 #[doc(hidden)]
-pub use raw_scope::{FrozenFuture, TimeCapsule};
+pub use raw_scope::{FreezeTaggedFuture, FreezeYieldFuture, FrozenFuture, TimeCapsule};
 /// From <https://blog.aloni.org/posts/a-stack-less-rust-coroutine-100-loc/>, originally from
 /// [genawaiter](https://lib.rs/crates/genawaiter).
 mod waker;
+mod zip_scope;
 
-pub use box_scope::BoxScope;
+#[cfg(feature = "tokio")]
+pub use blocking_scope::BlockingScope;
+pub use box_scope::{scope_fn, BoxScope};
+pub use dyn_scope::DynScope;
+pub use enter_future::{EnterAsyncFn, EnterFuture};
+pub use family2::{Family2, Frozen2, TwoLifetimes};
+pub use inline_scope::InlineScope;
+pub use iter::ScopeIter;
+pub use map_output_scope::MapOutputScope;
+pub use map_scope::MapScope;
+#[cfg(feature = "derive")]
+pub use nolife_derive::Family;
+pub use raw_scope::RawScope;
+pub use rc_scope::RcScope;
+pub use reusable_scope::ReusableScope;
 pub use scope::Scope;
 pub use scope::TopScope;
+pub use scope_builder::{PanicPolicy, PoisonPolicy, ScopeBuilder};
+pub use scope_guard::ScopeGuard;
+pub use stack_scope::{stack_scope, StackScope};
+#[cfg(feature = "futures")]
+pub use stream::ScopeStream;
+#[cfg(feature = "std")]
+pub use sync_scope::SyncScope;
+pub use waker::noop_waker;
+pub use zip_scope::{ZipCompleted, ZipScope};
 
 use core::marker::PhantomData;
 
@@ -40,15 +90,251 @@ use core::marker::PhantomData;
 /// Should the [the `!` “never” type][never] ever be stabilized, this type would become a type alias and
 /// eventually be deprecated. See [the relevant section](std::convert::Infallible#future-compatibility)
 /// for more information.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Never {}
 
+/// Error returned by [`BoxScope::try_enter`] when the data frozen inside of a scope cannot be accessed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScopeError {
+    /// The underlying future panicked in a previous call, and can no longer be polled.
+    ///
+    /// A poisoned scope stays poisoned: all future calls will return this error.
+    Poisoned,
+    /// The underlying future returned without freezing the scope.
+    ///
+    /// This typically happens when the scope awaits a future other than the [`FrozenFuture`].
+    NotFrozen,
+    /// The underlying future completed, and can no longer be polled.
+    ///
+    /// This can only happen to scopes created with [`BoxScope::new_with_output`].
+    Completed,
+    /// The underlying future froze its data behind a shared reference, using [`TimeCapsule::freeze_ref`].
+    ///
+    /// Such data can only be accessed with [`BoxScope::enter_ref`].
+    FrozenShared,
+    /// The underlying future was dropped by [`BoxScope::cancel`], and can no longer be polled.
+    ///
+    /// The scope stays cancelled until it is reset with [`BoxScope::reset`].
+    Cancelled,
+}
+
+impl core::fmt::Display for ScopeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ScopeError::Poisoned => {
+                f.write_str("the scope's future panicked and can no longer be polled")
+            }
+            ScopeError::NotFrozen => f.write_str("the scope's future did not fill the value"),
+            ScopeError::Completed => {
+                f.write_str("the scope's future completed and can no longer be polled")
+            }
+            ScopeError::FrozenShared => {
+                f.write_str("the scope's future froze the value behind a shared reference")
+            }
+            ScopeError::Cancelled => {
+                f.write_str("the scope's future was cancelled and can no longer be polled")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ScopeError {}
+
+/// The output of the future of a scope, returned when it completes instead of freezing the scope.
+///
+/// See [`BoxScope::enter_or_complete`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Completed<O>(pub O);
+
+/// Error returned by [`BoxScope::enter_bounded`] when the underlying future did not freeze within the allowed
+/// number of polls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StepLimitExceeded;
+
+impl core::fmt::Display for StepLimitExceeded {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("the scope's future did not freeze within the allowed number of polls")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for StepLimitExceeded {}
+
+/// Error returned by [`BoxScope::enter_until`] when the underlying future did not freeze before the deadline.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimedOut;
+
+#[cfg(feature = "std")]
+impl core::fmt::Display for TimedOut {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("the scope's future did not freeze before the deadline")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TimedOut {}
+
 /// Describes a family of types containing a lifetime.
 ///
 /// This type is typically implemented on a helper type to describe the lifetime of the borrowed data we want to freeze in time.
 /// See [the module documentation](self) for more information.
+///
+/// The borrowed data can be unsized, such as `str`, `[T]` or `dyn Trait + 'a`,
+/// in which case it is frozen behind a fat pointer, that keeps its length or vtable.
+///
+/// ```
+/// use nolife::{family, scope, BoxScope};
+///
+/// family!(StrFamily<'a> = str);
+///
+/// let mut scope = BoxScope::<StrFamily>::new_dyn(scope!({
+///     let mut line = String::from("frozen");
+///     freeze_forever!(line.as_mut_str())
+/// }));
+///
+/// scope.enter(|line| line.make_ascii_uppercase());
+/// assert_eq!(scope.enter(|line| line.to_owned()), "FROZEN");
+/// ```
 pub trait Family<'a> {
     /// An instance with lifetime `'a` of the borrowed data.
-    type Family: 'a;
+    type Family: ?Sized + 'a;
+}
+
+/// Describes a family whose scope is resumed with a value of type [`ResumeFamily::Resume`].
+///
+/// The scope freezes with [`TimeCapsule::freeze_yield`], and is entered with [`BoxScope::enter_yield`].
+///
+/// # Example
+///
+/// ```
+/// use nolife::{family, scope, BoxScope, ResumeFamily};
+///
+/// family!(CounterFamily<'a> = u32);
+///
+/// enum Command {
+///     Increment,
+///     Reset,
+/// }
+///
+/// impl ResumeFamily for CounterFamily {
+///     type Resume = Command;
+/// }
+///
+/// let mut scope = BoxScope::<CounterFamily>::new_dyn(scope!({
+///     let mut counter = 0;
+///     loop {
+///         match freeze_yield!(&mut counter) {
+///             Command::Increment => counter += 1,
+///             Command::Reset => counter = 0,
+///         }
+///     }
+/// }));
+///
+/// scope.enter_yield(|_| Command::Increment);
+/// scope.enter_yield(|counter| {
+///     assert_eq!(*counter, 1);
+///     Command::Increment
+/// });
+/// scope.enter_yield(|counter| {
+///     assert_eq!(*counter, 2);
+///     Command::Reset
+/// });
+/// scope.enter_yield(|counter| {
+///     assert_eq!(*counter, 0);
+///     Command::Increment
+/// });
+/// ```
+pub trait ResumeFamily: for<'a> Family<'a> {
+    /// The type of the values passed back to the scope when it is entered.
+    type Resume;
+}
+
+/// Describes a family whose scope reports a tag of type [`TagFamily::Tag`] alongside its frozen data.
+///
+/// The scope freezes with [`TimeCapsule::freeze_tagged`], and is entered with [`BoxScope::enter_tagged`].
+///
+/// # Example
+///
+/// ```
+/// use nolife::{family, scope, BoxScope, TagFamily};
+///
+/// family!(LinesFamily<'a> = String);
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Phase {
+///     Header,
+///     Body,
+/// }
+///
+/// impl TagFamily for LinesFamily {
+///     type Tag = Phase;
+/// }
+///
+/// let mut scope = BoxScope::<LinesFamily>::new_dyn(scope!({
+///     let mut line = String::from("Title");
+///     freeze_tagged!(&mut line, Phase::Header);
+///     loop {
+///         line = String::from("Some text");
+///         freeze_tagged!(&mut line, Phase::Body);
+///     }
+/// }));
+///
+/// assert_eq!(scope.enter_tagged(|line, phase| (line.clone(), *phase == Phase::Header)), ("Title".into(), true));
+/// assert_eq!(scope.enter_tagged(|_, phase| format!("{phase:?}")), "Body");
+/// ```
+pub trait TagFamily: for<'a> Family<'a> {
+    /// The type of the tags reported by the scope with its frozen data.
+    type Tag;
+}
+
+/// Declares helper types implementing [`Family`].
+///
+/// `family!(MyFamily<'a> = MyType<'a>);` declares the `MyFamily` type, and implements [`Family<'a>`] for it with
+/// `MyType<'a>` as the [`Family::Family`] associated type.
+///
+/// Type parameters can be added after the lifetime, optionally bounded by traits or lifetimes.
+/// Type parameters need to be `'static` for the resulting type to implement `for<'a> Family<'a>`.
+/// Several families can be declared in a single invocation by separating them with a `;`.
+/// `MyType<'a>` can be any type, including structs and enums with a lifetime, see also
+/// `#[derive(Family)]` with the `derive` feature.
+///
+/// # Example
+///
+/// ```
+/// use nolife::{family, scope, BoxScope};
+///
+/// family! {
+///     /// Mutably borrows some bytes.
+///     pub BytesFamily<'a> = &'a mut [u8];
+///     /// Borrows a slice of any `'static` type.
+///     pub SliceFamily<'a, T: 'static> = &'a [T];
+/// }
+///
+/// let mut scope = BoxScope::<SliceFamily<u32>>::new_dyn(scope!({
+///     let data = vec![0, 1, 2];
+///     freeze_forever!(&mut data.as_slice())
+/// }));
+///
+/// scope.enter(|data| assert_eq!(data.len(), 3));
+/// ```
+#[macro_export]
+macro_rules! family {
+    ($(
+        $(#[$meta:meta])*
+        $vis:vis $name:ident<$lt:lifetime $(, $param:ident $(: $bound:tt $(+ $bounds:tt)*)?)* $(,)?> = $ty:ty
+    );+ $(;)?) => {$(
+        $(#[$meta])*
+        $vis struct $name<$($param $(: $bound $(+ $bounds)*)?),*>(::core::marker::PhantomData<fn() -> ($($param,)*)>);
+
+        impl<$lt, $($param $(: $bound $(+ $bounds)*)?),*> $crate::Family<$lt> for $name<$($param),*>
+        where
+            $ty: $lt,
+        {
+            type Family = $ty;
+        }
+    )+};
 }
 
 /// Helper type for static types.
@@ -57,11 +343,284 @@ pub trait Family<'a> {
 ///
 /// The usefulness of using `'static` types in the scopes of this crate is dubious, but should you want to do this,
 /// for any `T : 'static` you can use this family.
+///
+/// # Example
+///
+/// ```
+/// use nolife::{scope, BoxScope, SingleFamily};
+///
+/// let mut scope = BoxScope::<SingleFamily<String>>::new_dyn(scope!({
+///     let mut greeting = String::from("Hello");
+///     loop {
+///         freeze!(&mut greeting);
+///         greeting.push('!');
+///     }
+/// }));
+///
+/// scope.enter(|greeting| assert_eq!(greeting, "Hello"));
+/// scope.enter(|greeting| assert_eq!(greeting, "Hello!"));
+/// ```
 pub struct SingleFamily<T: 'static>(PhantomData<T>);
+/// The family of a `'static` type is the type itself, regardless of the lifetime `'a`.
 impl<'a, T: 'static> Family<'a> for SingleFamily<T> {
     type Family = T;
 }
 
+/// Helper type for scopes that only yield control, without exposing any data.
+///
+/// A scope of this family pauses with `freeze_unit!()`, see [`TimeCapsule::freeze_unit`],
+/// and each call to [`BoxScope::enter`] resumes it until its next pause. This is useful for step-through execution
+/// and cooperative scheduling, where the frozen value is irrelevant.
+///
+/// # Example
+///
+/// ```
+/// use std::{cell::Cell, rc::Rc};
+/// use nolife::{scope, BoxScope, UnitFamily};
+///
+/// let steps = Rc::new(Cell::new(0));
+/// let counter = steps.clone();
+/// let mut scope = BoxScope::<UnitFamily>::new_dyn(scope!({
+///     loop {
+///         counter.set(counter.get() + 1);
+///         freeze_unit!();
+///     }
+/// }));
+///
+/// scope.enter(|()| ());
+/// scope.enter(|()| ());
+/// assert_eq!(steps.get(), 2);
+/// ```
+pub struct UnitFamily;
+/// The family of a scope that only yields control is the unit type, regardless of the lifetime `'a`.
+impl<'a> Family<'a> for UnitFamily {
+    type Family = ();
+}
+
+impl TimeCapsule<UnitFamily> {
+    /// Freeze a scope without exposing any data, until it is entered again.
+    ///
+    /// This is the same as freezing `&mut ()` with [`Self::freeze`].
+    pub async fn freeze_unit(&mut self) {
+        self.freeze(&mut ()).await
+    }
+}
+
+/// Helper type for shared references to static types.
+///
+/// For any `T: 'static`, possibly unsized, this family borrows a `T` with the lifetime `'a`.
+///
+/// # Example
+///
+/// ```
+/// use nolife::{scope, BoxScope, RefFamily};
+///
+/// let mut scope = BoxScope::<RefFamily<str>>::new_dyn(scope!({
+///     let greeting = String::from("Hello");
+///     freeze_forever!(&mut greeting.as_str())
+/// }));
+///
+/// scope.enter(|greeting| assert_eq!(*greeting, "Hello"));
+/// ```
+pub struct RefFamily<T: ?Sized + 'static>(PhantomData<fn() -> *const T>);
+/// The family of a shared reference is the shared reference with the lifetime `'a`.
+impl<'a, T: ?Sized + 'static> Family<'a> for RefFamily<T> {
+    type Family = &'a T;
+}
+
+/// Helper type for mutable references to static types.
+///
+/// For any `T: 'static`, possibly unsized, this family mutably borrows a `T` with the lifetime `'a`.
+///
+/// # Example
+///
+/// ```
+/// use nolife::{scope, BoxScope, MutFamily};
+///
+/// struct Config {
+///     verbose: bool,
+/// }
+///
+/// let mut scope = BoxScope::<MutFamily<Config>>::new_dyn(scope!({
+///     let mut config = Config { verbose: false };
+///     loop {
+///         freeze!(&mut &mut config);
+///         assert!(config.verbose);
+///     }
+/// }));
+///
+/// scope.enter(|config| config.verbose = true);
+/// scope.enter(|config| assert!(config.verbose));
+/// ```
+pub struct MutFamily<T: ?Sized + 'static>(PhantomData<fn() -> *const T>);
+/// The family of a mutable reference is the mutable reference with the lifetime `'a`.
+impl<'a, T: ?Sized + 'static> Family<'a> for MutFamily<T> {
+    type Family = &'a mut T;
+}
+
+/// Helper type for pinned mutable references to static types.
+///
+/// For any `T: 'static`, possibly unsized, this family mutably borrows a pinned `T` with the lifetime `'a`.
+///
+/// The frozen data is the `Pin` itself, so the pinning guarantees of `T` are preserved: `enter` only gets
+/// a `&mut Pin<&'a mut T>`, from which a `&mut T` can only be obtained if `T` is [`Unpin`].
+///
+/// # Example
+///
+/// ```
+/// use core::{future::Future, pin::{pin, Pin}, task::{Context, Poll}};
+/// use nolife::{noop_waker, scope, BoxScope, PinMutFamily};
+///
+/// let mut scope = BoxScope::<PinMutFamily<dyn Future<Output = u32>>>::new_dyn(scope!({
+///     let mut fut: Pin<&mut dyn Future<Output = u32>> = pin!(async { 42 });
+///     freeze_forever!(&mut fut)
+/// }));
+///
+/// let mut cx = Context::from_waker(noop_waker());
+/// assert_eq!(scope.enter(|fut| fut.as_mut().poll(&mut cx)), Poll::Ready(42));
+/// ```
+pub struct PinMutFamily<T: ?Sized + 'static>(PhantomData<fn() -> *const T>);
+/// The family of a pinned mutable reference is the pinned mutable reference with the lifetime `'a`.
+impl<'a, T: ?Sized + 'static> Family<'a> for PinMutFamily<T> {
+    type Family = core::pin::Pin<&'a mut T>;
+}
+
+/// Helper type for `'static` data of another family, lent with the lifetime of the scope.
+///
+/// For any family `F`, this family mutably borrows the instance of `F` with the lifetime `'static`, for the lifetime
+/// `'a`. The data itself lives for `'static`, such as a leaked configuration or a singleton,
+/// and is frozen with [`TimeCapsule::freeze_static`], which lends it to [`BoxScope::enter`] and gives the
+/// `'static` reference back to the scope when it resumes.
+///
+/// The passed function of `enter` only sees a reborrow of the reference, that cannot outlive the borrow of the scope.
+///
+/// # Example
+///
+/// ```
+/// use nolife::{scope, BoxScope, SingleFamily, StaticFamily};
+///
+/// let config: &'static mut Vec<&'static str> = Box::leak(Box::new(vec!["quiet"]));
+///
+/// let mut scope = BoxScope::<StaticFamily<SingleFamily<Vec<&'static str>>>>::new_dyn(scope!({
+///     let mut config = config;
+///     loop {
+///         config = freeze_static!(config);
+///         config.push("resumed");
+///     }
+/// }));
+///
+/// scope.enter(|config| config.push("verbose"));
+/// assert_eq!(scope.enter(|config| config.clone()), ["quiet", "verbose", "resumed"]);
+/// ```
+pub struct StaticFamily<F>(PhantomData<fn() -> *const F>);
+/// The family of `'static` data is the mutable reference with the lifetime `'a` to the instance of `F` with the
+/// lifetime `'static`.
+impl<'a, F: Family<'static>> Family<'a> for StaticFamily<F> {
+    type Family = &'a mut <F as Family<'static>>::Family;
+}
+
+/// Helper type for optional values of another family.
+///
+/// For any family `F`, this family maps the lifetime `'a` to an `Option` of the instance of `F` with the same lifetime,
+/// for data that is only present at some of the freezes of the scope.
+///
+/// # Example
+///
+/// ```
+/// use nolife::{scope, BoxScope, MutFamily, OptionFamily};
+///
+/// let mut scope = BoxScope::<OptionFamily<MutFamily<u32>>>::new_dyn(scope!({
+///     let mut count = 0;
+///     loop {
+///         freeze!(&mut Some(&mut count));
+///         freeze!(&mut None);
+///     }
+/// }));
+///
+/// scope.enter(|count| **count.as_mut().unwrap() += 1);
+/// assert!(scope.enter(|count| count.is_none()));
+/// assert_eq!(scope.enter(|count| count.as_deref().copied()), Some(1));
+/// ```
+pub struct OptionFamily<F>(PhantomData<fn() -> *const F>);
+/// The family of an optional value is the `Option` of the instance of `F` with the lifetime `'a`.
+impl<'a, F: Family<'a>> Family<'a> for OptionFamily<F>
+where
+    <F as Family<'a>>::Family: Sized,
+{
+    type Family = Option<<F as Family<'a>>::Family>;
+}
+
+/// One of two values, the instance of [`EitherFamily`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Either<L, R> {
+    /// The value of the left family.
+    Left(L),
+    /// The value of the right family.
+    Right(R),
+}
+
+/// A family adapter for scopes that freeze values of one of two families, depending on their phase.
+///
+/// Freeze values with [`TimeCapsule::freeze_left`] and [`TimeCapsule::freeze_right`],
+/// or the `freeze_left!` and `freeze_right!` macros, and match on the [`Either`] when entering the scope.
+///
+/// # Example
+///
+/// ```
+/// use nolife::{scope, BoxScope, Either, EitherFamily, MutFamily, RefFamily};
+///
+/// let mut scope = BoxScope::<EitherFamily<MutFamily<Vec<u32>>, RefFamily<str>>>::new_dyn(scope!({
+///     let mut numbers = Vec::new();
+///     // collecting phase
+///     for _ in 0..2 {
+///         freeze_left!(&mut numbers);
+///     }
+///     // reporting phase
+///     let report = format!("{numbers:?}");
+///     freeze_forever!(&mut Either::Right(report.as_str()))
+/// }));
+///
+/// for n in [1, 2] {
+///     scope.enter(|phase| match phase {
+///         Either::Left(numbers) => numbers.push(n),
+///         Either::Right(_) => unreachable!(),
+///     });
+/// }
+/// assert_eq!(scope.enter(|phase| *phase == Either::Right("[1, 2]")), true);
+/// ```
+pub struct EitherFamily<L, R>(PhantomData<fn() -> (*const L, *const R)>);
+/// The family of one of two values is the [`Either`] of the instances of `L` and `R` with the lifetime `'a`.
+impl<'a, L: Family<'a>, R: Family<'a>> Family<'a> for EitherFamily<L, R>
+where
+    <L as Family<'a>>::Family: Sized,
+    <R as Family<'a>>::Family: Sized,
+{
+    type Family = Either<<L as Family<'a>>::Family, <R as Family<'a>>::Family>;
+}
+
+macro_rules! tuple_family {
+    ($($name:ident)+) => {
+        /// The family of a tuple of families is the tuple of their instances with the same lifetime `'a`.
+        ///
+        /// This allows freezing several values at once.
+        impl<'a, $($name: Family<'a>),+> Family<'a> for ($($name,)+)
+        where
+            $(<$name as Family<'a>>::Family: Sized,)+
+        {
+            type Family = ($(<$name as Family<'a>>::Family,)+);
+        }
+    };
+}
+
+tuple_family!(A);
+tuple_family!(A B);
+tuple_family!(A B C);
+tuple_family!(A B C D);
+tuple_family!(A B C D E);
+tuple_family!(A B C D E F);
+tuple_family!(A B C D E F G);
+tuple_family!(A B C D E F G H);
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -97,15 +656,22 @@ mod test {
         assert_eq!(scope.enter(|x| *x + 42), 145);
     }
 
+    fn counter() -> impl TopScope<Family = SingleFamily<u32>> {
+        scope!({
+            let mut x = 0u32;
+            loop {
+                freeze!(&mut x);
+                x += 1;
+            }
+        })
+    }
+
     #[cfg(feature = "std")]
     fn must_panic<F, R>(f: F)
     where
         F: FnOnce() -> R,
     {
-        assert!(matches!(
-            std::panic::catch_unwind(core::panic::AssertUnwindSafe(f)),
-            Err(_)
-        ));
+        assert!(std::panic::catch_unwind(core::panic::AssertUnwindSafe(f)).is_err());
     }
 
     #[test]
@@ -115,7 +681,7 @@ mod test {
             BoxScope::<SingleFamily<u32>, _>::new(unsafe {
                 crate::scope::new_scope(|_time_capsule| {
                     panic!("panicking producer");
-                    #[allow(unreachable_code)]
+                    #[allow(unreachable_code, clippy::empty_loop)]
                     async {
                         loop {}
                     }
@@ -168,43 +734,3075 @@ mod test {
     }
 
     #[test]
-    fn ref_scope() {
-        use alloc::string::ToString;
+    fn family_macro() {
+        use alloc::{string::String, vec::Vec};
 
-        fn scope_with_ref<'scope, 'a: 'scope>(
-            s: &'a str,
-        ) -> impl TopScope<Family = SingleFamily<usize>> + 'scope {
-            scope!({ freeze_forever!(&mut s.len()) })
+        family!(
+            StrFamily<'a> = &'a str;
+            PairFamily<'a, T: 'static, U: Clone + 'static> = (&'a mut T, &'a [U]);
+        );
+
+        let mut scope = BoxScope::<StrFamily>::new_dyn(scope!({
+            let s = String::from("Intel the Beagle");
+            freeze_forever!(&mut s.as_str())
+        }));
+        scope.enter(|s| assert_eq!(*s, "Intel the Beagle"));
+
+        let mut scope = BoxScope::<PairFamily<u32, u8>>::new_dyn(scope!({
+            let mut x = 0u32;
+            let v: Vec<u8> = (0..4).collect();
+            loop {
+                freeze!(&mut (&mut x, v.as_slice()));
+                x += 1;
+            }
+        }));
+        scope.enter(|(x, v)| assert_eq!((**x, v.len()), (0, 4)));
+        scope.enter(|(x, _)| assert_eq!(**x, 1));
+    }
+
+    #[test]
+    #[cfg(feature = "families")]
+    fn families() {
+        use crate::families::{ArrayFamily, SliceFamily, SliceMutFamily, VecFamily};
+        use alloc::vec::Vec;
+
+        let mut scope = BoxScope::<(VecFamily<u32>, SliceFamily<u32>)>::new_dyn(scope!({
+            let mut v = Vec::new();
+            let input = [1, 2, 3];
+            loop {
+                freeze!(&mut (&mut v, &input[..]));
+            }
+        }));
+        scope.enter(|(v, input)| v.extend_from_slice(input));
+        assert_eq!(scope.enter(|(v, _)| v.clone()), [1, 2, 3]);
+
+        let mut scope = BoxScope::<(ArrayFamily<u32, 3>, SliceMutFamily<u32>)>::new_dyn(scope!({
+            let mut array = [0; 3];
+            let mut v = alloc::vec![1, 2];
+            loop {
+                freeze!(&mut (&mut array, &mut v[..]));
+            }
+        }));
+        scope.enter(|(array, slice)| array[..2].swap_with_slice(slice));
+        assert_eq!(
+            scope.enter(|(array, slice)| (**array, slice.to_vec())),
+            ([1, 2, 0], alloc::vec![0, 0])
+        );
+
+        #[cfg(feature = "std")]
+        {
+            use crate::families::HashMapFamily;
+            use std::collections::HashMap;
+
+            let mut scope = BoxScope::<HashMapFamily<&'static str, u32>>::new_dyn(scope!({
+                let mut map = HashMap::new();
+                loop {
+                    freeze!(&mut &mut map);
+                    *map.entry("entered").or_default() += 1;
+                }
+            }));
+            scope.enter(|_| ());
+            assert_eq!(scope.enter(|map| map.get("entered").copied()), Some(1));
         }
-        let x = "Intel the Beagle".to_string();
-        let mut scope = BoxScope::<SingleFamily<usize>, _>::new(scope_with_ref(&x));
+    }
 
-        scope.enter(|x| assert_eq!(*x, 16));
+    #[test]
+    fn ref_families() {
+        use alloc::vec::Vec;
+
+        let mut scope = BoxScope::<(RefFamily<[u8]>, MutFamily<Vec<u8>>)>::new_dyn(scope!({
+            let input = [1, 2, 3];
+            let mut output = Vec::new();
+            loop {
+                freeze!(&mut (&input[..], &mut output));
+            }
+        }));
+        scope.enter(|(input, output)| output.extend(input.iter().rev()));
+        assert_eq!(scope.enter(|(_, output)| output.clone()), [3, 2, 1]);
     }
 
     #[test]
-    fn awaiting_in_scope_ready() {
-        let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
-            freeze!(&mut 40);
-            core::future::ready(()).await;
-            freeze_forever!(&mut 42)
+    #[cfg(feature = "std")]
+    fn rc_scope() {
+        let scope = RcScope::<SingleFamily<u32>>::new_dyn(counter());
+        let handle = scope.clone();
+        assert!(scope.ptr_eq(&handle));
+
+        assert_eq!(scope.enter(|x| *x), 0);
+        assert_eq!(handle.enter(|x| *x), 1);
+        assert_eq!(scope.try_enter(|x| *x), Ok(2));
+
+        // entering through another handle while entered panics
+        must_panic(|| scope.enter(|_| handle.enter(|x| *x)));
+        assert!(!handle.is_poisoned());
+        assert_eq!(handle.enter(|x| *x), 4);
+
+        // querying through another handle while entered panics as well, with its own message
+        assert!(panic_message(|| scope.enter(|_| handle.is_poisoned()))
+            .starts_with("nolife: the `RcScope` was queried while it was entered"));
+        assert!(!handle.is_poisoned());
+        assert_eq!(handle.enter(|x| *x), 6);
+    }
+
+    #[test]
+    fn stack_scope_output() {
+        let output = stack_scope::<SingleFamily<u32>, _, _>(counter(), |mut scope| {
+            assert!(!scope.is_active());
+            assert_eq!(scope.enter(|x| *x + 42), 42);
+            assert_eq!(scope.try_enter(|x| *x + 42), Ok(43));
+            scope.enter(|x| *x += 100);
+            assert!(scope.is_active());
+            scope.enter(|x| *x + 42)
+        });
+        assert_eq!(output, 145);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn stack_scope_drops_future() {
+        use std::rc::Rc;
+
+        let witness = Rc::new(());
+        let inner = witness.clone();
+        must_panic(|| {
+            stack_scope::<SingleFamily<u32>, _, _>(
+                scope!({
+                    let _inner = inner;
+                    freeze_forever!(&mut 0)
+                }),
+                |mut scope| {
+                    scope.enter(|_| ());
+                    panic!("panicking body")
+                },
+            )
+        });
+        assert_eq!(Rc::strong_count(&witness), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn send_scope() {
+        let mut scope = BoxScope::<SingleFamily<u32>, _>::new(scope!({
+            let mut x = 0u32;
+            loop {
+                freeze!(&mut x);
+                x += 1;
+            }
         }));
+        scope.enter(|x| assert_eq!(*x, 0));
 
-        scope.enter(|x| assert_eq!(*x, 40));
+        let mut scope = std::thread::spawn(move || {
+            scope.enter(|x| assert_eq!(*x, 1));
+            scope
+        })
+        .join()
+        .unwrap();
+
+        scope.enter(|x| assert_eq!(*x, 2));
+
+        let mut scope =
+            BoxScope::<SingleFamily<u32>, _>::new_dyn_send(scope!({ freeze_forever!(&mut 42) }));
+        let mut scope = std::thread::spawn(move || {
+            scope.enter(|x| assert_eq!(*x, 42));
+            scope
+        })
+        .join()
+        .unwrap();
         scope.enter(|x| assert_eq!(*x, 42));
     }
 
     #[test]
     #[cfg(feature = "std")]
-    fn awaiting_in_scope_panics() {
-        let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
-            freeze!(&mut 40);
-            let () = core::future::pending().await;
-            freeze_forever!(&mut 42)
+    fn sync_scope() {
+        use std::sync::Arc;
+
+        let scope = Arc::new(SyncScope::<SingleFamily<u32>>::new_dyn(scope!({
+            let mut x = 0u32;
+            loop {
+                freeze!(&mut x);
+                x += 1;
+            }
+        })));
+
+        let threads: std::vec::Vec<_> = (0..4)
+            .map(|_| {
+                let scope = scope.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..10 {
+                        scope.enter(|_| ());
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        assert_eq!(scope.enter(|x| *x), 40);
+        must_panic(|| scope.enter(|_| panic!()));
+        assert_eq!(scope.try_enter(|x| *x), Ok(42));
+        assert!(!scope.is_poisoned());
+    }
+
+    #[test]
+    fn enter_ref() {
+        use alloc::string::String;
+
+        family!(StrFamily<'a> = &'a str);
+
+        let mut scope = BoxScope::<StrFamily>::new_dyn(scope!({
+            let mut s = String::from("Intel");
+            loop {
+                freeze!(&mut s.as_str());
+                s.push_str(" the Beagle");
+            }
         }));
 
-        scope.enter(|x| assert_eq!(*x, 40));
+        assert_eq!(scope.enter_ref(|s| s.len()), 5);
+        assert_eq!(scope.enter_ref(|s| s.len()), 16);
+    }
 
-        must_panic(|| scope.enter(|x| assert_eq!(*x, 42)));
+    #[test]
+    fn tuple_family() {
+        use alloc::vec::Vec;
+
+        family!(SliceFamily<'a> = &'a [u8]);
+        family!(VecFamily<'a> = &'a mut Vec<u8>);
+
+        let mut scope =
+            BoxScope::<(VecFamily, SliceFamily, SingleFamily<usize>)>::new_dyn(scope!({
+                let mut output = Vec::new();
+                let input = [1, 2, 3, 4, 5];
+                for (i, window) in input.windows(2).enumerate() {
+                    freeze!(&mut (&mut output, window, i));
+                }
+                freeze_forever!(&mut (&mut output, &[][..], input.len()))
+            }));
+
+        for expected in 0..4 {
+            scope.enter(|(output, window, i)| {
+                assert_eq!(*i, expected);
+                output.extend_from_slice(window);
+            });
+        }
+        assert_eq!(
+            scope.enter(|(output, _, i)| (output.clone(), *i)),
+            ([1, 2, 2, 3, 3, 4, 4, 5].to_vec(), 5)
+        );
+    }
+
+    #[test]
+    fn new_in() {
+        use crate::allocator::{AllocError, Allocator, Global};
+        use core::{alloc::Layout, cell::Cell, ptr::NonNull};
+
+        #[derive(Default)]
+        struct CountingAllocator {
+            allocated: Cell<usize>,
+            deallocated: Cell<usize>,
+        }
+
+        unsafe impl Allocator for CountingAllocator {
+            fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+                self.allocated.set(self.allocated.get() + 1);
+                Global.allocate(layout)
+            }
+
+            unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+                self.deallocated.set(self.deallocated.get() + 1);
+                unsafe { Global.deallocate(ptr, layout) }
+            }
+        }
+
+        let alloc = CountingAllocator::default();
+        {
+            let mut scope = BoxScope::<SingleFamily<u32>, _, _>::new_dyn_in(counter(), &alloc);
+            assert_eq!(scope.enter(|x| *x), 0);
+            assert_eq!(scope.enter(|x| *x), 1);
+            assert_eq!(alloc.allocated.get(), 1);
+            assert_eq!(alloc.deallocated.get(), 0);
+        }
+        assert_eq!(alloc.deallocated.get(), 1);
+
+        #[cfg(feature = "std")]
+        {
+            must_panic(|| {
+                let mut scope = BoxScope::<SingleFamily<u32>, _, _>::new_in(
+                    scope!({
+                        freeze!(&mut 0);
+                        panic!()
+                    }),
+                    &alloc,
+                );
+                scope.enter(|x| *x);
+                scope.enter(|x| *x)
+            });
+            assert_eq!(alloc.allocated.get(), 2);
+            assert_eq!(alloc.deallocated.get(), 2);
+        }
+    }
+
+    #[test]
+    fn freeze_ref() {
+        use alloc::vec::Vec;
+
+        let mut scope = BoxScope::<SingleFamily<Vec<u32>>>::new_dyn(scope!({
+            let mut v = Vec::new();
+            loop {
+                freeze_ref!(&v);
+                v.push(v.len() as u32);
+                freeze!(&mut v);
+            }
+        }));
+
+        assert_eq!(scope.enter_ref(|v| v.len()), 0);
+        scope.enter(|v| v.push(42));
+        // the rejected freeze is kept for the next call
+        assert_eq!(scope.try_enter(|v| v.len()), Err(ScopeError::FrozenShared));
+        assert_eq!(scope.try_enter(|v| v.len()), Err(ScopeError::FrozenShared));
+        assert_eq!(scope.enter_ref(|v| v.clone()), [0, 42]);
+        assert_eq!(scope.enter(|v| v.clone()), [0, 42, 2]);
+
+        // finishing the scope goes through shared freezes as well
+        let scope = BoxScope::<SingleFamily<u32>, _>::new_with_output(scope!({
+            let mut x = 1;
+            freeze_ref!(&x);
+            freeze!(&mut x);
+            x
+        }));
+        assert_eq!(scope.finish(), Some(1));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn scope_with_output() {
+        let mut scope = BoxScope::<SingleFamily<u32>, _>::new_dyn_with_output(scope!({
+            let mut x = 0u32;
+            freeze!(&mut x);
+            x += 1;
+            freeze!(&mut x);
+            alloc::string::String::from("done")
+        }));
+
+        assert_eq!(scope.enter_or_complete(|x| *x), Ok(0));
+        assert_eq!(scope.try_enter(|x| *x), Ok(1));
+        assert!(scope.is_active());
+        assert_eq!(
+            scope.enter_or_complete(|x| *x),
+            Err(Completed(alloc::string::String::from("done")))
+        );
+        assert!(!scope.is_active());
+        assert!(!scope.is_poisoned());
+        assert_eq!(scope.try_enter(|x| *x), Err(ScopeError::Completed));
+        must_panic(|| scope.enter(|x| *x));
+        must_panic(|| scope.enter_or_complete(|x| *x));
+    }
+
+    #[test]
+    fn scope_with_output_dropped_by_try_enter() {
+        let mut scope = BoxScope::<SingleFamily<u32>, _>::new_with_output(scope!({ 42u32 }));
+
+        assert_eq!(scope.try_enter(|x| *x), Err(ScopeError::Completed));
+        assert_eq!(scope.try_enter(|x| *x), Err(ScopeError::Completed));
+    }
+
+    #[test]
+    #[cfg(feature = "futures")]
+    fn into_stream() {
+        use core::{
+            pin::Pin,
+            task::{Context, Poll},
+        };
+        use futures_core::{FusedStream, Stream};
+
+        let scope = BoxScope::<SingleFamily<u32>, _>::new_with_output(scope!({
+            for mut x in 0..3 {
+                freeze!(&mut x);
+            }
+        }));
+        let mut stream = scope.into_stream(|x| *x * 2);
+
+        let waker = waker::create();
+        let mut cx = Context::from_waker(&waker);
+        let mut items = alloc::vec::Vec::new();
+        while let Poll::Ready(Some(item)) = Pin::new(&mut stream).poll_next(&mut cx) {
+            items.push(item);
+        }
+        assert_eq!(items, [0, 2, 4]);
+        assert!(stream.is_terminated());
+        assert_eq!(Pin::new(&mut stream).poll_next(&mut cx), Poll::Ready(None));
+    }
+
+    #[test]
+    fn enter_async() {
+        use core::{
+            future::Future,
+            pin::Pin,
+            task::{Context, Poll},
+        };
+
+        struct YieldNow(bool);
+        impl Future for YieldNow {
+            type Output = ();
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+                if self.0 {
+                    return Poll::Ready(());
+                }
+                self.0 = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+
+        let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+            let mut x = 0u32;
+            loop {
+                freeze!(&mut x);
+                YieldNow(false).await;
+                x += 1;
+            }
+        }));
+
+        let waker = waker::create();
+        let mut cx = Context::from_waker(&waker);
+
+        let mut fut = scope.enter_async(|x| core::future::ready(*x));
+        assert_eq!(Pin::new(&mut fut).poll(&mut cx), Poll::Ready(0));
+
+        let mut fut = scope.enter_async(|x| core::future::ready(*x));
+        assert_eq!(Pin::new(&mut fut).poll(&mut cx), Poll::Pending);
+        assert_eq!(Pin::new(&mut fut).poll(&mut cx), Poll::Ready(1));
+
+        // the future returned by the passed function can await while holding the frozen data
+        let mut fut = core::pin::pin!(scope.enter_async(|x| async move {
+            YieldNow(false).await;
+            *x += 10;
+            *x
+        }));
+        assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending);
+        assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending);
+        assert_eq!(fut.as_mut().poll(&mut cx), Poll::Ready(12));
+
+        // entering synchronously still reports the real pending as an error
+        assert_eq!(scope.try_enter(|x| *x), Err(ScopeError::NotFrozen));
+        assert_eq!(scope.try_enter(|x| *x), Ok(13));
+    }
+
+    #[test]
+    fn enter_async_borrowing_frozen_reference() {
+        use alloc::vec::Vec;
+        use core::{
+            future::Future,
+            task::{Context, Poll},
+        };
+
+        async fn push(values: &mut Vec<u32>, value: u32) {
+            values.push(value);
+        }
+
+        let mut scope = BoxScope::<MutFamily<Vec<u32>>>::new_dyn(scope!({
+            let mut values = Vec::new();
+            loop {
+                freeze!(&mut &mut values);
+            }
+        }));
+
+        let waker = waker::create();
+        let mut cx = Context::from_waker(&waker);
+
+        // the future returned by an async closure holds the frozen reference across its awaits
+        for expected in [2, 4] {
+            let mut fut = core::pin::pin!(scope.enter_async(async |values: &mut &mut Vec<u32>| {
+                push(values, 1).await;
+                push(values, 2).await;
+                values.len()
+            }));
+            assert_eq!(fut.as_mut().poll(&mut cx), Poll::Ready(expected));
+        }
+
+        assert_eq!(scope.enter(|values| values.clone()), [1, 2, 1, 2]);
+    }
+
+    #[test]
+    fn poll_enter() {
+        use core::{
+            future::Future,
+            pin::Pin,
+            task::{Context, Poll},
+        };
+
+        struct YieldNow(bool);
+        impl Future for YieldNow {
+            type Output = ();
+            fn poll(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+                if self.0 {
+                    return Poll::Ready(());
+                }
+                self.0 = true;
+                Poll::Pending
+            }
+        }
+
+        let mut scope = BoxScope::<SingleFamily<u32>, _>::new_with_output(scope!({
+            let mut x = 0u32;
+            freeze!(&mut x);
+            YieldNow(false).await;
+            freeze_ref!(&x);
+            freeze!(&mut x);
+            x
+        }));
+
+        let waker = waker::create();
+        let mut cx = Context::from_waker(&waker);
+
+        assert_eq!(
+            scope.poll_enter(&mut cx, |x| *x += 1),
+            Poll::Ready(Ok(Ok(())))
+        );
+        assert_eq!(scope.poll_enter(&mut cx, |x| *x), Poll::Pending);
+        assert_eq!(
+            scope.poll_enter(&mut cx, |x| *x),
+            Poll::Ready(Err(ScopeError::FrozenShared))
+        );
+        assert_eq!(scope.enter_ref(|x| *x), 1);
+        assert_eq!(
+            scope.poll_enter(&mut cx, |x| *x += 1),
+            Poll::Ready(Ok(Ok(())))
+        );
+        assert_eq!(
+            scope.poll_enter(&mut cx, |x| *x),
+            Poll::Ready(Ok(Err(Completed(2))))
+        );
+        assert_eq!(
+            scope.poll_enter(&mut cx, |x| *x),
+            Poll::Ready(Err(ScopeError::Completed))
+        );
+    }
+
+    #[test]
+    fn enter_bounded() {
+        use core::{
+            future::Future,
+            pin::Pin,
+            task::{Context, Poll},
+        };
+
+        struct PendingTimes(u32);
+        impl Future for PendingTimes {
+            type Output = ();
+            fn poll(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+                if self.0 == 0 {
+                    return Poll::Ready(());
+                }
+                self.0 -= 1;
+                Poll::Pending
+            }
+        }
+
+        let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+            let mut x = 0u32;
+            loop {
+                freeze!(&mut x);
+                PendingTimes(3).await;
+                x += 1;
+            }
+        }));
+
+        assert_eq!(scope.enter_bounded(0, |x| *x), Err(StepLimitExceeded));
+        assert_eq!(scope.enter_bounded(1, |x| *x), Ok(0));
+        assert_eq!(scope.enter_bounded(2, |x| *x), Err(StepLimitExceeded));
+        // resumes where the previous call stopped
+        assert_eq!(scope.enter_bounded(2, |x| *x), Ok(1));
+        assert_eq!(scope.enter_bounded(4, |x| *x), Ok(2));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn reset() {
+        use std::rc::Rc;
+
+        fn scope(rc: Rc<()>) -> impl Scope<Family = SingleFamily<u32>, Output = u32> {
+            scope!({
+                let _rc = rc;
+                let mut x = 0u32;
+                freeze!(&mut x);
+                if x == 0 {
+                    panic!()
+                }
+                x
+            })
+        }
+
+        let rc = Rc::new(());
+        let mut box_scope = BoxScope::new_with_output(scope(rc.clone()));
+        box_scope.enter(|x| assert_eq!(*x, 0));
+        must_panic(|| box_scope.enter(|x| *x));
+        assert!(box_scope.is_poisoned());
+
+        box_scope.reset(scope(rc.clone()));
+        assert!(!box_scope.is_poisoned());
+        // the previous future was dropped
+        assert_eq!(Rc::strong_count(&rc), 2);
+        box_scope.enter(|x| *x = 42);
+        assert_eq!(box_scope.enter_or_complete(|x| *x), Err(Completed(42)));
+
+        box_scope.reset(scope(rc.clone()));
+        assert_eq!(box_scope.try_enter(|x| *x), Ok(0));
+        drop(box_scope);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    #[test]
+    fn debug() {
+        use alloc::format;
+
+        let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({ freeze_forever!(&mut 0) }));
+        assert_eq!(
+            format!("{scope:?}"),
+            "BoxScope { poisoned: false, active: false, .. }"
+        );
+        scope.enter(|_| {});
+        assert_eq!(
+            format!("{scope:?}"),
+            "BoxScope { poisoned: false, active: true, .. }"
+        );
+
+        stack_scope::<SingleFamily<u32>, _, _>(scope!({ freeze_forever!(&mut 0) }), |scope| {
+            assert_eq!(
+                format!("{scope:?}"),
+                "StackScope { poisoned: false, active: false, .. }"
+            );
+        });
+
+        #[cfg(feature = "std")]
+        {
+            let scope =
+                SyncScope::<SingleFamily<u32>>::new_dyn(scope!({ freeze_forever!(&mut 0) }));
+            let debug = format!("{scope:?}");
+            assert!(debug.starts_with("SyncScope(Mutex {"));
+            assert!(debug.contains("BoxScope { poisoned: false, active: false, .. }"));
+        }
+    }
+
+    #[test]
+    fn freeze_yield() {
+        use alloc::{string::String, vec::Vec};
+
+        family!(LogFamily<'a> = &'a mut Vec<String>);
+        impl ResumeFamily for LogFamily {
+            type Resume = Option<String>;
+        }
+
+        let mut scope = BoxScope::<LogFamily, _>::new_with_output(scope!({
+            let mut log = Vec::new();
+            while let Some(line) = freeze_yield!(&mut &mut log) {
+                log.push(line);
+            }
+            log.len()
+        }));
+
+        scope.enter_yield(|log| {
+            assert!(log.is_empty());
+            Some(String::from("hello"))
+        });
+        scope.enter_yield(|log| {
+            assert_eq!(**log, ["hello"]);
+            Some(String::from("world"))
+        });
+        scope.enter_yield(|_| None);
+        assert_eq!(scope.enter_or_complete(|_| ()), Err(Completed(2)));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn freeze_yield_without_value() {
+        family!(CountFamily<'a> = u32);
+        impl ResumeFamily for CountFamily {
+            type Resume = u32;
+        }
+
+        let mut scope = BoxScope::<CountFamily>::new_dyn(scope!({
+            let mut x = 0u32;
+            loop {
+                x += freeze_yield!(&mut x);
+            }
+        }));
+
+        scope.enter_yield(|_| 1);
+        scope.enter_yield(|x| {
+            assert_eq!(*x, 1);
+            2
+        });
+        // not passing a value back poisons the scope
+        assert_eq!(scope.try_enter(|x| *x), Ok(3));
+        must_panic(|| scope.enter(|x| *x));
+        assert!(scope.is_poisoned());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn reentrant_enter() {
+        use crate::raw_scope::RawScope;
+        use core::ptr::NonNull;
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        // Re-entering a scope is not possible through the safe API, so this test uses `RawScope` directly.
+        fn reenter<S: TopScope<Family = SingleFamily<u32>>>(scope: S) {
+            let mut raw_scope = RawScope::<SingleFamily<u32>, S::Future>::new_uninit();
+            let raw_scope: NonNull<RawScope<SingleFamily<u32>, S::Future>> =
+                NonNull::from(&mut raw_scope).cast();
+            unsafe { RawScope::open(raw_scope.as_ptr(), scope) };
+
+            let err = catch_unwind(AssertUnwindSafe(|| unsafe {
+                RawScope::enter(raw_scope, |_| RawScope::enter(raw_scope, |x| *x))
+            }))
+            .unwrap_err();
+            assert_eq!(
+                err.downcast_ref::<&str>(),
+                Some(
+                    &"nolife: the scope was entered re-entrantly, from its own future \
+                      or from the function passed to `BoxScope::enter`"
+                )
+            );
+
+            // the scope can still be entered once the outer call returned
+            assert_eq!(unsafe { RawScope::try_enter(raw_scope, |x| *x) }, Ok(1));
+            unsafe { RawScope::drop_in_place(raw_scope) };
+        }
+
+        reenter(counter());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn finish_and_terminate() {
+        use std::rc::Rc;
+
+        let scope = BoxScope::<SingleFamily<u32>, _>::new_with_output(scope!({
+            freeze!(&mut 0);
+            freeze_ref!(&1);
+            2
+        }));
+        assert_eq!(scope.finish(), Some(2));
+
+        let mut scope = BoxScope::<SingleFamily<u32>, _>::new_with_output(scope!({
+            freeze!(&mut 0);
+            panic!()
+        }));
+        must_panic(|| {
+            scope
+                .enter_or_complete(|x| *x)
+                .and_then(|_| scope.enter_or_complete(|x| *x))
+        });
+        assert_eq!(scope.finish(), None);
+
+        let rc = Rc::new(());
+        let scope = BoxScope::<SingleFamily<u32>>::new_dyn({
+            let rc = rc.clone();
+            scope!({
+                let _rc = rc;
+                freeze_forever!(&mut 0)
+            })
+        });
+        assert_eq!(Rc::strong_count(&rc), 2);
+        scope.terminate();
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn enter_guard() {
+        let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+            let mut x = 0u32;
+            loop {
+                freeze!(&mut x);
+                x += 10;
+                freeze_ref!(&x);
+                x += 1;
+            }
+        }));
+
+        {
+            let mut guard = scope.enter_guard();
+            *guard += 1;
+            guard.with(|x| *x += 1);
+            assert_eq!(guard.with_ref(|x| *x), 2);
+        }
+        {
+            let mut guard = scope.enter_guard();
+            assert_eq!(*guard, 12);
+            must_panic(|| guard.with(|x| *x += 1));
+        }
+
+        assert_eq!(scope.enter(|x| *x), 13);
+    }
+
+    #[test]
+    fn enter_guard_borrowed_family() {
+        family!(StrFamily<'a> = &'a str);
+
+        let mut scope = BoxScope::<StrFamily>::new_dyn(scope!({
+            let s = alloc::string::String::from("hello");
+            freeze_forever!(&mut s.as_str())
+        }));
+        {
+            let mut guard = scope.enter_guard();
+            assert_eq!(guard.with_ref(|s| s.len()), 5);
+            guard.with(|s| *s = &s[1..]);
+        }
+        assert_eq!(scope.enter(|s| s.len()), 4);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn enter_guard_drop_resumes() {
+        use core::cell::Cell;
+
+        let resumed = Cell::new(0);
+        let resumed = &resumed;
+        let mut scope = BoxScope::<SingleFamily<u32>, _>::new_with_output(scope!({
+            let mut x = 0u32;
+            freeze!(&mut x);
+            resumed.set(1);
+            freeze!(&mut x);
+            resumed.set(2);
+            freeze!(&mut x);
+            resumed.set(3);
+            x
+        }));
+
+        let mut guard = scope.enter_guard();
+        *guard += 1;
+        assert_eq!(resumed.get(), 0);
+        drop(guard);
+        // the future ran up to its next freeze, that is entered without resuming it again
+        assert_eq!(resumed.get(), 1);
+        assert_eq!(scope.enter(|x| *x), 1);
+        assert_eq!(resumed.get(), 1);
+
+        // a guard dropped while unwinding does not resume the future
+        must_panic(|| {
+            let _guard = scope.enter_guard();
+            panic!()
+        });
+        assert_eq!(resumed.get(), 2);
+        assert_eq!(scope.enter_or_complete(|x| *x), Err(Completed(1)));
+        assert_eq!(resumed.get(), 3);
+    }
+
+    #[test]
+    fn family2() {
+        struct Pair<'a, 'b> {
+            x: &'a mut u32,
+            y: &'b [u32],
+        }
+
+        struct PairFamily;
+        impl<'a, 'b> Family2<'a, 'b> for PairFamily {
+            type Family = Pair<'a, 'b>;
+        }
+
+        let mut scope = BoxScope::<TwoLifetimes<PairFamily>>::new_dyn(scope!({
+            let data = [1, 2, 3];
+            let mut x = 0;
+            let mut pair = Pair {
+                x: &mut x,
+                y: &data,
+            };
+            loop {
+                freeze2!(&mut pair);
+                *pair.x += pair.y.iter().sum::<u32>();
+                pair.y = &pair.y[1..];
+            }
+        }));
+
+        assert_eq!(scope.enter(|pair| pair.with_ref(|pair| *pair.x)), 0);
+        assert_eq!(scope.enter(|pair| pair.with(|pair| *pair.x)), 6);
+        assert_eq!(
+            scope.enter(|pair| pair.with(|pair| (*pair.x, pair.y.to_vec()))),
+            (11, alloc::vec![3])
+        );
+    }
+
+    #[test]
+    fn map() {
+        struct Words<'a> {
+            words: alloc::vec::Vec<&'a str>,
+            count: usize,
+        }
+        family!(WordsFamily<'a> = Words<'a>);
+
+        let scope = BoxScope::<WordsFamily>::new_dyn(scope!({
+            let text = alloc::string::String::from("a few words");
+            let mut words = Words {
+                words: text.split(' ').collect(),
+                count: 0,
+            };
+            loop {
+                freeze!(&mut words);
+                words.count = words.words.len();
+            }
+        }));
+
+        let mut scope = scope.map::<SingleFamily<usize>, _>(|words| &mut words.count);
+        assert_eq!(scope.enter(|count| *count), 0);
+        assert_eq!(scope.try_enter(|count| *count), Ok(3));
+        assert!(!scope.get_ref().is_poisoned());
+
+        let mut scope = scope.into_inner();
+        assert_eq!(scope.enter(|words| words.words.join(" ")), "a few words");
+    }
+
+    #[test]
+    fn nested_enter() {
+        family!(StrFamily<'a> = &'a str);
+
+        let mut words = BoxScope::<StrFamily>::new_dyn(scope!({
+            let text = alloc::string::String::from("nested scopes");
+            loop {
+                for word in text.split(' ') {
+                    freeze!(&mut &*word);
+                }
+            }
+        }));
+        let mut lengths = BoxScope::<SingleFamily<alloc::vec::Vec<usize>>>::new_dyn(scope!({
+            freeze_forever!(&mut alloc::vec::Vec::new())
+        }));
+        let total = stack_scope::<SingleFamily<usize>, _, _>(
+            scope!({ freeze_forever!(&mut 0usize) }),
+            |mut stack| {
+                for _ in 0..3 {
+                    words.enter(|word| {
+                        lengths.enter(|lengths| {
+                            stack.enter(|total| {
+                                // the data of the three scopes is usable together
+                                lengths.push(word.len());
+                                *total += word.len();
+                            })
+                        })
+                    });
+                }
+                stack.enter(|total| *total)
+            },
+        );
+        assert_eq!(lengths.enter(|lengths| lengths.clone()), [6, 6, 6]);
+        assert_eq!(total, 18);
+    }
+
+    #[test]
+    fn iter() {
+        let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(counter());
+
+        assert!(scope.iter(|x| *x).take(3).eq([0, 1, 2]));
+        assert_eq!(scope.enter(|x| *x), 3);
+        assert_eq!(scope.iter(|x| *x).nth(2), Some(6));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn iter_terminates() {
+        let mut scope = BoxScope::<SingleFamily<u32>, _>::new_with_output(scope!({
+            freeze!(&mut 0);
+            freeze!(&mut 1);
+        }));
+        let mut iter = scope.iter(|x| *x);
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+
+        let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+            freeze!(&mut 0);
+            panic!()
+        }));
+        must_panic(|| scope.iter(|x| *x).count());
+        assert_eq!(scope.iter(|x| *x).next(), None);
+    }
+
+    #[test]
+    fn ref_scope() {
+        use alloc::string::ToString;
+
+        fn scope_with_ref<'scope, 'a: 'scope>(
+            s: &'a str,
+        ) -> impl TopScope<Family = SingleFamily<usize>> + 'scope {
+            scope!({ freeze_forever!(&mut s.len()) })
+        }
+        let x = "Intel the Beagle".to_string();
+        let mut scope = BoxScope::<SingleFamily<usize>, _>::new(scope_with_ref(&x));
+
+        scope.enter(|x| assert_eq!(*x, 16));
+    }
+
+    #[test]
+    fn awaiting_in_scope_ready() {
+        let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+            freeze!(&mut 40);
+            core::future::ready(()).await;
+            freeze_forever!(&mut 42)
+        }));
+
+        scope.enter(|x| assert_eq!(*x, 40));
+        scope.enter(|x| assert_eq!(*x, 42));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn awaiting_in_scope_panics() {
+        let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+            freeze!(&mut 40);
+            let () = core::future::pending().await;
+            freeze_forever!(&mut 42)
+        }));
+
+        scope.enter(|x| assert_eq!(*x, 40));
+
+        must_panic(|| scope.enter(|x| assert_eq!(*x, 42)));
+    }
+
+    #[test]
+    fn try_enter_frozen() {
+        let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(counter());
+
+        assert_eq!(scope.try_enter(|x| *x + 42), Ok(42));
+        assert_eq!(scope.try_enter(|x| *x + 42), Ok(43));
+    }
+
+    #[test]
+    fn try_enter_not_frozen() {
+        let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+            let () = core::future::pending().await;
+            freeze_forever!(&mut 42)
+        }));
+
+        assert_eq!(scope.try_enter(|x| *x), Err(ScopeError::NotFrozen));
+        assert_eq!(scope.try_enter(|x| *x), Err(ScopeError::NotFrozen));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn try_enter_poisoned() {
+        let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+            let mut x = 0u32;
+            freeze!(&mut x);
+            panic!()
+        }));
+
+        assert_eq!(scope.try_enter(|x| *x), Ok(0));
+        must_panic(|| scope.try_enter(|x| *x));
+        assert_eq!(scope.try_enter(|x| *x), Err(ScopeError::Poisoned));
+        must_panic(|| scope.enter(|x| *x));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn try_enter_panicking_enter_does_not_poison() {
+        let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(counter());
+
+        must_panic(|| scope.try_enter(|_| panic!()));
+        assert_eq!(scope.try_enter(|x| *x), Ok(1));
+    }
+
+    #[test]
+    fn single_poll_per_enter() {
+        use core::{
+            cell::Cell,
+            future::Future,
+            pin::Pin,
+            task::{Context, Poll},
+        };
+
+        struct CountPolls<'c, Fut> {
+            fut: Fut,
+            polls: &'c Cell<u32>,
+        }
+        impl<Fut: Future> Future for CountPolls<'_, Fut> {
+            type Output = Fut::Output;
+            fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                // SAFETY: `fut` is structurally pinned
+                let this = unsafe { self.get_unchecked_mut() };
+                this.polls.set(this.polls.get() + 1);
+                unsafe { Pin::new_unchecked(&mut this.fut) }.poll(cx)
+            }
+        }
+
+        let polls = Cell::new(0);
+        // SAFETY: the `FrozenFuture`s are awaited at the top level of the producer.
+        let scope = unsafe {
+            scope::new_scope(
+                |mut time_capsule: TimeCapsule<SingleFamily<u32>>| CountPolls {
+                    fut: async move {
+                        let mut x = 0u32;
+                        loop {
+                            time_capsule.freeze(&mut x).await;
+                            x += 1;
+                        }
+                    },
+                    polls: &polls,
+                },
+            )
+        };
+        let mut scope = BoxScope::<SingleFamily<u32>, _>::new(scope);
+        for i in 0..10 {
+            assert_eq!(scope.enter(|x| *x), i);
+            assert_eq!(polls.get(), i + 1);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn frozen_future_polled_after_completion() {
+        // SAFETY: the `FrozenFuture` is awaited at the top level of the producer, though several times.
+        let scope = unsafe {
+            scope::new_scope(
+                |mut time_capsule: TimeCapsule<SingleFamily<u32>>| async move {
+                    let mut x = 0u32;
+                    let mut fut = core::pin::pin!(time_capsule.freeze(&mut x));
+                    fut.as_mut().await;
+                    fut.as_mut().await;
+                    loop {
+                        time_capsule.freeze(&mut x).await
+                    }
+                },
+            )
+        };
+        let mut scope = BoxScope::new_dyn(scope);
+        scope.enter(|x| *x = 1);
+
+        let payload =
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| scope.enter(|x| *x)))
+                .unwrap_err();
+        assert_eq!(
+            payload.downcast_ref::<&str>(),
+            Some(
+                &"nolife: a `FrozenFuture` was polled after completion, \
+                  each freeze of a `TimeCapsule` must be awaited exactly once"
+            )
+        );
+        assert!(scope.is_poisoned());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn poisoned_and_active() {
+        let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+            let mut x = 0u32;
+            freeze!(&mut x);
+            panic!()
+        }));
+
+        assert!(!scope.is_active());
+        assert!(!scope.is_poisoned());
+        assert!(!scope.has_frozen());
+
+        scope.enter(|x| assert_eq!(*x, 0));
+        assert!(scope.is_active());
+        assert!(!scope.is_poisoned());
+        assert!(scope.has_frozen());
+
+        must_panic(|| scope.enter(|_| ()));
+        assert!(!scope.is_active());
+        assert!(scope.is_poisoned());
+        assert!(scope.has_frozen());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn inline_scope() {
+        use std::rc::Rc;
+
+        let rc = Rc::new(());
+        let mut scope = core::pin::pin!(InlineScope::<SingleFamily<u32>, 256>::new({
+            let rc = rc.clone();
+            scope!({
+                let _rc = rc;
+                let mut x = 0u32;
+                loop {
+                    freeze!(&mut x);
+                    x += 1;
+                }
+            })
+        }));
+        assert_eq!(Rc::strong_count(&rc), 2);
+        assert!(!scope.is_poisoned());
+        assert_eq!(scope.as_mut().enter(|x| *x), 0);
+        assert_eq!(scope.as_mut().try_enter(|x| *x), Ok(1));
+        assert!(!scope.is_poisoned());
+
+        // unopened scopes drop their captures
+        {
+            let scope = InlineScope::<SingleFamily<u32>, 256>::new({
+                let rc = rc.clone();
+                scope!({
+                    let _rc = rc;
+                    freeze_forever!(&mut 0)
+                })
+            });
+            assert_eq!(Rc::strong_count(&rc), 3);
+            let _ = scope;
+        }
+        assert_eq!(Rc::strong_count(&rc), 2);
+
+        let mut panicking = core::pin::pin!(InlineScope::<SingleFamily<u32>, 256>::new(scope!({
+            freeze!(&mut 0);
+            panic!()
+        })));
+        assert_eq!(panicking.as_mut().enter(|x| *x), 0);
+        must_panic(|| panicking.as_mut().enter(|x| *x));
+        assert!(panicking.is_poisoned());
+        assert_eq!(
+            panicking.as_mut().try_enter(|x| *x),
+            Err(ScopeError::Poisoned)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn try_new() {
+        let mut scope = BoxScope::<SingleFamily<u32>>::try_new(counter()).unwrap();
+        assert!(scope.has_frozen());
+        assert_eq!(scope.enter(|x| *x), 0);
+        assert_eq!(scope.enter(|x| *x), 1);
+
+        let payload = BoxScope::<SingleFamily<u32>>::try_new(scope!({
+            let mut x = 0u32;
+            if x == 0 {
+                panic!("setup failed");
+            }
+            freeze_forever!(&mut x)
+        }))
+        .unwrap_err();
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"setup failed"));
+
+        // the future is polled again if it didn't freeze when it was first polled
+        let mut scope = BoxScope::<SingleFamily<u32>>::try_new(scope!({
+            let mut x = 0u32;
+            core::future::poll_fn(|cx| {
+                cx.waker().wake_by_ref();
+                x += 1;
+                if x == 1 {
+                    core::task::Poll::Pending
+                } else {
+                    core::task::Poll::Ready(())
+                }
+            })
+            .await;
+            freeze_forever!(&mut x)
+        }))
+        .unwrap();
+        assert!(!scope.has_frozen());
+        assert_eq!(scope.enter(|x| *x), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn drop_order() {
+        use std::{cell::RefCell, rc::Rc};
+
+        struct Logger(&'static str, Rc<RefCell<Vec<&'static str>>>);
+        impl Drop for Logger {
+            fn drop(&mut self) {
+                self.1.borrow_mut().push(self.0);
+            }
+        }
+
+        struct Borrower<'a>(&'a Logger, Rc<RefCell<Vec<&'static str>>>);
+        impl Drop for Borrower<'_> {
+            fn drop(&mut self) {
+                // the borrowed data is still alive while the borrower is dropped
+                assert_eq!(self.0 .0, "owner");
+                self.1.borrow_mut().push("borrower");
+            }
+        }
+
+        struct BorrowerFamily;
+        impl<'a> Family<'a> for BorrowerFamily {
+            type Family = Borrower<'a>;
+        }
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut scope = BoxScope::<BorrowerFamily>::new_dyn({
+            let log = log.clone();
+            scope!({
+                let owner = Logger("owner", log.clone());
+                let mut borrower = Borrower(&owner, log.clone());
+                let _guard = Logger("guard", log);
+                freeze_forever!(&mut borrower)
+            })
+        });
+        scope.enter(|borrower| assert_eq!(borrower.0 .0, "owner"));
+        assert!(log.borrow().is_empty());
+
+        drop(scope);
+        assert_eq!(*log.borrow(), ["guard", "borrower", "owner"]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn unwind_safe() {
+        let scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+            let mut x = 0u32;
+            loop {
+                freeze!(&mut x);
+                x += 1;
+                if x == 2 {
+                    panic!()
+                }
+            }
+        }));
+
+        let mut scope = std::panic::catch_unwind(move || {
+            let mut scope = scope;
+            scope.enter(|x| assert_eq!(*x, 0));
+            scope
+        })
+        .unwrap();
+
+        assert!(!std::panic::catch_unwind(|| scope.is_poisoned()).unwrap());
+        scope.enter(|x| assert_eq!(*x, 1));
+        assert!(std::panic::catch_unwind(move || {
+            let mut scope = scope;
+            scope.enter(|_| ());
+        })
+        .is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn catch_enter() {
+        let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(counter());
+        assert_eq!(scope.catch_enter(|x| *x).unwrap(), Ok(0));
+        let payload = scope.catch_enter(|_| panic!("in enter")).unwrap_err();
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"in enter"));
+        assert!(scope.is_poisoned());
+        assert_eq!(scope.try_enter(|x| *x), Err(ScopeError::Poisoned));
+        assert_eq!(
+            scope.catch_enter(|x| *x).unwrap(),
+            Err(ScopeError::Poisoned)
+        );
+
+        // errors do not poison the scope, as the passed function did not run
+        let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+            let mut x = 0u32;
+            loop {
+                freeze_ref!(&x);
+                freeze!(&mut x);
+                x += 1;
+            }
+        }));
+        assert_eq!(
+            scope.catch_enter(|x| *x += 1).unwrap(),
+            Err(ScopeError::FrozenShared)
+        );
+        assert!(!scope.is_poisoned());
+        assert_eq!(scope.enter_ref(|x| *x), 0);
+        assert_eq!(scope.catch_enter(|x| *x).unwrap(), Ok(0));
+
+        let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+            freeze!(&mut 0);
+            panic!("in scope")
+        }));
+        assert_eq!(scope.catch_enter(|x| *x).unwrap(), Ok(0));
+        let payload = scope.catch_enter(|x| *x).unwrap_err();
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"in scope"));
+        assert_eq!(scope.try_enter(|x| *x), Err(ScopeError::Poisoned));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn abort_on_panic() {
+        let mut scope = BoxScope::<SingleFamily<u32>>::new_abort_on_panic(counter());
+        assert_eq!(scope.enter(|x| *x), 0);
+        // panics in the passed function unwind as usual
+        must_panic(|| scope.enter(|_| panic!()));
+        assert!(!scope.is_poisoned());
+        assert_eq!(scope.enter(|x| *x), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn cancel() {
+        use std::rc::Rc;
+
+        let rc = Rc::new(());
+        let mut scope = BoxScope::<SingleFamily<u32>, _>::new({
+            let rc = rc.clone();
+            scope!({
+                let _rc = rc;
+                let mut x = 0u32;
+                loop {
+                    freeze!(&mut x);
+                    x += 1;
+                }
+            })
+        });
+        assert_eq!(scope.enter(|x| *x), 0);
+        assert_eq!(Rc::strong_count(&rc), 2);
+
+        scope.cancel();
+        assert_eq!(Rc::strong_count(&rc), 1);
+        assert!(!scope.is_active());
+        assert!(!scope.is_poisoned());
+        assert_eq!(scope.try_enter(|x| *x), Err(ScopeError::Cancelled));
+        // cancelling again does nothing
+        scope.cancel();
+        assert_eq!(scope.try_enter(|x| *x), Err(ScopeError::Cancelled));
+    }
+
+    #[test]
+    fn poll_past_completion() {
+        use core::task::{Context, Poll};
+
+        let mut scope = BoxScope::<SingleFamily<u32>, _>::new_with_output(scope!({
+            freeze!(&mut 0);
+            1
+        }));
+        let mut cx = Context::from_waker(&crate::waker::NOOP);
+        assert!(!scope.is_terminated());
+        assert_eq!(scope.poll_enter(&mut cx, |x| *x), Poll::Ready(Ok(Ok(0))));
+        assert!(!scope.is_terminated());
+        assert_eq!(
+            scope.poll_enter(&mut cx, |x| *x),
+            Poll::Ready(Ok(Err(Completed(1))))
+        );
+        for _ in 0..3 {
+            assert!(scope.is_terminated());
+            assert_eq!(
+                scope.poll_enter(&mut cx, |x| *x),
+                Poll::Ready(Err(ScopeError::Completed))
+            );
+        }
+
+        let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({ freeze_forever!(&mut 0) }));
+        scope.enter(|_| ());
+        scope.cancel();
+        assert!(scope.is_terminated());
+        assert_eq!(
+            scope.poll_enter(&mut cx, |x| *x),
+            Poll::Ready(Err(ScopeError::Cancelled))
+        );
+    }
+
+    #[test]
+    fn freeze_per_iteration_local() {
+        use alloc::{format, string::String};
+
+        let mut scope = BoxScope::<MutFamily<String>>::new_dyn(scope!({
+            let mut i = 0u32;
+            loop {
+                // a fresh local, borrowed for a single iteration only
+                let mut line = format!("line {i}");
+                freeze!(&mut &mut line);
+                i += 1;
+            }
+        }));
+        assert_eq!(scope.enter(|line| line.clone()), "line 0");
+        scope.enter(|line| line.push('!'));
+        assert_eq!(scope.enter(|line| line.clone()), "line 2");
+
+        let mut scope = BoxScope::<RefFamily<[u32]>>::new_dyn(scope!({
+            let mut len = 0;
+            loop {
+                let data: alloc::vec::Vec<u32> = (0..len).collect();
+                let mut slice = &data[..];
+                freeze!(&mut slice);
+                len += 1;
+            }
+        }));
+        assert_eq!(scope.enter(|data| data.len()), 0);
+        assert_eq!(scope.enter(|data| data.iter().sum::<u32>()), 0);
+        assert_eq!(scope.enter(|data| data.iter().sum::<u32>()), 1);
+    }
+
+    #[test]
+    fn dyn_scope() {
+        use alloc::{boxed::Box, vec::Vec};
+
+        let counter = BoxScope::new(counter());
+        let doubler = BoxScope::new(scope!({
+            let mut x = 1u32;
+            loop {
+                freeze!(&mut x);
+                x *= 2;
+            }
+        }));
+        let mut scopes: Vec<Box<dyn DynScope<SingleFamily<u32>>>> =
+            alloc::vec![counter.into_dyn(), doubler.into_dyn()];
+
+        for expected in [[0, 1], [1, 2], [2, 4]] {
+            let values: Vec<u32> = scopes.iter_mut().map(|scope| scope.enter(|x| *x)).collect();
+            assert_eq!(values, expected);
+        }
+        assert_eq!(scopes[0].try_enter(|x| *x), Ok(3));
+        assert!(!scopes[1].is_poisoned());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn reusable_scope() {
+        use std::rc::Rc;
+
+        let rc = Rc::new(());
+        let mut generation = 0u32;
+        let mut scope = BoxScope::<SingleFamily<u32>, _>::new_reusable({
+            let rc = rc.clone();
+            move || {
+                generation += 1;
+                let start = generation * 10;
+                let rc = rc.clone();
+                scope!({
+                    let _rc = rc;
+                    let mut x: u32 = start;
+                    loop {
+                        freeze!(&mut x);
+                        x += 1;
+                    }
+                })
+            }
+        });
+        // captured by the producer and by its current scope
+        assert_eq!(Rc::strong_count(&rc), 3);
+        assert_eq!(scope.enter(|x| *x), 10);
+        assert_eq!(scope.try_enter(|x| *x), Ok(11));
+
+        scope.reset();
+        assert_eq!(Rc::strong_count(&rc), 3);
+        assert_eq!(scope.enter(|x| *x), 20);
+
+        must_panic(|| scope.enter(|_| panic!()));
+        scope.reset();
+        assert_eq!(scope.enter(|x| *x), 30);
+
+        let mut scope = scope.into_inner();
+        assert_eq!(Rc::strong_count(&rc), 2);
+        assert_eq!(scope.enter(|x| *x), 31);
+    }
+
+    #[test]
+    fn peek() {
+        use alloc::string::String;
+
+        let mut scope = BoxScope::<SingleFamily<String>>::new_dyn(scope!({
+            let mut s = String::new();
+            loop {
+                freeze_ref!(&s);
+                s.push('a');
+            }
+        }));
+        assert_eq!(scope.peek(|s| s.len()), 0);
+        assert_eq!(scope.peek_clone(), "a");
+        assert_eq!(scope.peek_clone(), "aa");
+    }
+
+    #[test]
+    fn zip() {
+        let first = BoxScope::<SingleFamily<u32>, _>::new_with_output(scope!({
+            for mut x in 0..3 {
+                freeze!(&mut x);
+            }
+            "first"
+        }));
+        let second = BoxScope::<SingleFamily<u32>, _>::new_with_output(scope!({
+            for mut x in 10..12 {
+                freeze!(&mut x);
+            }
+            "second"
+        }));
+
+        let mut zipped = first.zip(second);
+        assert_eq!(zipped.enter(|x, y| (*x, *y)), (0, 10));
+        assert_eq!(zipped.try_enter(|x, y| (*x, *y)), Ok((1, 11)));
+        // the first scope froze again before the second one completed
+        assert_eq!(
+            zipped.enter_or_complete(|x, y| (*x, *y)),
+            Err(ZipCompleted::Second("second"))
+        );
+
+        let (mut first, mut second) = zipped.unzip();
+        assert_eq!(first.enter_or_complete(|x| *x), Err(Completed("first")));
+        assert_eq!(second.try_enter(|x| *x), Err(ScopeError::Completed));
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn spawn_blocking() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let scope = BoxScope::<SingleFamily<u32>, _>::new_dyn_send(scope!({
+                let mut x = 0u32;
+                loop {
+                    freeze!(&mut x);
+                    if x == 2 {
+                        panic!("two");
+                    }
+                    x += 1;
+                }
+            }))
+            .spawn_blocking();
+            let other = scope.clone();
+
+            assert_eq!(scope.enter(|x| *x).await, 0);
+            assert_eq!(other.enter(|x| *x).await, 1);
+            assert_eq!(scope.enter(|x| *x).await, 2);
+
+            // panics of the scope are resumed in the caller, and poison the scope
+            let panicked = tokio::spawn(async move { other.enter(|x| *x).await }).await;
+            assert!(panicked.unwrap_err().is_panic());
+            assert_eq!(scope.try_enter(|x| *x).await, Err(ScopeError::Poisoned));
+        });
+    }
+
+    #[test]
+    fn await_completion() {
+        use core::future::Future;
+        use core::pin::Pin;
+        use core::task::{Context, Poll, Waker};
+
+        let mut scope = BoxScope::<SingleFamily<u32>, _>::new_with_output(scope!({
+            let mut x = 0;
+            freeze!(&mut x);
+            freeze_ref!(&x);
+            x + 1
+        }));
+        scope.enter(|x| *x = 41);
+
+        let mut cx = Context::from_waker(Waker::noop());
+        // shared freezes are skipped like exclusive ones
+        assert_eq!(Pin::new(&mut scope).poll(&mut cx), Poll::Pending);
+        assert_eq!(Pin::new(&mut scope).poll(&mut cx), Poll::Ready(42));
+        assert!(scope.is_terminated());
+    }
+
+    #[cfg(all(feature = "std", debug_assertions))]
+    #[test]
+    #[should_panic(
+        expected = "nolife: a `FrozenFuture` was polled outside of the scope of its `TimeCapsule`"
+    )]
+    fn time_capsule_of_another_scope() {
+        use std::{cell::Cell, rc::Rc};
+
+        let smuggled: Rc<Cell<Option<TimeCapsule<SingleFamily<u32>>>>> = Rc::new(Cell::new(None));
+
+        let smuggler = smuggled.clone();
+        // SAFETY: the time capsule is smuggled out of its scope, violating the precondition on purpose.
+        let mut first = BoxScope::new(unsafe {
+            crate::scope::new_scope(move |mut time_capsule| async move {
+                smuggler.set(Some(time_capsule));
+                let mut x = 0;
+                loop {
+                    time_capsule.freeze(&mut x).await;
+                }
+            })
+        });
+        first.enter(|x| assert_eq!(*x, 0));
+
+        // SAFETY: see above
+        let mut second = BoxScope::new(unsafe {
+            crate::scope::new_scope(move |_: TimeCapsule<SingleFamily<u32>>| async move {
+                let mut time_capsule = smuggled.get().unwrap();
+                let mut y = 1;
+                loop {
+                    time_capsule.freeze(&mut y).await;
+                }
+            })
+        });
+        // the first scope is not polled, so its state must not be written
+        second.enter(|_| ());
+    }
+
+    #[test]
+    fn unsized_family() {
+        use alloc::{borrow::ToOwned, string::String};
+
+        crate::family!(StrFamily<'a> = str);
+        crate::family!(CallbackFamily<'a> = dyn FnMut() -> u32 + 'a);
+
+        let mut scope = BoxScope::<StrFamily>::new_dyn(scope!({
+            let mut line = String::from("frozen");
+            loop {
+                freeze!(line.as_mut_str());
+                line.push('!');
+            }
+        }));
+        assert_eq!(scope.enter(|line| line.len()), 6);
+        scope.enter(|line| line.make_ascii_uppercase());
+        assert_eq!(scope.enter(|line| line.to_owned()), "FROZEN!!");
+
+        let mut scope = BoxScope::<CallbackFamily>::new_dyn(scope!({
+            let mut count = 0;
+            let mut callback = || {
+                count += 1;
+                count
+            };
+            let callback: &mut dyn FnMut() -> u32 = &mut callback;
+            freeze_forever!(callback)
+        }));
+        assert_eq!(scope.enter(|callback| callback()), 1);
+        assert_eq!(scope.enter(|callback| callback()), 2);
+    }
+
+    #[test]
+    fn unsized_slice_family() {
+        use alloc::vec::Vec;
+
+        crate::family!(BytesFamily<'a> = [u8]);
+        struct BytesFamily2;
+        impl<'a, 'b> Family2<'a, 'b> for BytesFamily2 {
+            type Family = [&'a u8];
+        }
+
+        let mut scope = BoxScope::<BytesFamily>::new_dyn(scope!({
+            let mut bytes = Vec::from(*b"nolife");
+            loop {
+                freeze!(bytes.as_mut_slice());
+                bytes.truncate(2);
+            }
+        }));
+        // the length of the slice survives the round-trip through the state of the scope
+        assert_eq!(scope.enter(|bytes| bytes.len()), 6);
+        assert_eq!(scope.enter(|bytes| bytes.to_vec()), b"no");
+
+        let mut scope = BoxScope::<TwoLifetimes<BytesFamily2>>::new_dyn(scope!({
+            let bytes = *b"nolife";
+            let mut refs: Vec<&u8> = bytes.iter().collect();
+            loop {
+                freeze2!(refs.as_mut_slice());
+            }
+        }));
+        assert_eq!(scope.enter(|frozen| frozen.with_ref(|refs| refs.len())), 6);
+    }
+
+    #[test]
+    fn enter_into() {
+        use alloc::{string::String, vec::Vec};
+        use core::cell::OnceCell;
+
+        let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(counter());
+
+        let arena: Vec<OnceCell<String>> = (0..3).map(|_| OnceCell::new()).collect();
+        let copies: Vec<&str> = arena
+            .iter()
+            .map(|slot| {
+                scope.enter_into(slot, |x, slot| {
+                    slot.get_or_init(|| alloc::format!("{x}")).as_str()
+                })
+            })
+            .collect();
+        drop(scope);
+        assert_eq!(copies, ["0", "1", "2"]);
+    }
+
+    #[test]
+    fn unit_family() {
+        let mut scope = BoxScope::<UnitFamily, _>::new_with_output(scope!({
+            for _ in 0..2 {
+                freeze_unit!();
+            }
+            "done"
+        }));
+        assert_eq!(scope.enter_or_complete(|()| ()), Ok(()));
+        assert_eq!(scope.enter_or_complete(|()| ()), Ok(()));
+        assert_eq!(scope.enter_or_complete(|()| ()), Err(Completed("done")));
+    }
+
+    #[test]
+    fn generation() {
+        let mut scope = BoxScope::new(counter());
+        assert_eq!(scope.generation(), 0);
+        assert_eq!(scope.enter(|x| *x), 0);
+        assert_eq!(scope.enter(|x| *x), 1);
+        // entering does not change the generation
+        assert_eq!(scope.generation(), 0);
+
+        scope.reset(counter());
+        assert_eq!(scope.generation(), 1);
+        scope.cancel();
+        assert_eq!(scope.generation(), 2);
+        // cancelling again does nothing
+        scope.cancel();
+        assert_eq!(scope.generation(), 2);
+        scope.reset(counter());
+        assert_eq!(scope.generation(), 3);
+        assert_eq!(scope.enter(|x| *x), 0);
+    }
+
+    #[test]
+    fn scope_with_family() {
+        let mut scope = BoxScope::new(scope!(SingleFamily<u32>, {
+            let mut x = 0;
+            loop {
+                freeze!(&mut x);
+                x += 1;
+            }
+        }));
+        assert_eq!(scope.enter(|x| *x), 0);
+
+        let mut scope = BoxScope::new_with_output(scope!(UnitFamily, {
+            freeze_unit!();
+            "done"
+        }));
+        assert_eq!(scope.enter_or_complete(|()| ()), Ok(()));
+        assert_eq!(scope.enter_or_complete(|()| ()), Err(Completed("done")));
+    }
+
+    #[test]
+    fn freeze_loop() {
+        use alloc::{string::String, vec::Vec};
+
+        let mut scope = BoxScope::<SingleFamily<Vec<String>>>::new_dyn(scope!({
+            let mut generation = 0;
+            freeze_loop!(&mut Vec::new(), |lines: &mut Vec<String>| {
+                generation += 1;
+                lines.retain(|line| !line.is_empty());
+                lines.push(alloc::format!("generation {generation}"));
+            })
+        }));
+
+        assert!(scope.enter(|lines| lines.is_empty()));
+        // the closure runs after each call to `enter`, before the next freeze
+        scope.enter(|lines| {
+            assert_eq!(*lines, ["generation 1"]);
+            lines.push(String::new())
+        });
+        assert_eq!(
+            scope.enter(|lines| lines.clone()),
+            ["generation 1", "generation 2"]
+        );
+    }
+
+    #[test]
+    fn noop_waker() {
+        let waker = crate::noop_waker();
+        assert!(waker.will_wake(&waker::NOOP));
+        // waking does nothing, and cloning returns the same waker
+        waker.wake_by_ref();
+        let clone = waker.clone();
+        assert!(clone.will_wake(waker));
+        clone.wake();
+    }
+
+    #[test]
+    fn freeze_tagged() {
+        use alloc::vec::Vec;
+
+        crate::family!(SumFamily<'a> = u32);
+        impl TagFamily for SumFamily {
+            type Tag = Vec<u32>;
+        }
+
+        let mut scope = BoxScope::<SumFamily>::new_dyn(scope!({
+            let mut sum = 0;
+            let mut values = Vec::new();
+            loop {
+                freeze_tagged!(&mut sum, values.clone());
+                values.push(sum);
+            }
+        }));
+
+        assert_eq!(
+            scope.enter_tagged(|sum, values| (*sum, values.len())),
+            (0, 0)
+        );
+        scope.enter_tagged(|sum, _| *sum += 2);
+        assert_eq!(
+            scope.enter_tagged(|sum, values| (*sum, values.clone())),
+            (2, alloc::vec![0, 2])
+        );
+        // entering without reading the tag is still possible
+        assert_eq!(scope.enter(|sum| *sum), 2);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn enter_tagged_without_tag() {
+        crate::family!(UntaggedFamily<'a> = u32);
+        impl TagFamily for UntaggedFamily {
+            type Tag = ();
+        }
+
+        let mut scope = BoxScope::<UntaggedFamily>::new_dyn(scope!({ freeze_forever!(&mut 0) }));
+        assert!(panic_message(|| scope.enter_tagged(|_, ()| ()))
+            .starts_with("nolife: the scope froze without a tag"));
+        // the scope is not poisoned, as the underlying future did not panic
+        assert_eq!(scope.enter(|x| *x), 0);
+    }
+
+    #[test]
+    fn enter_fn_once() {
+        use alloc::{string::String, vec::Vec};
+
+        let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(counter());
+
+        let names = alloc::vec![String::from("first")];
+        // `names` is moved into the output, which requires `FnOnce`
+        let names: Vec<String> = scope.enter(move |x| {
+            let mut names = names;
+            names.push(alloc::format!("{x}"));
+            names
+        });
+        assert_eq!(names, ["first", "0"]);
+
+        let name = String::from("second");
+        assert_eq!(scope.try_enter(move |_| name), Ok(String::from("second")));
+    }
+
+    #[test]
+    fn scope_fn() {
+        use alloc::vec::Vec;
+
+        struct SetOnDrop<'a>(&'a core::cell::Cell<bool>);
+        impl Drop for SetOnDrop<'_> {
+            fn drop(&mut self) {
+                self.0.set(true);
+            }
+        }
+
+        // the scope can borrow from the caller, as it does not outlive `scope_fn`
+        let dropped = core::cell::Cell::new(false);
+        let flag = &dropped;
+        let (sum, output) = crate::scope_fn(
+            scope!(SingleFamily<u32>, {
+                let _guard = SetOnDrop(flag);
+                let mut x = 1;
+                freeze!(&mut x);
+                x = 2;
+                freeze!(&mut x);
+                "done"
+            }),
+            |scope| {
+                let mut values = Vec::new();
+                let output = loop {
+                    match scope.enter_or_complete(|x| *x) {
+                        Ok(x) => values.push(x),
+                        Err(Completed(output)) => break output,
+                    }
+                };
+                (values.iter().sum::<u32>(), output)
+            },
+        );
+        assert_eq!((sum, output), (3, "done"));
+        assert!(dropped.get());
+    }
+
+    #[test]
+    fn option_family() {
+        let mut scope =
+            BoxScope::<OptionFamily<(RefFamily<str>, SingleFamily<u32>)>>::new_dyn(scope!({
+                let words = ["first", "second"];
+                for (index, word) in words.iter().enumerate() {
+                    freeze!(&mut Some((*word, index as u32)));
+                }
+                freeze_forever!(&mut None)
+            }));
+
+        assert_eq!(scope.enter(|entry| *entry), Some(("first", 0)));
+        assert_eq!(scope.enter(|entry| entry.take()), Some(("second", 1)));
+        assert_eq!(scope.enter(|entry| *entry), None);
+        assert_eq!(scope.enter(|entry| *entry), None);
+    }
+
+    #[test]
+    fn complete_with_stale_freeze() {
+        use core::{
+            future::{poll_fn, Future},
+            pin::pin,
+            task::Poll,
+        };
+
+        // SAFETY: this scope deliberately breaks the precondition of `new_scope` by polling its `FrozenFuture` only
+        // once, to check that the pointer it leaves behind is never used after the future completes.
+        let scope = unsafe {
+            crate::scope::new_scope(
+                |mut time_capsule: TimeCapsule<SingleFamily<u32>>| async move {
+                    let mut x = 0;
+                    let mut frozen = pin!(time_capsule.freeze(&mut x));
+                    poll_fn(|cx| {
+                        let _ = frozen.as_mut().poll(cx);
+                        Poll::Ready(())
+                    })
+                    .await;
+                    42
+                },
+            )
+        };
+        let mut scope = BoxScope::new_with_output(scope);
+
+        assert_eq!(scope.enter_or_complete(|x| *x), Err(Completed(42)));
+        assert!(!scope.is_active());
+        assert_eq!(scope.try_enter(|x| *x), Err(ScopeError::Completed));
+    }
+
+    #[test]
+    fn enter_output_borrows_caller() {
+        use alloc::{string::String, vec::Vec};
+
+        fn longest<'ret>(
+            scope: &mut BoxScope<RefFamily<str>>,
+            words: &'ret [String],
+        ) -> Vec<&'ret str> {
+            scope.enter(|line| {
+                words
+                    .iter()
+                    .map(String::as_str)
+                    .filter(|word| word.len() >= line.len())
+                    .collect()
+            })
+        }
+
+        let words = [String::from("a"), String::from("abc"), String::from("abcd")];
+        let found = {
+            let mut scope = BoxScope::<RefFamily<str>>::new_dyn(scope!({
+                let line = String::from("abc");
+                freeze_forever!(&mut line.as_str())
+            }));
+            longest(&mut scope, &words)
+        };
+        // the scope was dropped, the output only borrows `words`
+        assert_eq!(found, ["abc", "abcd"]);
+
+        // the frozen data can be borrowed for as long as the scope is borrowed
+        let mut scope =
+            BoxScope::<RefFamily<str>>::new_dyn(scope!({ freeze_forever!(&mut "frozen") }));
+        let line: &str = scope.enter(|line| *line);
+        assert_eq!(line, "frozen");
+    }
+
+    #[test]
+    fn freeze_windows() {
+        use alloc::vec::Vec;
+
+        let mut scope = BoxScope::<MutFamily<[u32]>, _>::new_with_output(scope!({
+            let mut buf = [1, 2, 3, 4, 5];
+            freeze_windows!(&mut buf, 2);
+            // an empty buffer is never frozen
+            freeze_windows!(&mut [], 2);
+            buf
+        }));
+
+        let mut windows = Vec::new();
+        let buf = loop {
+            match scope.enter_or_complete(|window| {
+                windows.push(window.to_vec());
+                window.iter_mut().for_each(|x| *x *= 10);
+            }) {
+                Ok(()) => {}
+                Err(Completed(buf)) => break buf,
+            }
+        };
+        assert_eq!(
+            windows,
+            [alloc::vec![1, 2], alloc::vec![3, 4], alloc::vec![5]]
+        );
+        assert_eq!(buf, [10, 20, 30, 40, 50]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn freeze_windows_empty_window() {
+        let mut scope = BoxScope::<MutFamily<[u32]>, _>::new_with_output(scope!({
+            freeze_windows!(&mut [1, 2], 0);
+        }));
+        must_panic(|| scope.enter_or_complete(|window| window.len()));
+        assert!(scope.is_poisoned());
+    }
+
+    #[test]
+    fn enter_or_init() {
+        use alloc::vec::Vec;
+
+        let mut scope = BoxScope::new(counter());
+        let mut firsts = Vec::new();
+        for _ in 0..3 {
+            firsts.push(scope.enter_or_init(|x| (true, *x), |x| (false, *x)));
+        }
+        assert_eq!(firsts, [(true, 0), (false, 1), (false, 2)]);
+
+        scope.reset(counter());
+        assert_eq!(
+            scope.enter_or_init(|x| (true, *x), |x| (false, *x)),
+            (true, 0)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn enter_or_init_primed() {
+        let mut scope = BoxScope::<SingleFamily<u32>>::try_new(counter()).unwrap();
+        // the first freeze was reached by `try_new`, but not entered yet
+        assert!(scope.has_frozen());
+        assert!(scope.enter_or_init(|_| true, |_| false));
+        assert!(!scope.enter_or_init(|_| true, |_| false));
+    }
+
+    #[test]
+    fn enum_family() {
+        #[derive(Debug, PartialEq)]
+        enum Event<'a> {
+            Data(&'a [u8]),
+            Eof,
+        }
+
+        impl Event<'_> {
+            fn clone_data(&self) -> Option<[u8; 3]> {
+                match self {
+                    Event::Data(data) => Some((*data).try_into().unwrap()),
+                    Event::Eof => None,
+                }
+            }
+        }
+
+        crate::family!(EventFamily<'a> = Event<'a>);
+
+        let mut scope = BoxScope::<EventFamily>::new_dyn(scope!({
+            let mut data = [0u8, 1, 2];
+            loop {
+                freeze!(&mut Event::Data(&data));
+                freeze!(&mut Event::Eof);
+                data.reverse();
+            }
+        }));
+
+        assert_eq!(scope.enter(|event| event.clone_data()), Some([0, 1, 2]));
+        assert_eq!(scope.enter(|event| event.clone_data()), None);
+        assert_eq!(scope.enter(|event| event.clone_data()), Some([2, 1, 0]));
+        scope.enter(|event| assert_eq!(*event, Event::Eof));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn enter_until() {
+        use std::time::{Duration, Instant};
+
+        let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+            let mut x = 0;
+            freeze!(&mut x);
+            // never ready, the scope does not make progress
+            core::future::pending::<()>().await;
+            freeze_forever!(&mut x)
+        }));
+
+        // an expired deadline does not poll the underlying future
+        assert_eq!(scope.enter_until(Instant::now(), |x| *x), Err(TimedOut));
+        assert!(!scope.has_frozen());
+        let far = Instant::now() + Duration::from_secs(60);
+        assert_eq!(scope.enter_until(far, |x| *x), Ok(0));
+        // polled repeatedly until the deadline
+        let start = Instant::now();
+        assert_eq!(
+            scope.enter_until(start + Duration::from_millis(10), |x| *x),
+            Err(TimedOut)
+        );
+        assert!(start.elapsed() >= Duration::from_millis(10));
+        assert!(!scope.is_poisoned());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn drop_order_captures_and_allocator() {
+        use crate::allocator::{AllocError, Allocator, Global};
+        use core::{alloc::Layout, ptr::NonNull};
+        use std::{cell::RefCell, rc::Rc};
+
+        type Log = Rc<RefCell<Vec<&'static str>>>;
+
+        struct Logger(&'static str, Log);
+        impl Drop for Logger {
+            fn drop(&mut self) {
+                self.1.borrow_mut().push(self.0);
+            }
+        }
+
+        struct LoggingAllocator(Logger);
+        unsafe impl Allocator for LoggingAllocator {
+            fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+                Global.allocate(layout)
+            }
+
+            unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+                self.0 .1.borrow_mut().push("deallocate");
+                unsafe { Global.deallocate(ptr, layout) }
+            }
+        }
+
+        let log = Log::default();
+        let owner = Logger("captured owner", log.clone());
+        let alloc = LoggingAllocator(Logger("allocator", log.clone()));
+        let mut scope = BoxScope::<RefFamily<Logger>, _, _>::new_in(
+            scope!({
+                let _local = Logger("local", owner.1.clone());
+                // the frozen value borrows the captured owner
+                let mut borrower = &owner;
+                freeze_forever!(&mut borrower)
+            }),
+            alloc,
+        );
+        scope.enter(|borrower| assert_eq!(borrower.0, "captured owner"));
+        assert!(log.borrow().is_empty());
+
+        drop(scope);
+        assert_eq!(
+            *log.borrow(),
+            ["local", "captured owner", "deallocate", "allocator"]
+        );
+    }
+
+    #[test]
+    fn freeze_owned() {
+        use alloc::{string::String, vec::Vec};
+
+        let mut scope = BoxScope::<SingleFamily<Vec<String>>, _>::new_with_output(scope!({
+            let mut lines = Vec::new();
+            for word in ["a", "b"] {
+                // a fresh value is produced at each iteration, and owned by the scope again on resume
+                let line = freeze_owned!(alloc::vec![String::from(word)]);
+                lines.extend(line);
+            }
+            lines
+        }));
+
+        scope.enter(|line| line.push(String::from("pushed")));
+        assert_eq!(scope.enter_or_complete(|line| line.len()), Ok(1));
+        assert_eq!(
+            scope.enter_or_complete(|line| line.len()),
+            Err(Completed(alloc::vec![
+                String::from("a"),
+                String::from("pushed"),
+                String::from("b")
+            ]))
+        );
+    }
+
+    #[test]
+    fn new_borrows_environment() {
+        let start = 40u32;
+        let start = &start;
+        let mut scope = BoxScope::new(scope!(SingleFamily<u32>, {
+            let mut x = *start;
+            loop {
+                freeze!(&mut x);
+                x += 1;
+            }
+        }));
+        assert_eq!(scope.enter(|x| *x), 40);
+        assert_eq!(scope.enter(|x| *x), 41);
+    }
+
+    #[test]
+    fn pin_mut_family() {
+        use core::{marker::PhantomPinned, pin::Pin};
+
+        /// A self-referential counter, that must not move once pinned.
+        struct Counter {
+            count: u32,
+            this: *const Counter,
+            _pinned: PhantomPinned,
+        }
+
+        impl Counter {
+            fn increment(self: Pin<&mut Self>) -> u32 {
+                // SAFETY: `count` is not structurally pinned.
+                let this = unsafe { self.get_unchecked_mut() };
+                if this.this.is_null() {
+                    this.this = this;
+                }
+                assert_eq!(this.this, this as *const Counter, "moved after pinning");
+                this.count += 1;
+                this.count
+            }
+        }
+
+        let mut scope = BoxScope::<PinMutFamily<Counter>>::new_dyn(scope!({
+            let mut counter = core::pin::pin!(Counter {
+                count: 0,
+                this: core::ptr::null(),
+                _pinned: PhantomPinned,
+            });
+            loop {
+                freeze!(&mut counter.as_mut());
+                // the scope can still use the pinned value after it was entered
+                counter.as_mut().increment();
+            }
+        }));
+
+        assert_eq!(scope.enter(|counter| counter.as_mut().increment()), 1);
+        assert_eq!(scope.enter(|counter| counter.as_mut().increment()), 3);
+        assert_eq!(scope.enter(|counter| counter.count), 4);
+    }
+
+    fn freeze_indices() -> impl TopScope<Family = SingleFamily<u64>> {
+        scope!({
+            let mut index = freeze_index!();
+            loop {
+                freeze!(&mut index);
+                index = freeze_index!();
+            }
+        })
+    }
+
+    #[test]
+    fn freeze_index() {
+        let mut scope = BoxScope::new(freeze_indices());
+        assert_eq!(scope.enter(|index| *index), 0);
+        assert_eq!(scope.enter(|index| *index), 1);
+        assert_eq!(scope.enter(|index| *index), 2);
+
+        // the count restarts with the new future
+        scope.reset(freeze_indices());
+        assert_eq!(scope.enter(|index| *index), 0);
+        assert_eq!(scope.enter(|index| *index), 1);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn freeze_index_try_new() {
+        let mut scope = BoxScope::try_new(freeze_indices()).unwrap();
+        // the first freeze was reached by `try_new`
+        assert_eq!(scope.enter(|index| *index), 0);
+        assert_eq!(scope.enter(|index| *index), 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn snapshot() {
+        let mut scope = BoxScope::<RefFamily<str>>::new_dyn(scope!({
+            let mut s = String::from("a");
+            loop {
+                freeze_ref!(&s.as_str());
+                s.push('"');
+            }
+        }));
+        assert_eq!(scope.snapshot(), br#""a""#);
+        assert_eq!(scope.snapshot(), br#""a\"""#);
+        // snapshotting enters the scope like any other call
+        assert_eq!(scope.enter_ref(|s| s.len()), 3);
+    }
+
+    #[test]
+    fn either_family() {
+        use alloc::string::String;
+
+        let mut scope =
+            BoxScope::<EitherFamily<MutFamily<u32>, RefFamily<str>>>::new_dyn(scope!({
+                let mut count = 0;
+                let mut name = String::new();
+                loop {
+                    freeze_left!(&mut count);
+                    name.push('x');
+                    freeze_right!(name.as_str());
+                }
+            }));
+
+        for expected in ["x", "xx", "xxx"] {
+            scope.enter(|phase| match phase {
+                Either::Left(count) => **count += 1,
+                Either::Right(_) => panic!("expected the left phase"),
+            });
+            let name = scope.enter(|phase| match phase {
+                Either::Left(_) => panic!("expected the right phase"),
+                Either::Right(name) => String::from(*name),
+            });
+            assert_eq!(name, expected);
+        }
+        // replacing the frozen value does not change the phase of the scope, nor the data it borrowed
+        scope.enter(|phase| *phase = Either::Right("replaced"));
+        scope.enter(|phase| assert_eq!(*phase, Either::Right("xxxx")));
+        scope.enter(|phase| assert!(matches!(phase, Either::Left(count) if **count == 3)));
+    }
+
+    /// Reads the data borrowed by a scope in its destructor, so that Miri checks that the data is still valid
+    /// when the future of the scope is dropped.
+    #[cfg(feature = "std")]
+    struct ReadOnDrop<'a> {
+        words: &'a [alloc::string::String],
+        total: &'a core::cell::Cell<usize>,
+    }
+
+    #[cfg(feature = "std")]
+    impl Drop for ReadOnDrop<'_> {
+        fn drop(&mut self) {
+            let len: usize = self.words.iter().map(|word| word.len()).sum();
+            self.total.set(self.total.get() + len);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn drop_while_frozen_at_each_freeze() {
+        use alloc::string::{String, ToString};
+        use core::cell::Cell;
+
+        for entered in 0..4 {
+            let total = Cell::new(0);
+            let total = &total;
+            let mut scope = BoxScope::<RefFamily<[String]>, _>::new(scope!({
+                let words: Vec<String> = ["frozen", "in", "time"].map(ToString::to_string).into();
+                let _reader = ReadOnDrop {
+                    words: &words,
+                    total,
+                };
+                let mut steps = 0;
+                loop {
+                    freeze!(&mut &words[..steps]);
+                    steps = (steps + 1) % (words.len() + 1);
+                }
+            }));
+            for step in 0..entered {
+                assert_eq!(scope.enter(|words| words.len()), step);
+            }
+            drop(scope);
+            // the reader only exists once the future was polled
+            assert_eq!(total.get(), if entered == 0 { 0 } else { 12 });
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn drop_while_frozen_with_owned_values() {
+        use alloc::string::String;
+        use std::rc::Rc;
+
+        let rc = Rc::new(());
+        for entered in 0..3 {
+            let mut scope =
+                BoxScope::<EitherFamily<SingleFamily<Rc<()>>, SingleFamily<Rc<()>>>, _>::new({
+                    let rc = rc.clone();
+                    scope!({
+                        let mut rc = rc;
+                        loop {
+                            rc = match freeze_owned!(Either::Left(rc)) {
+                                Either::Left(rc) | Either::Right(rc) => rc,
+                            };
+                            freeze_right!(rc.clone());
+                        }
+                    })
+                });
+            for _ in 0..entered {
+                scope.enter(|_| {});
+            }
+            drop(scope);
+            assert_eq!(
+                Rc::strong_count(&rc),
+                1,
+                "leaked after {entered} calls to enter"
+            );
+        }
+
+        let mut scope = BoxScope::<SingleFamily<String>, _>::new(scope!({
+            let mut line = String::from("owned");
+            loop {
+                line = freeze_owned!(line);
+            }
+        }));
+        // the value moved out of the frozen data by `enter` is replaced, and the replacement dropped with the scope
+        let owned = scope.enter(|line| core::mem::replace(line, String::from("replacement")));
+        assert_eq!(owned, "owned");
+        assert_eq!(scope.enter(|line| line.clone()), "replacement");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn panic_while_dropping_frozen_scope() {
+        use core::cell::Cell;
+
+        struct PanicOnDrop<'a>(&'a Cell<bool>);
+        impl Drop for PanicOnDrop<'_> {
+            fn drop(&mut self) {
+                self.0.set(true);
+                panic!("dropping the frozen scope");
+            }
+        }
+
+        let dropped = Cell::new(false);
+        let dropped = &dropped;
+        let mut scope = BoxScope::<SingleFamily<u32>, _>::new(scope!({
+            let _guard = PanicOnDrop(dropped);
+            let mut x = 0;
+            loop {
+                freeze!(&mut x);
+                x += 1;
+            }
+        }));
+        assert_eq!(scope.enter(|x| *x), 0);
+        // the allocation is released even though dropping the future panics, which Miri checks
+        must_panic(move || drop(scope));
+        assert!(dropped.get());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn panic_in_future_with_frozen_borrows() {
+        use alloc::string::{String, ToString};
+        use core::cell::Cell;
+
+        let total = Cell::new(0);
+        let total = &total;
+        let mut scope = BoxScope::<RefFamily<[String]>, _>::new(scope!({
+            let words: Vec<String> = ["a", "bc"].map(ToString::to_string).into();
+            let _reader = ReadOnDrop {
+                words: &words,
+                total,
+            };
+            freeze!(&mut &words[..]);
+            panic!("panicking while the frozen data borrows the locals")
+        }));
+        assert_eq!(scope.enter(|words| words.len()), 2);
+        // the locals are dropped while unwinding out of the future
+        must_panic(|| scope.enter(|_| {}));
+        assert_eq!(total.get(), 3);
+        assert!(scope.is_poisoned());
+        assert_eq!(
+            scope.try_enter(|words| words.len()),
+            Err(ScopeError::Poisoned)
+        );
+        // dropping the poisoned scope does not drop the locals again
+        drop(scope);
+        assert_eq!(total.get(), 3);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn reenter_after_failures() {
+        fn panicking_counter() -> impl TopScope<Family = SingleFamily<u32>> {
+            scope!({
+                let mut x = 0u32;
+                loop {
+                    freeze!(&mut x);
+                    x += 1;
+                    assert!(x != 3, "the future panics on its third resume");
+                }
+            })
+        }
+
+        let mut scope = BoxScope::new(panicking_counter());
+        for _ in 0..2 {
+            // a panicking closure skips the frozen value, but does not poison the scope
+            must_panic(|| scope.enter(|_| panic!()));
+            assert!(!scope.is_poisoned());
+            assert_eq!(scope.enter(|x| *x), 1);
+            assert_eq!(scope.enter(|x| *x), 2);
+            // the future panics, which poisons the scope
+            must_panic(|| scope.enter(|_| {}));
+            must_panic(|| scope.enter(|_| {}));
+            assert!(scope.is_poisoned());
+            assert_eq!(scope.try_enter(|x| *x), Err(ScopeError::Poisoned));
+            assert_eq!(scope.try_enter(|x| *x), Err(ScopeError::Poisoned));
+            // resetting recovers from the poisoning
+            scope.reset(panicking_counter());
+        }
+
+        assert_eq!(scope.enter(|x| *x), 0);
+        scope.cancel();
+        assert_eq!(scope.try_enter(|x| *x), Err(ScopeError::Cancelled));
+        scope.reset(panicking_counter());
+        assert_eq!(scope.enter(|x| *x), 0);
+        assert_eq!(scope.enter(|x| *x), 1);
+    }
+
+    #[test]
+    fn map_output() {
+        use alloc::vec::Vec;
+        use core::cell::Cell;
+
+        fn summing() -> impl Scope<Family = SingleFamily<u32>, Output = u32> {
+            scope!({
+                let mut sum = 0;
+                for mut x in 0..3 {
+                    freeze!(&mut x);
+                    sum += x;
+                }
+                sum
+            })
+        }
+
+        let calls = Cell::new(0);
+        let mut scope = BoxScope::new_with_output(summing()).map_output(|sum| {
+            calls.set(calls.get() + 1);
+            alloc::vec![sum; 2]
+        });
+        assert_eq!(scope.enter(|x| *x), 0);
+        assert_eq!(scope.enter_or_complete(|x| *x = 10), Ok(()));
+        assert_eq!(scope.enter_or_complete(|x| *x), Ok(2));
+        assert_eq!(calls.get(), 0);
+        assert_eq!(
+            scope.enter_or_complete(|x| *x),
+            Err(Completed(alloc::vec![12; 2]))
+        );
+        assert_eq!(calls.get(), 1);
+        // the completion is only reported once, so the mapping function does not run again
+        assert_eq!(scope.try_enter(|x| *x), Err(ScopeError::Completed));
+        assert_eq!(scope.finish(), None::<Vec<u32>>);
+        assert_eq!(calls.get(), 1);
+
+        let scope = BoxScope::new_with_output(summing()).map_output(|sum| sum * 2);
+        assert_eq!(scope.finish(), Some(6));
+
+        // dropping the scope before it completes does not run the mapping function
+        let mut scope = BoxScope::new_with_output(summing()).map_output(|_| calls.set(10));
+        scope.enter(|_| {});
+        drop(scope);
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn nth() {
+        let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(counter());
+        assert_eq!(scope.nth(3, |x| *x), Some(3));
+        assert_eq!(scope.nth(0, |x| *x), Some(4));
+        assert_eq!(scope.enter(|x| *x), 5);
+
+        let mut scope = BoxScope::<SingleFamily<u32>, _>::new_with_output(scope!({
+            for mut x in 0..3 {
+                freeze!(&mut x);
+            }
+        }));
+        assert_eq!(scope.nth(3, |x| *x), None);
+        assert_eq!(scope.nth(0, |x| *x), None);
+    }
+
+    #[test]
+    fn enter_if() {
+        let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(counter());
+        assert_eq!(scope.enter_if(|x| *x == 1, |x| *x), None);
+        assert_eq!(scope.enter_if(|x| *x == 1, |x| *x), None);
+        // the value rejected by the predicate is accessed by the next call to `enter`
+        assert_eq!(scope.enter(|x| *x), 0);
+        assert_eq!(scope.enter_if(|x| *x == 1, |x| *x += 10), Some(()));
+        assert_eq!(scope.enter(|x| *x), 12);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn enter_if_panicking_predicate() {
+        let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(counter());
+        must_panic(|| scope.enter_if(|_| panic!(), |x| *x));
+        assert!(!scope.is_poisoned());
+        assert_eq!(scope.enter_if(|_| true, |x| *x), Some(0));
+    }
+
+    #[test]
+    fn freeze_from_helper_with_copied_time_capsule() {
+        use alloc::vec::Vec;
+
+        async fn freeze_twice(mut time_capsule: TimeCapsule<SingleFamily<u32>>, x: &mut u32) {
+            time_capsule.freeze(x).await;
+            *x += 1;
+            time_capsule.freeze(x).await;
+        }
+
+        // SAFETY: each freeze is awaited immediately, as the helper is, so that at most one freeze is pending at a time.
+        let scope = unsafe {
+            scope::new_scope(|time_capsule: TimeCapsule<SingleFamily<u32>>| async move {
+                let mut x = 0;
+                loop {
+                    freeze_twice(time_capsule, &mut x).await;
+                    x += 10;
+                    freeze_twice(time_capsule, &mut x).await;
+                    x += 100;
+                }
+            })
+        };
+        let mut scope = BoxScope::new(scope);
+        let values: Vec<u32> = (0..6).map(|_| scope.enter(|x| *x)).collect();
+        assert_eq!(values, [0, 1, 11, 12, 112, 113]);
+    }
+
+    #[test]
+    fn enter_owned_results() {
+        use alloc::{
+            boxed::Box,
+            string::{String, ToString},
+            vec::Vec,
+        };
+        use core::fmt::Display;
+
+        fn describe(scope: &mut BoxScope<RefFamily<[u32]>>) -> Box<dyn Display> {
+            scope.enter(|values| Box::new(alloc::format!("{values:?}")))
+        }
+
+        fn doubled(scope: &mut BoxScope<RefFamily<[u32]>>) -> impl Iterator<Item = u32> {
+            let values =
+                scope.enter(|values| values.iter().map(|value| value * 2).collect::<Vec<_>>());
+            values.into_iter()
+        }
+
+        let mut scope = BoxScope::<RefFamily<[u32]>>::new_dyn(scope!({
+            let mut values = alloc::vec![1, 2];
+            loop {
+                freeze!(&mut values.as_slice());
+                values.push(values.len() as u32 + 1);
+            }
+        }));
+
+        let described = describe(&mut scope);
+        let doubled = doubled(&mut scope);
+        let boxed = scope.enter(|values| Box::new(values.len()) as Box<dyn Display>);
+        // the results do not borrow the scope
+        drop(scope);
+        assert_eq!(described.to_string(), "[1, 2]");
+        assert_eq!(doubled.collect::<Vec<_>>(), [2, 4, 6]);
+        assert_eq!(boxed.to_string(), String::from("4"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn builder_default() {
+        let mut scope = BoxScope::builder().build(counter());
+        assert_eq!(scope.enter(|x| *x), 0);
+        // same as `BoxScope::new`: a panic of the passed function does not poison the scope
+        must_panic(|| scope.enter(|_| panic!()));
+        assert!(!scope.is_poisoned());
+        assert_eq!(scope.enter(|x| *x), 2);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn builder_poison_on_any_panic() {
+        let mut scope = BoxScope::builder()
+            .on_poison(PoisonPolicy::OnAnyPanic)
+            .build(counter());
+        assert_eq!(scope.enter(|x| *x), 0);
+        must_panic(|| scope.enter(|_| panic!()));
+        assert!(scope.is_poisoned());
+        assert_eq!(scope.try_enter(|x| *x), Err(ScopeError::Poisoned));
+
+        // the policy is kept when the scope is reset
+        scope.reset(counter());
+        assert_eq!(scope.enter(|x| *x), 0);
+        must_panic(|| scope.enter_if(|_| panic!(), |x| *x));
+        assert!(scope.is_poisoned());
+    }
+
+    #[test]
+    fn builder_allocator() {
+        use crate::allocator::{AllocError, Allocator, Global};
+        use core::{alloc::Layout, cell::Cell, ptr::NonNull};
+
+        #[derive(Default)]
+        struct CountingAllocator {
+            allocated: Cell<usize>,
+        }
+
+        unsafe impl Allocator for CountingAllocator {
+            fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+                self.allocated.set(self.allocated.get() + 1);
+                Global.allocate(layout)
+            }
+
+            unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+                unsafe { Global.deallocate(ptr, layout) }
+            }
+        }
+
+        let mut scope = BoxScope::builder()
+            .abort_on_panic(true)
+            .allocator(CountingAllocator::default())
+            .build(counter());
+        assert_eq!(scope.allocator().allocated.get(), 1);
+        assert_eq!(scope.enter(|x| *x), 0);
+        assert_eq!(scope.enter(|x| *x), 1);
+    }
+
+    #[test]
+    fn step() {
+        use alloc::vec::Vec;
+
+        let mut scope = BoxScope::<SingleFamily<u32>, _>::new_with_output(scope!({
+            let mut x = 0u32;
+            freeze!(&mut x);
+            x += 1;
+            freeze_ref!(&x);
+            x += 1;
+            freeze!(&mut x);
+            x
+        }));
+        assert_eq!(scope.step(), Ok(()));
+        assert_eq!(scope.step(), Ok(()));
+        // reading the stepped data does not poll the underlying future
+        assert_eq!(scope.enter_ref(|x| *x), 1);
+        assert_eq!(scope.step(), Ok(()));
+        assert_eq!(scope.step(), Err(ScopeError::Completed));
+        assert_eq!(scope.step(), Err(ScopeError::Completed));
+        assert!(!scope.is_poisoned());
+
+        let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(counter());
+        let values: Vec<(bool, u32)> = (0..3)
+            .map(|_| {
+                scope.step().unwrap();
+                scope.enter_or_init(|x| (true, *x), |x| (false, *x))
+            })
+            .collect();
+        assert_eq!(values, [(true, 0), (false, 1), (false, 2)]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn step_poisoned() {
+        let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+            let mut x = 0u32;
+            freeze!(&mut x);
+            panic!()
+        }));
+        assert_eq!(scope.step(), Ok(()));
+        must_panic(|| scope.step());
+        assert!(scope.is_poisoned());
+        assert_eq!(scope.step(), Err(ScopeError::Poisoned));
+    }
+
+    #[cfg(feature = "std")]
+    fn panic_message<F, R>(f: F) -> std::string::String
+    where
+        F: FnOnce() -> R,
+    {
+        let Err(payload) = std::panic::catch_unwind(core::panic::AssertUnwindSafe(f)) else {
+            panic!("the function did not panic");
+        };
+        // formatted messages are passed as a `String`
+        match payload.downcast::<std::string::String>() {
+            Ok(message) => *message,
+            Err(payload) => std::string::String::from(*payload.downcast_ref::<&str>().unwrap()),
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn panic_messages() {
+        let mut scope = BoxScope::<SingleFamily<u32>, _>::new_with_output(scope!({
+            freeze_ref!(&0);
+            let mut x = 1;
+            freeze!(&mut x);
+        }));
+        assert!(panic_message(|| scope.enter(|x| *x))
+            .starts_with("nolife: the scope's future froze the value behind a shared reference"));
+        assert_eq!(scope.enter_ref(|x| *x), 0);
+        scope.enter(|x| assert_eq!(*x, 1));
+        assert!(panic_message(|| scope.enter(|x| *x))
+            .starts_with("nolife: the scope's future completed and can no longer be polled"));
+
+        let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({ panic!("in scope") }));
+        assert_eq!(panic_message(|| scope.enter(|x| *x)), "in scope");
+        assert!(panic_message(|| scope.enter(|x| *x))
+            .starts_with("nolife: the scope's future panicked and can no longer be polled"));
+
+        let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+            let mut x = 0;
+            loop {
+                freeze!(&mut x);
+            }
+        }));
+        scope.cancel();
+        assert!(panic_message(|| scope.enter(|x| *x))
+            .starts_with("nolife: the scope's future was cancelled and can no longer be polled"));
+
+        let mut scope = BoxScope::<MutFamily<[u8]>, _>::new_with_output(scope!({
+            freeze_windows!(&mut [0, 1], 0);
+        }));
+        assert_eq!(
+            panic_message(|| scope.enter(|window| window.len())),
+            "nolife: the window length must be non-zero"
+        );
+
+        #[cfg(feature = "serde")]
+        {
+            use alloc::collections::BTreeMap;
+
+            // JSON objects can only have string keys
+            let mut scope = BoxScope::<RefFamily<BTreeMap<(u8, u8), u8>>>::new_dyn(scope!({
+                let map = BTreeMap::from([((0, 0), 0)]);
+                loop {
+                    freeze_ref!(&&map);
+                }
+            }));
+            assert!(panic_message(|| scope.snapshot())
+                .starts_with("nolife: failed to serialize the frozen data: "));
+        }
+    }
+
+    #[test]
+    fn freeze_static() {
+        use alloc::boxed::Box;
+
+        let counter: &'static mut u32 = Box::leak(Box::new(0));
+        let counter_ptr: *const u32 = counter;
+        let mut scope = BoxScope::<StaticFamily<SingleFamily<u32>>, _>::new_with_output(scope!({
+            let mut counter = counter;
+            for _ in 0..2 {
+                counter = freeze_static!(counter);
+                *counter += 10;
+            }
+            counter
+        }));
+
+        scope.enter(|counter| **counter += 1);
+        assert_eq!(scope.enter(|counter| **counter), 11);
+        // the scope gives the same `'static` reference back when it completes
+        let Err(Completed(counter)) = scope.enter_or_complete(|_| ()) else {
+            panic!("the scope did not complete");
+        };
+        assert_eq!(*counter, 21);
+        assert!(core::ptr::eq(counter, counter_ptr));
+        // SAFETY: the reference was leaked from a box above, and is no longer used by the scope.
+        drop(unsafe { Box::from_raw(counter) });
+
+        // the data of a family with a lifetime is lent with that lifetime set to `'static`
+        let name: &'static mut &'static str = Box::leak(Box::new("a"));
+        let mut scope = BoxScope::<StaticFamily<RefFamily<str>>, _>::new_with_output(scope!({
+            freeze_static!(name)
+        }));
+        scope.enter(|name| **name = "b");
+        let Err(Completed(name)) = scope.enter_or_complete(|_| ()) else {
+            panic!("the scope did not complete");
+        };
+        assert_eq!(*name, "b");
+        // SAFETY: see above
+        drop(unsafe { Box::from_raw(name) });
+    }
+
+    #[cfg(feature = "std")]
+    fn panicking_after_freeze() -> impl TopScope<Family = SingleFamily<u32>> {
+        scope!({
+            let mut x = 0u32;
+            freeze!(&mut x);
+            panic!("in scope")
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn panic_policy_unwind() {
+        let mut scope = BoxScope::builder()
+            .on_panic(PanicPolicy::Unwind)
+            .build(panicking_after_freeze());
+        assert_eq!(scope.try_enter(|x| *x), Ok(0));
+        assert_eq!(panic_message(|| scope.try_enter(|x| *x)), "in scope");
+        assert!(scope.is_poisoned());
+        assert_eq!(scope.try_enter(|x| *x), Err(ScopeError::Poisoned));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn panic_policy_poison() {
+        let mut scope = BoxScope::builder()
+            .on_panic(PanicPolicy::Poison)
+            .build(panicking_after_freeze());
+        assert_eq!(scope.try_enter(|x| *x), Ok(0));
+        // the panic of the future is reported as an error by the call that panicked
+        assert_eq!(scope.try_enter(|x| *x), Err(ScopeError::Poisoned));
+        assert!(scope.is_poisoned());
+        assert_eq!(scope.try_enter(|x| *x), Err(ScopeError::Poisoned));
+        assert!(panic_message(|| scope.enter(|x| *x))
+            .starts_with("nolife: the scope's future panicked and can no longer be polled"));
+
+        let mut scope = BoxScope::builder()
+            .on_panic(PanicPolicy::Poison)
+            .build(panicking_after_freeze());
+        assert_eq!(scope.enter(|x| *x), 0);
+        assert!(panic_message(|| scope.enter(|x| *x))
+            .starts_with("nolife: the scope's future panicked and can no longer be polled"));
+
+        // panics of the passed function are not caught
+        let mut scope = BoxScope::builder()
+            .on_panic(PanicPolicy::Poison)
+            .build(counter());
+        assert_eq!(
+            panic_message(|| scope.enter(|_| panic!("in enter"))),
+            "in enter"
+        );
+        assert!(!scope.is_poisoned());
+        assert_eq!(scope.enter(|x| *x), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    #[cfg_attr(miri, ignore)]
+    fn panic_policy_abort() {
+        const CHILD: &str = "NOLIFE_PANIC_POLICY_ABORT_CHILD";
+
+        if std::env::var_os(CHILD).is_some() {
+            let mut scope = BoxScope::builder()
+                .on_panic(PanicPolicy::Abort)
+                .build(panicking_after_freeze());
+            assert_eq!(scope.enter(|x| *x), 0);
+            let _ = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| scope.enter(|x| *x)));
+            // not reached, the second call aborts the process
+            std::process::exit(0);
+        }
+
+        // the test is run again in a child process, that must abort
+        let status = std::process::Command::new(std::env::current_exe().unwrap())
+            .args(["--exact", "test::panic_policy_abort", "--test-threads=1"])
+            .env(CHILD, "1")
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .unwrap();
+        assert!(!status.success());
+        #[cfg(unix)]
+        assert_eq!(
+            std::os::unix::process::ExitStatusExt::signal(&status),
+            Some(6) // SIGABRT
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "families")]
+    fn dyn_fn_mut_family() {
+        use crate::families::DynFnMutFamily;
+        use alloc::vec::Vec;
+
+        let mut scope = BoxScope::<DynFnMutFamily>::new_dyn(scope!({
+            let mut events = Vec::new();
+            loop {
+                // closures of distinct types, so that each freeze carries its own vtable
+                let mut on_click: &mut dyn FnMut() = &mut || events.push("click");
+                freeze!(&mut on_click);
+                let mut count = 0;
+                let mut on_key: &mut dyn FnMut() = &mut || {
+                    count += 1;
+                    events.push("key");
+                };
+                freeze!(&mut on_key);
+                events.push(if count == 2 { "two keys" } else { "other keys" });
+                let mut check: &mut dyn FnMut() = &mut || {
+                    assert_eq!(events, ["click", "key", "key", "two keys"]);
+                    events.clear();
+                };
+                freeze!(&mut check);
+            }
+        }));
+
+        for _ in 0..2 {
+            scope.enter(|on_click| on_click());
+            scope.enter(|on_key| {
+                on_key();
+                on_key();
+            });
+            scope.enter(|check| check());
+        }
+    }
+
+    #[test]
+    fn future_size() {
+        fn buffered() -> impl TopScope<Family = SingleFamily<u32>> {
+            scope!({
+                let mut buffer = [0u32; 256];
+                loop {
+                    freeze!(&mut buffer[0]);
+                    buffer.rotate_left(1);
+                }
+            })
+        }
+
+        let sized = BoxScope::new(buffered());
+        let mut erased = BoxScope::new_dyn(buffered());
+        assert!(sized.future_size() >= core::mem::size_of::<[u32; 256]>());
+        // the size survives the erasure of the type of the future
+        assert_eq!(erased.future_size(), sized.future_size());
+        erased.enter(|x| *x = 1);
+        assert_eq!(erased.future_size(), sized.future_size());
+
+        let small = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({ freeze_forever!(&mut 0) }));
+        assert!(small.future_size() < sized.future_size());
     }
 }
@@ -0,0 +1,172 @@
+use core::future::Future;
+
+use crate::{
+    allocator::{Allocator, Global},
+    panics, BoxScope, Completed, Family, ScopeError,
+};
+
+/// A [`BoxScope`] whose output is transformed when its underlying future completes.
+///
+/// Created with [`BoxScope::map_output`].
+///
+/// The frozen data is handed to `enter` unchanged. The mapping function runs exactly once,
+/// when the completion of the underlying future is reported by [`MapOutputScope::enter_or_complete`]
+/// or [`MapOutputScope::finish`]. It never runs if the future is dropped before it completes,
+/// or if the completion is observed through [`MapOutputScope::try_enter`], that drops the output.
+pub struct MapOutputScope<T, F: ?Sized, M, A = Global>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+{
+    scope: BoxScope<T, F, A>,
+    map: Option<M>,
+}
+
+impl<T, F: ?Sized, A> BoxScope<T, F, A>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+{
+    /// Wraps the scope so that its output is transformed by `map` when its underlying future completes.
+    ///
+    /// See [`MapOutputScope`] for when `map` runs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, Completed, SingleFamily};
+    ///
+    /// let scope = BoxScope::<SingleFamily<u32>, _>::new_with_output(scope!({
+    ///     let mut sum = 0;
+    ///     for mut x in 0..2 {
+    ///         freeze!(&mut x);
+    ///         sum += x;
+    ///     }
+    ///     sum
+    /// }));
+    ///
+    /// let mut scope = scope.map_output(|sum| format!("sum: {sum}"));
+    /// assert_eq!(scope.enter_or_complete(|x| *x), Ok(0));
+    /// assert_eq!(scope.enter_or_complete(|x| *x = 41), Ok(()));
+    /// assert_eq!(scope.enter_or_complete(|x| *x), Err(Completed(String::from("sum: 41"))));
+    /// ```
+    pub fn map_output<O, M>(self, map: M) -> MapOutputScope<T, F, M, A>
+    where
+        M: FnOnce(F::Output) -> O,
+    {
+        MapOutputScope {
+            scope: self,
+            map: Some(map),
+        }
+    }
+}
+
+impl<T, F: ?Sized, M, A, O> MapOutputScope<T, F, M, A>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+    M: FnOnce(F::Output) -> O,
+{
+    /// Enters the scope, making it possible to access the data frozen inside of the scope,
+    /// or returns the transformed output of the underlying future if it completes instead of freezing the scope.
+    ///
+    /// # Panics
+    ///
+    /// - Same as [`BoxScope::enter_or_complete`], or if the mapping function panics.
+    pub fn enter_or_complete<'borrow, Output, G>(
+        &'borrow mut self,
+        f: G,
+    ) -> Result<Output, Completed<O>>
+    where
+        G: for<'a> FnOnce(&'borrow mut <T as Family<'a>>::Family) -> Output,
+    {
+        let map = &mut self.map;
+        self.scope
+            .enter_or_complete(f)
+            .map_err(|Completed(output)| {
+                // the underlying future completes at most once, so the mapping function is still present.
+                let Some(map) = map.take() else {
+                    panics::mapped_twice()
+                };
+                Completed(map(output))
+            })
+    }
+
+    /// Drives the underlying future to completion without accessing the frozen data,
+    /// returning its transformed output.
+    ///
+    /// # Panics
+    ///
+    /// - Same as [`BoxScope::finish`], or if the mapping function panics.
+    pub fn finish(mut self) -> Option<O> {
+        let map = self.map.take();
+        // the mapping function is only taken when the future completes, after which `finish` returns `None`.
+        self.scope.finish().map(|output| {
+            let Some(map) = map else {
+                panics::mapped_twice()
+            };
+            map(output)
+        })
+    }
+}
+
+impl<T, F: ?Sized, M, A> MapOutputScope<T, F, M, A>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+{
+    /// Enters the scope, making it possible to access the data frozen inside of the scope.
+    ///
+    /// # Panics
+    ///
+    /// - Same as [`BoxScope::enter`].
+    pub fn enter<'borrow, Output, G>(&'borrow mut self, f: G) -> Output
+    where
+        G: for<'a> FnOnce(&'borrow mut <T as Family<'a>>::Family) -> Output,
+    {
+        self.scope.enter(f)
+    }
+
+    /// Enters the scope, making it possible to access the data frozen inside of the scope.
+    ///
+    /// # Errors
+    ///
+    /// - Same as [`BoxScope::try_enter`]. The output of the future is dropped without being transformed.
+    ///
+    /// # Panics
+    ///
+    /// - Same as [`BoxScope::try_enter`].
+    pub fn try_enter<'borrow, Output, G>(&'borrow mut self, f: G) -> Result<Output, ScopeError>
+    where
+        G: for<'a> FnOnce(&'borrow mut <T as Family<'a>>::Family) -> Output,
+    {
+        self.scope.try_enter(f)
+    }
+
+    /// Returns the underlying scope, dropping the mapping function.
+    pub fn into_inner(self) -> BoxScope<T, F, A> {
+        self.scope
+    }
+
+    /// Returns a reference to the underlying scope.
+    pub fn get_ref(&self) -> &BoxScope<T, F, A> {
+        &self.scope
+    }
+}
+
+impl<T, F: ?Sized, M, A> core::fmt::Debug for MapOutputScope<T, F, M, A>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MapOutputScope")
+            .field("scope", &self.scope)
+            .finish_non_exhaustive()
+    }
+}
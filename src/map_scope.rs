@@ -0,0 +1,143 @@
+use core::{future::Future, marker::PhantomData};
+
+use crate::{
+    allocator::{Allocator, Global},
+    BoxScope, Family, ScopeError,
+};
+
+/// A [`BoxScope`] whose frozen data is transformed before being handed to `enter`.
+///
+/// Created with [`BoxScope::map`].
+pub struct MapScope<T, U, F: ?Sized, M, A = Global>
+where
+    T: for<'a> Family<'a>,
+    U: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+{
+    scope: BoxScope<T, F, A>,
+    map: M,
+    family: PhantomData<fn() -> U>,
+}
+
+impl<T, F: ?Sized, A> BoxScope<T, F, A>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+{
+    /// Wraps the scope so that its frozen data is transformed by `map` before being handed to `enter`.
+    ///
+    /// This allows exposing a different view on the frozen data, without changing the underlying future.
+    /// `map` is higher-ranked: it is called on a reference `&'b mut` to the data frozen with family `T`,
+    /// and returns a reference to data of family `U` borrowed from it, with the same lifetime `'b`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, SingleFamily};
+    ///
+    /// struct Buffer {
+    ///     data: Vec<u8>,
+    ///     reads: usize,
+    /// }
+    ///
+    /// let scope = BoxScope::<SingleFamily<Buffer>>::new_dyn(scope!({
+    ///     let mut buffer = Buffer { data: vec![1, 2, 3], reads: 0 };
+    ///     loop {
+    ///         freeze!(&mut buffer);
+    ///         buffer.reads += 1;
+    ///     }
+    /// }));
+    ///
+    /// // the consumer only sees the data of the buffer
+    /// let mut scope = scope.map::<SingleFamily<Vec<u8>>, _>(|buffer| &mut buffer.data);
+    /// scope.enter(|data| data[0] = 42);
+    /// assert_eq!(scope.enter(|data| data.clone()), [42, 2, 3]);
+    ///
+    /// let mut scope = scope.into_inner();
+    /// assert_eq!(scope.enter(|buffer| buffer.reads), 2);
+    /// ```
+    pub fn map<U, M>(self, map: M) -> MapScope<T, U, F, M, A>
+    where
+        U: for<'a> Family<'a>,
+        M: for<'a, 'b> Fn(&'b mut <T as Family<'a>>::Family) -> &'b mut <U as Family<'b>>::Family,
+    {
+        MapScope {
+            scope: self,
+            map,
+            family: PhantomData,
+        }
+    }
+}
+
+impl<T, U, F: ?Sized, M, A> MapScope<T, U, F, M, A>
+where
+    T: for<'a> Family<'a>,
+    U: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+    M: for<'a, 'b> Fn(&'b mut <T as Family<'a>>::Family) -> &'b mut <U as Family<'b>>::Family,
+{
+    /// Enters the scope, making it possible to access the transformed data frozen inside of the scope.
+    ///
+    /// # Panics
+    ///
+    /// - Same as [`BoxScope::enter`], or if the mapping function panics.
+    pub fn enter<'borrow, Output, G>(&'borrow mut self, f: G) -> Output
+    where
+        G: for<'a> FnOnce(&'borrow mut <U as Family<'a>>::Family) -> Output,
+    {
+        let map = &self.map;
+        self.scope.enter(|frozen| f(map(frozen)))
+    }
+
+    /// Enters the scope, making it possible to access the transformed data frozen inside of the scope.
+    ///
+    /// # Errors
+    ///
+    /// - Same as [`BoxScope::try_enter`].
+    ///
+    /// # Panics
+    ///
+    /// - Same as [`BoxScope::try_enter`], or if the mapping function panics.
+    pub fn try_enter<'borrow, Output, G>(&'borrow mut self, f: G) -> Result<Output, ScopeError>
+    where
+        G: for<'a> FnOnce(&'borrow mut <U as Family<'a>>::Family) -> Output,
+    {
+        let map = &self.map;
+        self.scope.try_enter(|frozen| f(map(frozen)))
+    }
+}
+
+impl<T, U, F: ?Sized, M, A> MapScope<T, U, F, M, A>
+where
+    T: for<'a> Family<'a>,
+    U: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+{
+    /// Returns the underlying scope, dropping the mapping function.
+    pub fn into_inner(self) -> BoxScope<T, F, A> {
+        self.scope
+    }
+
+    /// Returns a reference to the underlying scope.
+    pub fn get_ref(&self) -> &BoxScope<T, F, A> {
+        &self.scope
+    }
+}
+
+impl<T, U, F: ?Sized, M, A> core::fmt::Debug for MapScope<T, U, F, M, A>
+where
+    T: for<'a> Family<'a>,
+    U: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MapScope")
+            .field("scope", &self.scope)
+            .finish_non_exhaustive()
+    }
+}
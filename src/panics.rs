@@ -0,0 +1,190 @@
+//! The panics of the crate.
+//!
+//! Each message names the crate, the invariant that failed, and the likely mistake, so that a misuse of the scopes,
+//! or of the unsafe [`crate::scope::new_scope`], can be told apart from a panic of the user code.
+
+use crate::ScopeError;
+
+/// Panics when a scope is entered in a way that reports `err`.
+#[cold]
+#[track_caller]
+pub(crate) fn scope_error(err: ScopeError) -> ! {
+    match err {
+        ScopeError::Poisoned => panic!(
+            "nolife: the scope's future panicked and can no longer be polled, \
+             check `BoxScope::is_poisoned` or enter it with `BoxScope::try_enter`"
+        ),
+        ScopeError::NotFrozen => panic!(
+            "nolife: the scope's future did not fill the value, it awaited a future other than a freeze, \
+             enter it with `BoxScope::poll_enter` to drive such futures"
+        ),
+        ScopeError::Completed => panic!(
+            "nolife: the scope's future completed and can no longer be polled, \
+             enter it with `BoxScope::enter_or_complete` to observe its completion"
+        ),
+        ScopeError::FrozenShared => panic!(
+            "nolife: the scope's future froze the value behind a shared reference, \
+             enter it with `BoxScope::enter_ref`"
+        ),
+        ScopeError::Cancelled => panic!(
+            "nolife: the scope's future was cancelled and can no longer be polled, \
+             reset it with `BoxScope::reset`"
+        ),
+    }
+}
+
+/// Panics when a scope is entered while it is already entered.
+#[cold]
+#[track_caller]
+pub(crate) fn entered_reentrantly() -> ! {
+    panic!(
+        "nolife: the scope was entered re-entrantly, from its own future \
+         or from the function passed to `BoxScope::enter`"
+    )
+}
+
+/// Panics when an [`crate::RcScope`] is entered while it is already entered through another handle.
+#[cold]
+#[track_caller]
+pub(crate) fn rc_entered_reentrantly() -> ! {
+    panic!(
+        "nolife: the `RcScope` was entered re-entrantly, \
+         from the function passed to `RcScope::enter` through another handle"
+    )
+}
+
+/// Panics when the status of an [`crate::RcScope`] is queried while it is entered through another handle.
+#[cold]
+#[track_caller]
+pub(crate) fn rc_queried_while_entered() -> ! {
+    panic!(
+        "nolife: the `RcScope` was queried while it was entered, \
+         from the function passed to `RcScope::enter` through another handle"
+    )
+}
+
+/// Panics when a [`crate::FrozenFuture`] is polled after it completed.
+#[cold]
+#[track_caller]
+pub(crate) fn frozen_future_polled_after_completion() -> ! {
+    panic!(
+        "nolife: a `FrozenFuture` was polled after completion, \
+         each freeze of a `TimeCapsule` must be awaited exactly once"
+    )
+}
+
+/// Panics when a [`crate::FrozenFuture`] is polled by a scope other than the one of its time capsule.
+#[cfg(debug_assertions)]
+#[cold]
+#[track_caller]
+pub(crate) fn foreign_time_capsule() -> ! {
+    panic!(
+        "nolife: a `FrozenFuture` was polled outside of the scope of its `TimeCapsule`, \
+         the time capsule was moved out of its scope"
+    )
+}
+
+/// Panics when a [`crate::FrozenFuture`] is polled while another one froze the scope.
+#[cfg(debug_assertions)]
+#[cold]
+#[track_caller]
+pub(crate) fn concurrent_freezes() -> ! {
+    panic!(
+        "nolife: a `FrozenFuture` was polled while another `FrozenFuture` froze the scope, \
+         freezes must be awaited one at a time, not joined"
+    )
+}
+
+/// Panics when a scope frozen with [`crate::TimeCapsule::freeze_yield`] is resumed without a value.
+#[cold]
+#[track_caller]
+pub(crate) fn resumed_without_value() -> ! {
+    panic!(
+        "nolife: the scope was resumed without a value, \
+         a scope frozen with `TimeCapsule::freeze_yield` must be entered with `BoxScope::enter_yield`"
+    )
+}
+
+/// Panics when a scope is entered with [`crate::BoxScope::enter_tagged`] while it froze without a tag.
+#[cold]
+#[track_caller]
+pub(crate) fn frozen_without_tag() -> ! {
+    panic!(
+        "nolife: the scope froze without a tag, \
+         freeze it with `TimeCapsule::freeze_tagged` to enter it with `BoxScope::enter_tagged`"
+    )
+}
+
+/// Panics when an [`crate::EnterFuture`] is polled after it completed.
+#[cold]
+#[track_caller]
+pub(crate) fn enter_future_polled_after_completion() -> ! {
+    panic!("nolife: an `EnterFuture` was polled after completion")
+}
+
+/// Panics when the blocking task of a [`crate::BlockingScope`] stopped.
+#[cfg(feature = "tokio")]
+#[cold]
+#[track_caller]
+pub(crate) fn blocking_task_stopped() -> ! {
+    panic!("nolife: the blocking task of the scope was stopped, the Tokio runtime was shut down")
+}
+
+/// Panics when [`crate::TimeCapsule::freeze_windows`] is called with a window length of zero.
+#[cold]
+#[track_caller]
+pub(crate) fn empty_window() -> ! {
+    panic!("nolife: the window length must be non-zero")
+}
+
+/// Panics when [`crate::BoxScope::snapshot`] fails to serialize the frozen data.
+#[cfg(feature = "serde")]
+#[cold]
+#[track_caller]
+pub(crate) fn serialization_failed(err: serde_json::Error) -> ! {
+    panic!("nolife: failed to serialize the frozen data: {err}")
+}
+
+/// Panics when a scope that aborts on panic panicked, to abort the process without `std`.
+///
+/// Must only be called while unwinding, as panicking while unwinding aborts the process.
+#[cfg(not(feature = "std"))]
+#[cold]
+pub(crate) fn panicked_in_abort_scope() -> ! {
+    panic!("nolife: a panic occurred while polling a scope that aborts on panic")
+}
+
+/// Panics when a scope calls the function passed to enter it more than once.
+#[cold]
+#[track_caller]
+pub(crate) fn entered_twice() -> ! {
+    panic!("nolife: the scope called the function passed to enter it more than once, this is a bug of the crate")
+}
+
+/// Panics when a scope calls the function passed to enter it, but reports that it did not freeze.
+#[cold]
+#[track_caller]
+pub(crate) fn entered_without_freezing() -> ! {
+    panic!("nolife: the scope called the function passed to enter it without freezing, this is a bug of the crate")
+}
+
+/// Panics when entering a scope succeeds without calling the passed function.
+#[cold]
+#[track_caller]
+pub(crate) fn entered_without_calling() -> ! {
+    panic!("nolife: the scope was entered without calling the passed function, this is a bug of the crate")
+}
+
+/// Panics when the state of an [`crate::EnterFuture`] changes between reading and replacing it.
+#[cold]
+#[track_caller]
+pub(crate) fn enter_future_state_changed() -> ! {
+    panic!("nolife: the state of an `EnterFuture` changed while it was polled, this is a bug of the crate")
+}
+
+/// Panics when the output of a [`crate::MapOutputScope`] is mapped more than once.
+#[cold]
+#[track_caller]
+pub(crate) fn mapped_twice() -> ! {
+    panic!("nolife: the output of a `MapOutputScope` was mapped more than once, this is a bug of the crate")
+}
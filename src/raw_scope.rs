@@ -1,14 +1,29 @@
-use crate::{waker, Family, Never, TopScope};
+use crate::{
+    panics, scope::Scope, waker, Completed, Either, EitherFamily, Family, Never, ResumeFamily,
+    ScopeError, StaticFamily, TagFamily,
+};
 use core::{
     future::Future,
-    marker::PhantomData,
-    mem::MaybeUninit,
+    marker::{PhantomData, PhantomPinned},
+    mem::{self, MaybeUninit},
     pin::Pin,
     ptr::{addr_of_mut, NonNull},
-    task::Poll,
+    task::{Context, Poll},
 };
 
 /// The future resulting from using a time capsule to freeze some scope.
+///
+/// This future does nothing unless it is awaited: the scope only freezes when the future is polled.
+/// Forgetting the `.await` results in a scope that never freezes, so that entering it fails with
+/// [`ScopeError::NotFrozen`] or never returns.
+///
+/// # Panics
+///
+/// Polling this future again after it completed panics with the message "`FrozenFuture` polled after completion".
+/// This cannot happen with the macros of [`crate::scope!`], that await each `FrozenFuture` exactly once,
+/// but can happen when a `FrozenFuture` is pinned and polled manually, for instance by awaiting
+/// `Pin<&mut FrozenFuture>` several times.
+#[must_use = "freeze does nothing unless you .await it"]
 pub struct FrozenFuture<'a, 'b, T>
 where
     T: for<'c> Family<'c>,
@@ -19,17 +34,182 @@ where
     // operations that "touch" the FrozenFuture such moving it or passing it to a function.
     // (This probably wasn't exploitable with the scope! macro, but it still seems
     // more correct this way.)
-    mut_ref: State<T>,
-    state: *mut State<T>,
+    //
+    // The frozen pointer is stored as separate fields rather than as a `Frozen`, so that freezing the scope
+    // writes them to the state one by one. Copying a whole `Frozen` that was just written to the future
+    // defeats store forwarding, which noticeably slows down `enter` on tight loops.
+    ptr: Option<NonNull<<T as Family<'static>>::Family>>,
+    shared: bool,
+    state: NonNull<State<T>>,
+    /// The status of the scope that `state` belongs to, see [`TimeCapsule::status`].
+    #[cfg(debug_assertions)]
+    status: NonNull<Status>,
     marker: PhantomData<&'a mut <T as Family<'b>>::Family>,
 }
 
+/// The future resulting from using a time capsule to freeze some scope until it is entered with a value.
+///
+/// See [`TimeCapsule::freeze_yield`]. Like [`FrozenFuture`], this future does nothing unless it is awaited.
+#[must_use = "freeze_yield does nothing unless you .await it"]
+pub struct FreezeYieldFuture<'a, 'b, T>
+where
+    T: ResumeFamily,
+    'b: 'a,
+{
+    frozen: FrozenFuture<'a, 'b, T>,
+    // Written through `Frozen::resume` while the scope is frozen.
+    resume: Option<T::Resume>,
+    // `Frozen::resume` points to `resume`, so this future must not move once polled.
+    _pinned: PhantomPinned,
+}
+
+/// The future resulting from using a time capsule to freeze some scope with a tag.
+///
+/// See [`TimeCapsule::freeze_tagged`]. Like [`FrozenFuture`], this future does nothing unless it is awaited.
+#[must_use = "freeze_tagged does nothing unless you .await it"]
+pub struct FreezeTaggedFuture<'a, 'b, T>
+where
+    T: TagFamily,
+    'b: 'a,
+{
+    frozen: FrozenFuture<'a, 'b, T>,
+    // Read through `Frozen::tag` while the scope is frozen.
+    tag: T::Tag,
+    // `Frozen::tag` points to `tag`, so this future must not move once polled.
+    _pinned: PhantomPinned,
+}
+
 /// Passed to the closures of a scope so that they can freeze the scope.
+///
+/// # Copies
+///
+/// A `TimeCapsule` is [`Copy`]: all its copies point to the state of the same scope, which is how `sub_scope!`
+/// passes it to sub-scopes, and how it can be passed by value to helper `async fn`s.
+///
+/// The freeze methods take `&mut self`, so that a copy cannot start a freeze while its previous one is pending.
+/// This does not prevent two copies from freezing at the same time, though: that at most one freeze is pending
+/// at a time is guaranteed by awaiting each freeze immediately, which [`crate::scope!`] ensures and the unsafe
+/// constructors of scopes require from their producers.
 pub struct TimeCapsule<T>
 where
     T: for<'a> Family<'a>,
 {
-    pub(crate) state: *mut State<T>,
+    pub(crate) state: NonNull<State<T>>,
+    /// The status of the scope that `state` belongs to.
+    ///
+    /// Read by [`TimeCapsule::freeze_index`]. In debug builds, [`FrozenFuture`] also checks that this scope is
+    /// the one being polled, so that a time capsule smuggled to another scope panics instead of corrupting the state
+    /// of its scope.
+    pub(crate) status: NonNull<Status>,
+}
+
+// SAFETY: the future of a scope owns or borrows the frozen data, so it is sent along with the data.
+// The pointer to the state is only accessed when the future is polled by its scope,
+// which requires exclusive access to the scope, so it is never accessed from two threads simultaneously.
+unsafe impl<'a, 'b, T> Send for FrozenFuture<'a, 'b, T>
+where
+    T: for<'c> Family<'c>,
+    for<'c> <T as Family<'c>>::Family: Send,
+{
+}
+
+// SAFETY: see `FrozenFuture`. `TimeCapsule` only gives access to the state through `FrozenFuture`.
+unsafe impl<T> Send for TimeCapsule<T>
+where
+    T: for<'a> Family<'a>,
+    for<'a> <T as Family<'a>>::Family: Send,
+{
+}
+
+impl<'a, 'b, T> Future for FreezeYieldFuture<'a, 'b, T>
+where
+    T: ResumeFamily,
+{
+    type Output = T::Resume;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: nothing is moved out of `this`
+        let this = unsafe { self.get_unchecked_mut() };
+        match Pin::new(&mut this.frozen).poll(cx) {
+            Poll::Ready(()) => match this.resume.take() {
+                Some(resume) => Poll::Ready(resume),
+                None => panics::resumed_without_value(),
+            },
+            Poll::Pending => {
+                // SAFETY: the state is valid and not borrowed elsewhere while the underlying future is polled,
+                // see `FrozenFuture::poll`.
+                if let State::Frozen(frozen) = unsafe { this.frozen.state.as_mut() } {
+                    frozen.resume = Some(NonNull::from(&mut this.resume).cast());
+                }
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<'a, 'b, T> Future for FreezeTaggedFuture<'a, 'b, T>
+where
+    T: TagFamily,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: nothing is moved out of `this`
+        let this = unsafe { self.get_unchecked_mut() };
+        let poll = Pin::new(&mut this.frozen).poll(cx);
+        if poll.is_pending() {
+            // SAFETY: the state is valid and not borrowed elsewhere while the underlying future is polled,
+            // see `FrozenFuture::poll`.
+            if let State::Frozen(frozen) = unsafe { this.frozen.state.as_mut() } {
+                frozen.tag = Some(NonNull::from(&this.tag).cast());
+            }
+        }
+        poll
+    }
+}
+
+impl<'a, 'b, T> core::fmt::Debug for FreezeTaggedFuture<'a, 'b, T>
+where
+    T: TagFamily,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FreezeTaggedFuture")
+            .field("polled", &self.frozen.ptr.is_none())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, 'b, T> core::fmt::Debug for FreezeYieldFuture<'a, 'b, T>
+where
+    T: ResumeFamily,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FreezeYieldFuture")
+            .field("polled", &self.frozen.ptr.is_none())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, 'b, T> core::fmt::Debug for FrozenFuture<'a, 'b, T>
+where
+    T: for<'c> Family<'c>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // The frozen value is not printed: it is borrowed by the future, and its type may not implement `Debug`.
+        f.debug_struct("FrozenFuture")
+            .field("polled", &self.ptr.is_none())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> core::fmt::Debug for TimeCapsule<T>
+where
+    T: for<'a> Family<'a>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // The state is not read: the `TimeCapsule` may outlive the scope it points to.
+        f.debug_struct("TimeCapsule").finish_non_exhaustive()
+    }
 }
 
 impl<T> Clone for TimeCapsule<T>
@@ -43,6 +223,68 @@ where
 
 impl<T> Copy for TimeCapsule<T> where T: for<'a> Family<'a> {}
 
+impl<T> Clone for Frozen<T>
+where
+    T: for<'a> Family<'a>,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Frozen<T> where T: for<'a> Family<'a> {}
+
+impl<L, R> TimeCapsule<EitherFamily<L, R>>
+where
+    L: for<'a> Family<'a>,
+    R: for<'a> Family<'a>,
+    for<'a> <L as Family<'a>>::Family: Sized,
+    for<'a> <R as Family<'a>>::Family: Sized,
+{
+    /// Freeze a scope with a value of the left family, available as [`Either::Left`] to the next call to
+    /// [`crate::BoxScope::enter`].
+    ///
+    /// The value is moved into the returned future, as with [`Self::freeze_owned`].
+    /// The passed function of `enter` may replace the value, which is dropped when the scope resumes.
+    ///
+    /// See [`EitherFamily`].
+    pub async fn freeze_left<'b>(&mut self, left: <L as Family<'b>>::Family) {
+        self.freeze(&mut Either::Left(left)).await
+    }
+
+    /// Freeze a scope with a value of the right family, available as [`Either::Right`] to the next call to
+    /// [`crate::BoxScope::enter`].
+    ///
+    /// The value is moved into the returned future, as with [`Self::freeze_owned`].
+    /// The passed function of `enter` may replace the value, which is dropped when the scope resumes.
+    ///
+    /// See [`EitherFamily`].
+    pub async fn freeze_right<'b>(&mut self, right: <R as Family<'b>>::Family) {
+        self.freeze(&mut Either::Right(right)).await
+    }
+}
+
+impl<F> TimeCapsule<StaticFamily<F>>
+where
+    F: Family<'static>,
+{
+    /// Freeze a scope with `'static` data, lending it to the next call to [`crate::BoxScope::enter`]
+    /// with the lifetime of the scope.
+    ///
+    /// The `'static` reference is given back when the scope resumes, so that it can be frozen again.
+    ///
+    /// See [`StaticFamily`].
+    pub async fn freeze_static(
+        &mut self,
+        data: &'static mut <F as Family<'static>>::Family,
+    ) -> &'static mut <F as Family<'static>>::Family {
+        // `enter` only sees a reborrow, that ends when the scope resumes.
+        let mut lent: &mut <F as Family<'static>>::Family = &mut *data;
+        self.freeze(&mut lent).await;
+        data
+    }
+}
+
 impl<T> TimeCapsule<T>
 where
     T: for<'a> Family<'a>,
@@ -53,6 +295,25 @@ where
     ///
     /// For simple cases where you don't need to execute code in the scope between two calls to `enter`,
     /// use [`Self::freeze_forever`].
+    ///
+    /// The lifetime `'b` of the frozen data is chosen anew for each call, so that the same time capsule
+    /// can freeze data that only lives for one iteration of a loop:
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, MutFamily};
+    ///
+    /// let mut scope = BoxScope::<MutFamily<String>>::new_dyn(scope!({
+    ///     let mut i = 0;
+    ///     loop {
+    ///         let mut line = format!("line {i}");
+    ///         freeze!(&mut &mut line);
+    ///         i += 1;
+    ///     }
+    /// }));
+    ///
+    /// assert_eq!(scope.enter(|line| line.clone()), "line 0");
+    /// assert_eq!(scope.enter(|line| line.clone()), "line 1");
+    /// ```
     pub fn freeze<'a, 'b>(
         &'a mut self,
         t: &'a mut <T as Family<'b>>::Family,
@@ -61,12 +322,160 @@ where
         'b: 'a,
     {
         FrozenFuture {
-            mut_ref: Some(NonNull::from(t).cast()),
+            ptr: Some(erase_lifetime::<T>(NonNull::from(t))),
+            shared: false,
+            state: self.state,
+            #[cfg(debug_assertions)]
+            status: self.status,
+            marker: PhantomData,
+        }
+    }
+
+    /// Freeze a scope, making the data it has borrowed available to the outside behind a shared reference.
+    ///
+    /// Once a scope is frozen this way, its borrowed data can only be accessed through [`crate::BoxScope::enter_ref`].
+    /// Calling [`crate::BoxScope::enter`] instead results in [`crate::ScopeError::FrozenShared`], and keeps the
+    /// frozen data for the next call.
+    ///
+    /// This allows the scope to keep a mutable handle on its data between two calls to `enter_ref`.
+    pub fn freeze_ref<'a, 'b>(
+        &'a mut self,
+        t: &'a <T as Family<'b>>::Family,
+    ) -> FrozenFuture<'a, 'b, T>
+    where
+        'b: 'a,
+    {
+        FrozenFuture {
+            ptr: Some(erase_lifetime::<T>(NonNull::from(t))),
+            shared: true,
             state: self.state,
+            #[cfg(debug_assertions)]
+            status: self.status,
             marker: PhantomData,
         }
     }
 
+    /// Freeze a scope, making the data it has borrowed available to the outside, and resume it with a value.
+    ///
+    /// Once a scope is frozen this way, it expects to be entered with [`crate::BoxScope::enter_yield`].
+    /// The returned future then resolves to the value returned by the function passed to `enter_yield`.
+    ///
+    /// This allows building request/response loops, where the scope exposes some state and reacts to a command.
+    ///
+    /// # Panics
+    ///
+    /// The returned future panics, poisoning the scope, if the scope is entered with a method other than
+    /// [`crate::BoxScope::enter_yield`], as no value is then passed back to the scope.
+    pub fn freeze_yield<'a, 'b>(
+        &'a mut self,
+        t: &'a mut <T as Family<'b>>::Family,
+    ) -> FreezeYieldFuture<'a, 'b, T>
+    where
+        'b: 'a,
+        T: ResumeFamily,
+    {
+        FreezeYieldFuture {
+            frozen: self.freeze(t),
+            resume: None,
+            _pinned: PhantomPinned,
+        }
+    }
+
+    /// Freeze a scope, making the data it has borrowed available to the outside along with a tag.
+    ///
+    /// Once a scope is frozen this way, the tag can be read through [`crate::BoxScope::enter_tagged`],
+    /// for instance to report the phase or progress of a generator without storing it in the frozen data.
+    ///
+    /// The tag is owned by the returned future: it is dropped when the scope resumes.
+    pub fn freeze_tagged<'a, 'b>(
+        &'a mut self,
+        t: &'a mut <T as Family<'b>>::Family,
+        tag: T::Tag,
+    ) -> FreezeTaggedFuture<'a, 'b, T>
+    where
+        'b: 'a,
+        T: TagFamily,
+    {
+        FreezeTaggedFuture {
+            frozen: self.freeze(t),
+            tag,
+            _pinned: PhantomPinned,
+        }
+    }
+
+    /// Freeze a scope with a value it owns, lending the value to the outside and getting it back when the scope resumes.
+    ///
+    /// The value is moved into the returned future, which is pinned while it is awaited.
+    /// The state of the scope only stores a pointer to the value, as with [`Self::freeze`]:
+    /// the next call to [`crate::BoxScope::enter`] accesses the value mutably through this pointer,
+    /// and the future then resolves to the value, including the changes made by `enter`.
+    ///
+    /// If the scope is dropped while frozen, the value is dropped with the future.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, SingleFamily};
+    ///
+    /// let mut scope = BoxScope::<SingleFamily<String>>::new_dyn(scope!({
+    ///     let mut line = String::from("hello");
+    ///     loop {
+    ///         line = freeze_owned!(line);
+    ///         line.push('!');
+    ///     }
+    /// }));
+    ///
+    /// scope.enter(|line| line.make_ascii_uppercase());
+    /// assert_eq!(scope.enter(|line| line.clone()), "HELLO!");
+    /// ```
+    pub async fn freeze_owned<'b>(
+        &mut self,
+        mut t: <T as Family<'b>>::Family,
+    ) -> <T as Family<'b>>::Family
+    where
+        <T as Family<'b>>::Family: Sized,
+    {
+        self.freeze(&mut t).await;
+        t
+    }
+
+    /// The number of times the scope froze so far.
+    ///
+    /// This is 0 until the scope reaches its first freeze, and is then incremented each time the scope freezes, whether
+    /// or not the frozen data was accessed. After the `n`-th call to [`crate::BoxScope::enter`] resumed the scope,
+    /// the scope froze `n` times, unless the scope was created with [`crate::BoxScope::try_new`], which makes the first
+    /// scope freeze before the first call to `enter`.
+    ///
+    /// Inside of a [`crate::scope!`], use the `freeze_index!()` macro.
+    ///
+    /// This can be used to perform some setup lazily, after the first freeze:
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, SingleFamily};
+    ///
+    /// let mut scope = BoxScope::<SingleFamily<Vec<u32>>>::new_dyn(scope!({
+    ///     let mut values = Vec::new();
+    ///     loop {
+    ///         freeze!(&mut values);
+    ///         if freeze_index!() == 1 {
+    ///             // values only needed once the scope was entered once
+    ///             values.extend([1, 2, 3]);
+    ///         }
+    ///         values.push(0);
+    ///     }
+    /// }));
+    ///
+    /// assert!(scope.enter(|values| values.is_empty()));
+    /// assert_eq!(scope.enter(|values| values.clone()), [1, 2, 3, 0]);
+    /// assert_eq!(scope.enter(|values| values.len()), 5);
+    /// ```
+    pub fn freeze_index(&self) -> u64 {
+        // SAFETY: the status belongs to the scope of the time capsule, that is alive while its future runs,
+        // as per the precondition of `scope::new_scope`. It is only read, and no reference to it is live
+        // while the future is polled.
+        unsafe { (*self.status.as_ptr()).freeze_count }
+    }
+
     /// Freeze a scope forever, making the data it has borrowed available to the outside.
     ///
     /// Once a scope is frozen, its borrowed data can be accessed through [`crate::BoxScope::enter`].
@@ -80,25 +489,297 @@ where
             self.freeze(t).await
         }
     }
+
+    /// Freeze a scope repeatedly, calling `step` on its borrowed data after each call to [`crate::BoxScope::enter`].
+    ///
+    /// This is the same as `loop { freeze!(t); step(t) }`, without the risk of forgetting to await the freeze.
+    ///
+    /// Calling methods on the parameter of `step` may require annotating its type,
+    /// as the family of the scope is usually not yet inferred when the closure is type-checked.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, SingleFamily};
+    ///
+    /// let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+    ///     freeze_loop!(&mut 0, |x| *x += 1)
+    /// }));
+    ///
+    /// assert_eq!(scope.enter(|x| *x), 0);
+    /// assert_eq!(scope.enter(|x| *x), 1);
+    /// assert_eq!(scope.enter(|x| *x), 2);
+    /// ```
+    pub async fn freeze_loop<'a, 'b, M>(
+        &'a mut self,
+        t: &'a mut <T as Family<'b>>::Family,
+        mut step: M,
+    ) -> Never
+    where
+        M: FnMut(&mut <T as Family<'b>>::Family),
+    {
+        loop {
+            self.freeze(t).await;
+            step(t);
+        }
+    }
+
+    /// Freeze a scope once for each window of `window_len` elements of `buf`, in order, making the current window
+    /// available to the outside.
+    ///
+    /// The windows do not overlap, and cover the whole buffer: when the length of `buf` is not a multiple of
+    /// `window_len`, the final window is shorter and holds the remaining elements, as with [`slice::chunks_mut`].
+    /// This returns once the scope was resumed after the final window, and immediately for an empty buffer.
+    ///
+    /// # Panics
+    ///
+    /// - If `window_len` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, MutFamily};
+    ///
+    /// let mut scope = BoxScope::<MutFamily<[u8]>, _>::new_with_output(scope!({
+    ///     let mut buf = *b"abcdefg";
+    ///     freeze_windows!(&mut buf, 3);
+    ///     buf
+    /// }));
+    ///
+    /// assert_eq!(scope.enter_or_complete(|window| window.to_vec()), Ok(b"abc".to_vec()));
+    /// assert_eq!(scope.enter_or_complete(|window| window.make_ascii_uppercase()), Ok(()));
+    /// assert_eq!(scope.enter_or_complete(|window| window.to_vec()), Ok(b"g".to_vec()));
+    /// assert_eq!(scope.enter_or_complete(|window| window.len()).unwrap_err().0, *b"abcDEFg");
+    /// ```
+    pub async fn freeze_windows<E>(&mut self, buf: &mut [E], window_len: usize)
+    where
+        T: for<'a> Family<'a, Family = &'a mut [E]>,
+    {
+        if window_len == 0 {
+            panics::empty_window();
+        }
+        for mut window in buf.chunks_mut(window_len) {
+            self.freeze(&mut window).await;
+        }
+    }
+}
+
+/// Whether the future of a scope is frozen, and if so, where its data is.
+///
+/// This type is a pointer-type and lifetime-erased equivalent of
+/// `Option<&'a mut <T as Family<'b>>::Family>`, or `Option<&'a <T as Family<'b>>::Family>`
+/// when the data was frozen with `TimeCapsule::freeze_ref`.
+///
+/// Entering a scope polls its future exactly once, with the following transitions:
+///
+/// 1. The [`FrozenFuture`] that froze the scope during the previous poll finds the state `Frozen`,
+///    resets it to `Empty` and completes.
+/// 2. The future runs until its next freeze, where a new [`FrozenFuture`] finds the state `Empty`,
+///    sets it to `Frozen` and returns `Pending`.
+/// 3. [`RawScope::poll_frozen`] finds the state `Frozen`, and the frozen data is handed to the caller.
+///
+/// The two polls of `FrozenFuture`s happen within the single poll of the future, and are inlined
+/// in its state machine, so they don't cost an additional poll. If the future panics, the state is reset
+/// to `Empty` as the data it pointed to may have been dropped while unwinding.
+pub(crate) enum State<T>
+where
+    T: for<'a> Family<'a>,
+{
+    /// The future is not frozen: it was not polled yet, it is running, or it is waiting on another future.
+    Empty,
+    /// The future froze, and is waiting for the outside to access the data before it is polled again.
+    Frozen(Frozen<T>),
+}
+
+/// Pointer to the data frozen in a scope.
+pub(crate) struct Frozen<T>
+where
+    T: for<'a> Family<'a>,
+{
+    // NonNull differs in variance, which would typically be corrected
+    // with a `PhantomData` marker, however a projection like
+    // `<T as Family<'static>>::Family>` has T invariant already anyway.
+    ptr: NonNull<<T as Family<'static>>::Family>,
+    /// Set when the data was frozen behind a shared reference, in which case it must not be mutated.
+    shared: bool,
+    /// Points to the `Option<T::Resume>` slot of a [`FreezeYieldFuture`], when the data was frozen with
+    /// [`TimeCapsule::freeze_yield`].
+    resume: Option<NonNull<()>>,
+    /// Points to the `T::Tag` of a [`FreezeTaggedFuture`], when the data was frozen with [`TimeCapsule::freeze_tagged`].
+    tag: Option<NonNull<()>>,
+}
+
+/// Lifecycle information about the future of a [`RawScope`].
+pub(crate) struct Status {
+    /// Set when polling the future panicked. A poisoned future must never be polled again.
+    poisoned: bool,
+    /// Set when the future completed. A completed future must never be polled again.
+    completed: bool,
+    /// Set when the future was dropped without being replaced, because [`RawScope::reset`] panicked.
+    /// A vacant future must never be polled nor dropped again. A vacant scope is also poisoned, unless it is cancelled.
+    vacant: bool,
+    /// Set when the future was dropped by [`RawScope::cancel`]. A cancelled scope is also vacant.
+    cancelled: bool,
+    /// Set when the future froze for the first time.
+    has_frozen: bool,
+    /// Set when the future was polled by [`RawScope::prime`] and froze.
+    /// The next poll is skipped, and enters the data that the future froze in that call.
+    primed: bool,
+    /// Set when the scope was created with [`crate::BoxScope::new_abort_on_panic`].
+    /// A panic while polling the future then aborts the process instead of poisoning the scope.
+    abort_on_panic: bool,
+    /// Set when the scope was built with [`crate::PoisonPolicy::OnAnyPanic`].
+    /// A panic while the scope is entered, including in the passed function, then poisons the scope.
+    #[cfg(feature = "std")]
+    poison_on_enter_panic: bool,
+    /// Set when the scope was created with [`crate::PanicPolicy::Poison`]. A panic of the future is then caught
+    /// by [`RawScope::poll_frozen`], that poisons the scope and reports [`ScopeError::Poisoned`].
+    #[cfg(feature = "std")]
+    catch_future_panic: bool,
+    /// Set while the scope is entered, to detect re-entrant calls.
+    entered: bool,
+    /// Incremented each time the future is dropped by [`RawScope::reset`] or [`RawScope::cancel`].
+    generation: u64,
+    /// The number of times the future froze, read by [`TimeCapsule::freeze_index`].
+    freeze_count: u64,
+    /// Set while the future is polled by [`RawScope::poll_frozen`].
+    #[cfg(debug_assertions)]
+    polling: bool,
+}
+
+/// Clears [`Status::entered`] when dropped.
+///
+/// With the `std` feature, it also poisons the scope if it is dropped while unwinding and the scope was built with
+/// [`crate::PoisonPolicy::OnAnyPanic`].
+struct EnterGuard {
+    status: *mut Status,
+    /// Whether the thread was already panicking when the scope was entered, for instance if the scope is entered
+    /// from a destructor while unwinding. The scope is then not poisoned by this panic.
+    #[cfg(feature = "std")]
+    was_panicking: bool,
+}
+
+impl EnterGuard {
+    /// Marks the scope as entered until the returned guard is dropped.
+    ///
+    /// # Panics
+    ///
+    /// - If the scope is already entered.
+    ///
+    /// # Safety
+    ///
+    /// 1. `status` points to the status of a properly aligned, fully initialized `RawScope`,
+    ///    that outlives the returned guard.
+    /// 2. No reference to the status is live, nor created before the returned guard is dropped.
+    #[inline]
+    unsafe fn new(status: *mut Status) -> Self {
+        // SAFETY: precondition (1) + (2)
+        let entered = unsafe { &mut (*status).entered };
+        if *entered {
+            panics::entered_reentrantly();
+        }
+        *entered = true;
+        Self {
+            status,
+            #[cfg(feature = "std")]
+            was_panicking: std::thread::panicking(),
+        }
+    }
 }
 
-// This type is a pointer-type and lifetime-erased equivalent of
-// Option<&'a mut <T as Family<'b>>::Family>.
-//
-// NonNull differs in variance, which would typically be corrected
-// with a `PhantomData` marker, however a projection like
-// `<T as Family<'static>>::Family>` has T invariant already anyway.
-pub(crate) type State<T> = Option<NonNull<<T as Family<'static>>::Family>>;
+impl Drop for EnterGuard {
+    #[inline]
+    fn drop(&mut self) {
+        // SAFETY: precondition (1) + (2) of `EnterGuard::new`
+        unsafe {
+            (*self.status).entered = false;
+            #[cfg(feature = "std")]
+            if (*self.status).poison_on_enter_panic
+                && !self.was_panicking
+                && std::thread::panicking()
+            {
+                (*self.status).poisoned = true;
+            }
+        }
+    }
+}
 
-/// Underlying representation of a scope.
+/// Underlying representation of a scope, to build scope containers other than the ones of this crate.
+///
+/// A `RawScope` holds the future of a scope, the pointer to the data it froze, and the status of the future.
+/// [`crate::BoxScope`], [`crate::StackScope`] and the other scopes of this crate are safe containers around it:
+/// they decide where the `RawScope` lives, and make sure that the safety contracts of its functions hold.
+///
+/// Its functions take a `NonNull<Self>` rather than a reference, as the future accesses the `RawScope` through
+/// pointers while it runs, and no reference to the `RawScope` may be live during that time.
+///
+/// # Lifecycle
+///
+/// 1. Create an empty `RawScope<T, MaybeUninit<F>>` with [`RawScope::new_uninit`], and place it in its final
+///    location, where it must stay until it is dropped.
+/// 2. Cast the pointer to this location to a `*mut RawScope<T, F>`, and initialize the future with [`RawScope::open`].
+/// 3. Enter the scope with [`RawScope::enter`] and its variants, never concurrently, and never while the data passed
+///    to an earlier call is still borrowed.
+/// 4. Drop the future with [`RawScope::drop_in_place`], then release the memory without dropping the `RawScope`
+///    again, for instance by dropping it as the `RawScope<T, MaybeUninit<F>>` it was created as.
+///
+/// # Example
+///
+/// ```
+/// use core::{future::Future, mem::MaybeUninit, ptr::NonNull};
+/// use nolife::{scope, Family, RawScope, SingleFamily, TopScope};
+///
+/// /// A scope container owning its `RawScope` in a `Box`.
+/// struct MyScope<T: for<'a> Family<'a>, F: Future>(NonNull<RawScope<T, F>>);
+///
+/// impl<T: for<'a> Family<'a>, F: Future> MyScope<T, F> {
+///     fn new<S: TopScope<Family = T, Future = F>>(scope: S) -> Self {
+///         let raw_scope = Box::into_raw(Box::new(RawScope::<T, F>::new_uninit()));
+///         let raw_scope: *mut RawScope<T, F> = raw_scope.cast();
+///         // SAFETY: `raw_scope` was created by `new_uninit`, and is heap allocated so it never moves.
+///         unsafe { RawScope::open(raw_scope, scope) };
+///         // SAFETY: `raw_scope` comes from a `Box`, so it is non-null.
+///         Self(unsafe { NonNull::new_unchecked(raw_scope) })
+///     }
+///
+///     fn enter<'borrow, Output, G>(&'borrow mut self, f: G) -> Output
+///     where
+///         G: for<'a> FnOnce(&'borrow mut <T as Family<'a>>::Family) -> Output,
+///     {
+///         // SAFETY: the scope was opened in `new`, never moves, and is borrowed exclusively for `'borrow`.
+///         unsafe { RawScope::enter(self.0, f) }
+///     }
+/// }
+///
+/// impl<T: for<'a> Family<'a>, F: Future> Drop for MyScope<T, F> {
+///     fn drop(&mut self) {
+///         // SAFETY: the scope was opened in `new`, and is not used after this function.
+///         unsafe {
+///             RawScope::drop_in_place(self.0);
+///             drop(Box::from_raw(self.0.as_ptr().cast::<RawScope<T, MaybeUninit<F>>>()));
+///         }
+///     }
+/// }
+///
+/// let mut scope = MyScope::new(scope!(SingleFamily<u32>, {
+///     let mut x = 0;
+///     loop {
+///         freeze!(&mut x);
+///         x += 1;
+///     }
+/// }));
+/// assert_eq!(scope.enter(|x| *x), 0);
+/// assert_eq!(scope.enter(|x| *x), 1);
+/// ```
 // SAFETY: repr C to ensure conversion between RawScope<T, MaybeUninit<F>> and RawScope<T, F>
 // does not rely on unstable memory layout.
 #[repr(C)]
-pub(crate) struct RawScope<T, F: ?Sized>
+pub struct RawScope<T, F: ?Sized>
 where
     T: for<'a> Family<'a>,
 {
     state: State<T>,
+    status: Status,
     active_fut: F,
 }
 
@@ -106,20 +787,51 @@ impl<T, F> RawScope<T, F>
 where
     T: for<'a> Family<'a>,
 {
-    /// Creates a new closed scope.
+    /// Creates a new closed scope, whose future is not initialized yet.
+    ///
+    /// See [`RawScope::open`] to initialize the future.
     pub fn new_uninit() -> RawScope<T, MaybeUninit<F>> {
         RawScope {
-            state: None,
+            state: State::Empty,
+            status: Status {
+                poisoned: false,
+                completed: false,
+                vacant: false,
+                cancelled: false,
+                has_frozen: false,
+                primed: false,
+                abort_on_panic: false,
+                #[cfg(feature = "std")]
+                poison_on_enter_panic: false,
+                #[cfg(feature = "std")]
+                catch_future_panic: false,
+                entered: false,
+                generation: 0,
+                freeze_count: 0,
+                #[cfg(debug_assertions)]
+                polling: false,
+            },
             active_fut: MaybeUninit::uninit(),
         }
     }
 }
 
+impl<T, F: ?Sized> core::fmt::Debug for RawScope<T, F>
+where
+    T: for<'a> Family<'a>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // the status is not read, as the scope may be entered through a pointer
+        f.debug_struct("RawScope").finish_non_exhaustive()
+    }
+}
+
 struct RawScopeFields<T, F: ?Sized>
 where
     T: for<'a> Family<'a>,
 {
     state: *mut State<T>,
+    status: *mut Status,
     active_fut: *mut F,
 }
 impl<T, F: ?Sized> RawScope<T, F>
@@ -135,83 +847,982 @@ where
             // SAFETY: precondition (1)
             state: unsafe { addr_of_mut!((*this).state) },
             // SAFETY: precondition (1)
+            status: unsafe { addr_of_mut!((*this).status) },
+            // SAFETY: precondition (1)
             active_fut: unsafe { addr_of_mut!((*this).active_fut) },
         }
     }
-}
 
-impl<T, F> RawScope<T, F>
-where
-    T: for<'a> Family<'a>,
-    F: Future<Output = Never>,
-{
+    /// Whether the future panicked, in which case the scope can no longer be entered.
+    ///
     /// # Safety
     ///
-    /// 1. `this` points to a properly aligned allocation that can hold a `RawScope<T, F>`, where `active_fut` is not necessarily initialized.
-    /// 2. `this.state` is initialized.
+    /// 1. `this` points to a properly aligned, fully initialized `RawScope<T, F>`.
+    /// 2. No call to [`Self::enter`] or its variants is running for the duration of this call, on another thread or
+    ///    further up the stack, for instance from the function passed to it.
+    pub unsafe fn is_poisoned(this: NonNull<Self>) -> bool {
+        // SAFETY: precondition (1)
+        let RawScopeFields { status, .. } = unsafe { Self::fields(this.as_ptr()) };
+        // SAFETY: precondition (1) + (2)
+        unsafe { (*status).poisoned }
+    }
+
+    /// Makes any panic while polling the future abort the process, instead of poisoning the scope.
     ///
-    /// # Post-condition
+    /// # Safety
     ///
-    /// 1. `this.active_fut` is fully initialized
-    pub(crate) unsafe fn open<S: TopScope<Family = T, Future = F>>(this: *mut Self, scope: S)
-    where
-        T: for<'a> Family<'a>,
-        F: Future<Output = Never>,
-    {
+    /// 1. `this` points to a properly aligned, fully initialized `RawScope<T, F>`.
+    /// 2. No call to [`Self::enter`] or its variants is running for the duration of this call, on another thread or
+    ///    further up the stack, for instance from the function passed to it.
+    pub(crate) unsafe fn set_abort_on_panic(this: NonNull<Self>) {
         // SAFETY: precondition (1)
-        let RawScopeFields { state, active_fut } = unsafe { Self::fields(this) };
+        let RawScopeFields { status, .. } = unsafe { Self::fields(this.as_ptr()) };
+        // SAFETY: precondition (1) + (2)
+        unsafe { (*status).abort_on_panic = true }
+    }
 
-        let time_capsule = TimeCapsule { state };
+    /// Makes any panic while the scope is entered poison the scope, including panics of the passed function.
+    ///
+    /// # Safety
+    ///
+    /// 1. `this` points to a properly aligned, fully initialized `RawScope<T, F>`.
+    /// 2. No call to [`Self::enter`] or its variants is running for the duration of this call, on another thread or
+    ///    further up the stack, for instance from the function passed to it.
+    #[cfg(feature = "std")]
+    pub(crate) unsafe fn set_poison_on_enter_panic(this: NonNull<Self>) {
+        // SAFETY: precondition (1)
+        let RawScopeFields { status, .. } = unsafe { Self::fields(this.as_ptr()) };
+        // SAFETY: precondition (1) + (2)
+        unsafe { (*status).poison_on_enter_panic = true }
+    }
 
-        // SAFETY:
-        // - precondition (1)
-        // - using `scope.run` from the executor
-        unsafe {
-            active_fut.write(scope.run(time_capsule));
-        }
+    /// Makes any panic while polling the future poison the scope without unwinding, reporting [`ScopeError::Poisoned`]
+    /// instead.
+    ///
+    /// # Safety
+    ///
+    /// 1. `this` points to a properly aligned, fully initialized `RawScope<T, F>`.
+    /// 2. No call to [`Self::enter`] or its variants is running for the duration of this call, on another thread or
+    ///    further up the stack, for instance from the function passed to it.
+    #[cfg(feature = "std")]
+    pub(crate) unsafe fn set_catch_future_panic(this: NonNull<Self>) {
+        // SAFETY: precondition (1)
+        let RawScopeFields { status, .. } = unsafe { Self::fields(this.as_ptr()) };
+        // SAFETY: precondition (1) + (2)
+        unsafe { (*status).catch_future_panic = true }
     }
-}
 
-impl<T, F: ?Sized> RawScope<T, F>
-where
-    T: for<'a> Family<'a>,
-    F: Future<Output = Never>,
-{
+    /// Whether the future will never be polled again, because it panicked, completed or was cancelled.
+    ///
     /// # Safety
     ///
     /// 1. `this` points to a properly aligned, fully initialized `RawScope<T, F>`.
-    /// 2. `this` verifies the guarantees of `Pin` (one of its fields is pinned in this function)
-    /// 3. No other exclusive reference to the frozen value. In particular, no concurrent calls to this function.
-    #[allow(unused_unsafe)]
-    pub(crate) unsafe fn enter<'borrow, Output, G>(this: NonNull<Self>, f: G) -> Output
-    where
-        G: for<'a> FnOnce(&'borrow mut <T as Family<'a>>::Family) -> Output,
-    {
+    /// 2. No call to [`Self::enter`] or its variants is running for the duration of this call, on another thread or
+    ///    further up the stack, for instance from the function passed to it.
+    pub unsafe fn is_terminated(this: NonNull<Self>) -> bool {
         // SAFETY: precondition (1)
-        let RawScopeFields { state, active_fut } = unsafe { Self::fields(this.as_ptr()) };
+        let RawScopeFields { status, .. } = unsafe { Self::fields(this.as_ptr()) };
+        // SAFETY: precondition (1) + (2)
+        let status = unsafe { &*status };
+        status.poisoned || status.completed || status.cancelled
+    }
 
-        // SAFETY: precondition (2)
-        let active_fut: Pin<&mut F> = unsafe { Pin::new_unchecked(&mut *active_fut) };
+    /// Poisons the scope, so that its future is never polled again.
+    ///
+    /// # Safety
+    ///
+    /// 1. `this` points to a properly aligned, fully initialized `RawScope<T, F>`.
+    /// 2. No call to [`Self::enter`] or its variants is running for the duration of this call, on another thread or
+    ///    further up the stack, for instance from the function passed to it.
+    #[cfg(feature = "std")]
+    pub(crate) unsafe fn poison(this: NonNull<Self>) {
+        // SAFETY: precondition (1)
+        let RawScopeFields { status, .. } = unsafe { Self::fields(this.as_ptr()) };
+        // SAFETY: precondition (1) + (2)
+        unsafe { (*status).poisoned = true }
+    }
+
+    /// Whether the future is currently frozen, with its data available to the next call to [`Self::enter`].
+    ///
+    /// # Safety
+    ///
+    /// 1. `this` points to a properly aligned, fully initialized `RawScope<T, F>`.
+    /// 2. No call to [`Self::enter`] or its variants is running for the duration of this call, on another thread or
+    ///    further up the stack, for instance from the function passed to it.
+    pub unsafe fn is_active(this: NonNull<Self>) -> bool {
+        // SAFETY: precondition (1)
+        let RawScopeFields { state, .. } = unsafe { Self::fields(this.as_ptr()) };
+        // SAFETY: precondition (1) + (2). Only the pointer is read, not the frozen value.
+        unsafe { matches!(*state, State::Frozen(_)) }
+    }
 
-        match active_fut.poll(&mut core::task::Context::from_waker(&waker::create())) {
-            Poll::Ready(never) => match never {},
-            Poll::Pending => {}
+    /// Whether the future froze when [`Self::prime`] polled it, and was not polled since.
+    ///
+    /// # Safety
+    ///
+    /// 1. `this` points to a properly aligned, fully initialized `RawScope<T, F>`.
+    /// 2. No call to [`Self::enter`] or its variants is running for the duration of this call, on another thread or
+    ///    further up the stack, for instance from the function passed to it.
+    pub(crate) unsafe fn is_primed(this: NonNull<Self>) -> bool {
+        // SAFETY: precondition (1)
+        let RawScopeFields { status, .. } = unsafe { Self::fields(this.as_ptr()) };
+        // SAFETY: precondition (1) + (2)
+        unsafe { (*status).primed }
+    }
+
+    /// How many times the future froze since it was created or last reset, including a primed freeze.
+    ///
+    /// # Safety
+    ///
+    /// 1. `this` points to a properly aligned, fully initialized `RawScope<T, F>`.
+    /// 2. No call to [`Self::enter`] or its variants is running for the duration of this call, on another thread or
+    ///    further up the stack, for instance from the function passed to it.
+    pub(crate) unsafe fn freeze_count(this: NonNull<Self>) -> u64 {
+        // SAFETY: precondition (1)
+        let RawScopeFields { status, .. } = unsafe { Self::fields(this.as_ptr()) };
+        // SAFETY: precondition (1) + (2)
+        unsafe { (*status).freeze_count }
+    }
+
+    /// # Safety
+    ///
+    /// 1. `this` points to a properly aligned, fully initialized `RawScope<T, F>`.
+    /// 2. No call to [`Self::enter`] or its variants is running for the duration of this call, on another thread or
+    ///    further up the stack, for instance from the function passed to it.
+    pub(crate) unsafe fn generation(this: NonNull<Self>) -> u64 {
+        // SAFETY: precondition (1)
+        let RawScopeFields { status, .. } = unsafe { Self::fields(this.as_ptr()) };
+        // SAFETY: precondition (1) + (2)
+        unsafe { (*status).generation }
+    }
+
+    /// The size of the future, in bytes.
+    ///
+    /// # Safety
+    ///
+    /// 1. `this` points to a properly aligned, fully initialized `RawScope<T, F>`.
+    /// 2. No call to [`Self::enter`] or its variants is running for the duration of this call, on another thread or
+    ///    further up the stack, for instance from the function passed to it.
+    pub(crate) unsafe fn future_size(this: NonNull<Self>) -> usize {
+        // SAFETY: precondition (1)
+        let RawScopeFields { active_fut, .. } = unsafe { Self::fields(this.as_ptr()) };
+        // SAFETY: precondition (1) + (2), only the size is read, from the metadata of the pointer when `F` is unsized.
+        mem::size_of_val(unsafe { &*active_fut })
+    }
+
+    /// Whether the future froze at least once.
+    ///
+    /// # Safety
+    ///
+    /// 1. `this` points to a properly aligned, fully initialized `RawScope<T, F>`.
+    /// 2. No call to [`Self::enter`] or its variants is running for the duration of this call, on another thread or
+    ///    further up the stack, for instance from the function passed to it.
+    pub unsafe fn has_frozen(this: NonNull<Self>) -> bool {
+        // SAFETY: precondition (1)
+        let RawScopeFields { status, .. } = unsafe { Self::fields(this.as_ptr()) };
+        // SAFETY: precondition (1) + (2)
+        unsafe { (*status).has_frozen }
+    }
+
+    /// Marks the scope as entered until the returned guard is dropped.
+    ///
+    /// # Panics
+    ///
+    /// - If the scope is already entered, which would otherwise poll the future from within its own `poll`.
+    ///
+    /// # Safety
+    ///
+    /// 1. `this` points to a properly aligned, fully initialized `RawScope<T, F>`, that outlives the returned guard.
+    unsafe fn enter_guard(this: NonNull<Self>) -> EnterGuard {
+        // SAFETY: precondition (1)
+        let RawScopeFields { status, .. } = unsafe { Self::fields(this.as_ptr()) };
+        // SAFETY: precondition (1). The status is only accessed through pointers while the scope is entered.
+        unsafe { EnterGuard::new(status) }
+    }
+
+    /// Calls `f` on the data currently frozen in the scope, without polling the future.
+    ///
+    /// # Errors
+    ///
+    /// - [`ScopeError::NotFrozen`] if the scope is not frozen.
+    /// - [`ScopeError::FrozenShared`] if the scope froze its data with [`TimeCapsule::freeze_ref`].
+    ///
+    /// # Safety
+    ///
+    /// 1. `this` points to a properly aligned, fully initialized `RawScope<T, F>`.
+    /// 2. No other exclusive reference to the frozen value, and the future is not polled before `'borrow` ends.
+    pub(crate) unsafe fn access<'borrow, Output, G>(
+        this: NonNull<Self>,
+        f: G,
+    ) -> Result<Output, ScopeError>
+    where
+        G: for<'a> FnOnce(&'borrow mut <T as Family<'a>>::Family) -> Output,
+    {
+        // SAFETY: precondition (1)
+        let _enter_guard = unsafe { Self::enter_guard(this) };
+        // SAFETY: precondition (1)
+        let RawScopeFields { state, .. } = unsafe { Self::fields(this.as_ptr()) };
+
+        // SAFETY:
+        // - dereferenceable: precondition (1)
+        // - drop: reading a pointer (no drop glue)
+        match unsafe { state.read() } {
+            State::Empty => Err(ScopeError::NotFrozen),
+            State::Frozen(Frozen { shared: true, .. }) => Err(ScopeError::FrozenShared),
+            State::Frozen(Frozen { mut ptr, .. }) => {
+                // SAFETY:
+                // - aliasing: precondition (2) + `mut_ref` cannot escape this function via `f`.
+                // - lifetime: the value is still live as the future is frozen and not polled before `'borrow` ends
+                //   as per precondition (2).
+                let mut_ref = unsafe { ptr.as_mut() };
+                Ok(f(mut_ref))
+            }
         }
+    }
+
+    /// Calls `f` on the data currently frozen in the scope, without polling the future.
+    ///
+    /// # Errors
+    ///
+    /// - [`ScopeError::NotFrozen`] if the scope is not frozen.
+    ///
+    /// # Safety
+    ///
+    /// 1. `this` points to a properly aligned, fully initialized `RawScope<T, F>`.
+    /// 2. No exclusive reference to the frozen value, and the future is not polled before `'borrow` ends.
+    pub(crate) unsafe fn access_ref<'borrow, Output, G>(
+        this: NonNull<Self>,
+        f: G,
+    ) -> Result<Output, ScopeError>
+    where
+        G: for<'a> FnOnce(&'borrow <T as Family<'a>>::Family) -> Output,
+    {
+        // SAFETY: precondition (1)
+        let RawScopeFields { state, .. } = unsafe { Self::fields(this.as_ptr()) };
 
         // SAFETY:
         // - dereferenceable: precondition (1)
-        // - drop: reading a reference (no drop glue)
-        // - aliasing: precondition (3) + `mut_ref` cannot escape this function via `f`
+        // - drop: reading a pointer (no drop glue)
+        match unsafe { state.read() } {
+            State::Empty => Err(ScopeError::NotFrozen),
+            State::Frozen(Frozen { ptr, .. }) => {
+                // SAFETY:
+                // - aliasing: precondition (2) + `shared_ref` cannot escape this function via `f`.
+                // - lifetime: the value is still live as the future is frozen and not polled before `'borrow` ends
+                //   as per precondition (2).
+                let shared_ref = unsafe { ptr.as_ref() };
+                Ok(f(shared_ref))
+            }
+        }
+    }
+
+    /// Drops the future of the scope, unless it is vacant.
+    ///
+    /// # Safety
+    ///
+    /// 1. `this` points to a properly aligned, fully initialized `RawScope<T, F>`.
+    /// 2. `this` is not used afterwards, except to deallocate it.
+    pub unsafe fn drop_in_place(this: NonNull<Self>) {
+        // SAFETY: precondition (1)
+        let RawScopeFields {
+            status, active_fut, ..
+        } = unsafe { Self::fields(this.as_ptr()) };
+        // SAFETY: precondition (1)
+        if unsafe { (*status).vacant } {
+            return;
+        }
+
+        // SAFETY: the future is not vacant, so it is initialized as per precondition (1), and never used again as per (2).
+        //
+        // Only the future is dropped, as the other fields have no drop glue.
+        unsafe { core::ptr::drop_in_place(active_fut) }
+    }
+}
+
+impl<T, F> RawScope<T, F>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+{
+    /// Initializes the future of the scope with the future of `scope`.
+    ///
+    /// # Safety
+    ///
+    /// 1. `this` points to a properly aligned allocation that can hold a `RawScope<T, F>`, where `active_fut` is not necessarily initialized.
+    /// 2. `this.state` and `this.status` are initialized, for instance because `this` was created with
+    ///    [`RawScope::new_uninit`].
+    /// 3. `this` does not move until the future is dropped, as the future points to the `RawScope`.
+    ///
+    /// # Post-condition
+    ///
+    /// 1. `this.active_fut` is fully initialized
+    pub unsafe fn open<S: Scope<Family = T, Future = F>>(this: *mut Self, scope: S)
+    where
+        T: for<'a> Family<'a>,
+        F: Future,
+    {
+        // SAFETY: precondition (1)
+        let RawScopeFields {
+            state,
+            active_fut,
+            status,
+        } = unsafe { Self::fields(this) };
+
+        // SAFETY: precondition (1), `this` points to an allocation so is non-null.
+        let time_capsule = TimeCapsule {
+            state: unsafe { NonNull::new_unchecked(state) },
+            // SAFETY: precondition (1), `this` points to an allocation so is non-null.
+            status: unsafe { NonNull::new_unchecked(status) },
+        };
+
+        // SAFETY:
+        // - precondition (1)
+        // - using `scope.run` from the executor
+        unsafe {
+            active_fut.write(scope.run(time_capsule));
+        }
+    }
+
+    /// Replaces the future of the scope with the future of `scope`, in place.
+    ///
+    /// # Safety
+    ///
+    /// 1. `this` points to a properly aligned, fully initialized `RawScope<T, F>`.
+    /// 2. `this` verifies the guarantees of `Pin` (the future is dropped in place)
+    /// 3. No reference to the frozen value. In particular, no concurrent calls to [`Self::enter`].
+    pub(crate) unsafe fn reset<S: Scope<Family = T, Future = F>>(this: NonNull<Self>, scope: S) {
+        // SAFETY: precondition (1)
+        let RawScopeFields { status, .. } = unsafe { Self::fields(this.as_ptr()) };
+
+        // SAFETY: precondition (1). `status` is only accessed through raw pointers while the guard lives,
+        // including by `Self::drop_future` and by the scope when opening it.
+        let _enter_guard = unsafe { EnterGuard::new(status) };
+
+        // SAFETY: precondition (1)
+        unsafe { (*status).generation = (*status).generation.wrapping_add(1) };
+        // SAFETY: precondition (1)
+        if !unsafe { (*status).vacant } {
+            // SAFETY: forwarding the preconditions, the future is not vacant.
+            unsafe { Self::drop_future(this) };
+        }
+        // the dropped future must not be used, even if opening the scope panics
+        // SAFETY: precondition (1)
+        unsafe { (*status).poisoned = true };
+
+        // SAFETY:
+        // 1. precondition (1), the future was dropped above
+        // 2. precondition (1)
+        unsafe {
+            Self::open(this.as_ptr(), scope);
+        }
+
+        // SAFETY: precondition (1)
+        unsafe {
+            (*status).poisoned = false;
+            (*status).completed = false;
+            (*status).vacant = false;
+            (*status).cancelled = false;
+            (*status).has_frozen = false;
+            (*status).primed = false;
+            (*status).freeze_count = 0;
+        }
+    }
+}
+
+impl<T, F: ?Sized> RawScope<T, F>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+{
+    /// Drops the future in place, without replacing it.
+    ///
+    /// # Safety
+    ///
+    /// 1. `this` points to a properly aligned, fully initialized `RawScope<T, F>`.
+    /// 2. `this` verifies the guarantees of `Pin`, the future is dropped in place.
+    /// 3. No reference to the frozen value.
+    pub(crate) unsafe fn cancel(this: NonNull<Self>) {
+        // SAFETY: precondition (1)
+        let RawScopeFields { status, .. } = unsafe { Self::fields(this.as_ptr()) };
+
+        // SAFETY: precondition (1). `status` is only accessed through raw pointers while the guard lives,
+        // including by `Self::drop_future`.
+        let _enter_guard = unsafe { EnterGuard::new(status) };
+
+        // SAFETY: precondition (1)
+        if unsafe { (*status).vacant } {
+            return;
+        }
+        // SAFETY: precondition (1)
+        unsafe { (*status).generation = (*status).generation.wrapping_add(1) };
+        // SAFETY: forwarding the preconditions, the future is not vacant.
+        unsafe { Self::drop_future(this) };
+        // SAFETY: precondition (1)
+        unsafe { (*status).cancelled = true };
+    }
+
+    /// Drops the future in place, leaving the scope vacant.
+    ///
+    /// The scope is poisoned as well if dropping the future panics, so that the dropped future is never used again.
+    ///
+    /// # Safety
+    ///
+    /// 1. `this` points to a properly aligned, fully initialized `RawScope<T, F>`, whose future is not vacant.
+    /// 2. `this` verifies the guarantees of `Pin`, the future is dropped in place.
+    /// 3. No reference to the frozen value, nor to the status of the scope.
+    unsafe fn drop_future(this: NonNull<Self>) {
+        // SAFETY: precondition (1)
+        let RawScopeFields {
+            state,
+            status,
+            active_fut,
+        } = unsafe { Self::fields(this.as_ptr()) };
+
+        struct VacantGuard {
+            status: *mut Status,
+        }
+        // guard ensures the scope is marked as vacant if dropping the future panics
+        let vacant_guard = VacantGuard { status };
+        impl Drop for VacantGuard {
+            fn drop(&mut self) {
+                // SAFETY: precondition (1) + (3) of `drop_future`, that owns the guard.
+                unsafe {
+                    (*self.status).vacant = true;
+                    (*self.status).poisoned = true;
+                }
+            }
+        }
+
+        // SAFETY:
+        // - precondition (1) + (3). The state is cleared first, so it never points to the dropped future.
+        // - the future is dropped in place as per precondition (2), and marked as vacant if this panics.
+        unsafe {
+            state.write(State::Empty);
+            core::ptr::drop_in_place(active_fut);
+        }
+        mem::forget(vacant_guard); // defuse guard
+
+        // SAFETY: precondition (1) + (3)
+        unsafe { (*status).vacant = true };
+    }
+}
+
+impl<T, F: ?Sized> RawScope<T, F>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+{
+    /// Polls the future once, and calls `f` on the data it froze.
+    ///
+    /// # Panics
+    ///
+    /// - If the future did not freeze its data behind an exclusive reference, see [`Self::try_enter`].
+    ///
+    /// # Safety
+    ///
+    /// 1. `this` points to a properly aligned, fully initialized `RawScope<T, F>`.
+    /// 2. `this` verifies the guarantees of `Pin` (one of its fields is pinned in this function)
+    /// 3. No other exclusive reference to the frozen value. In particular, no concurrent calls to this function.
+    /// 4. For all of `'borrow`, the scope is not polled, entered, reset or dropped, as the reference passed to `f`
+    ///    may be kept that long.
+    pub unsafe fn enter<'borrow, Output, G>(this: NonNull<Self>, f: G) -> Output
+    where
+        G: for<'a> FnOnce(&'borrow mut <T as Family<'a>>::Family) -> Output,
+    {
+        // SAFETY: forwarding the preconditions
+        match unsafe { Self::try_enter(this, f) } {
+            Ok(output) => output,
+            Err(err) => panics::scope_error(err),
+        }
+    }
+
+    /// Polls the future once, and calls `f` on the data it froze.
+    ///
+    /// # Errors
+    ///
+    /// - The [`ScopeError`] preventing the access to the frozen data, `f` is then not called.
+    ///
+    /// # Safety
+    ///
+    /// 1. `this` points to a properly aligned, fully initialized `RawScope<T, F>`.
+    /// 2. `this` verifies the guarantees of `Pin` (one of its fields is pinned in this function)
+    /// 3. No other exclusive reference to the frozen value. In particular, no concurrent calls to this function.
+    /// 4. For all of `'borrow`, the scope is not polled, entered, reset or dropped, as the reference passed to `f`
+    ///    may be kept that long.
+    pub unsafe fn try_enter<'borrow, Output, G>(
+        this: NonNull<Self>,
+        f: G,
+    ) -> Result<Output, ScopeError>
+    where
+        G: for<'a> FnOnce(&'borrow mut <T as Family<'a>>::Family) -> Output,
+    {
+        // SAFETY: forwarding the preconditions
+        match unsafe { Self::enter_or_complete(this, f) }? {
+            Ok(output) => Ok(output),
+            Err(Completed(_)) => Err(ScopeError::Completed),
+        }
+    }
+
+    /// Polls the future once, and calls `f` on the data it froze, or returns the output of the future if it completed.
+    ///
+    /// # Errors
+    ///
+    /// - The [`ScopeError`] preventing the access to the frozen data, `f` is then not called.
+    ///
+    /// # Safety
+    ///
+    /// 1. `this` points to a properly aligned, fully initialized `RawScope<T, F>`.
+    /// 2. `this` verifies the guarantees of `Pin` (one of its fields is pinned in this function)
+    /// 3. No other exclusive reference to the frozen value. In particular, no concurrent calls to this function.
+    /// 4. For all of `'borrow`, the scope is not polled, entered, reset or dropped, as the reference passed to `f`
+    ///    may be kept that long.
+    pub unsafe fn enter_or_complete<'borrow, Output, G>(
+        this: NonNull<Self>,
+        f: G,
+    ) -> Result<Result<Output, Completed<F::Output>>, ScopeError>
+    where
+        G: for<'a> FnOnce(&'borrow mut <T as Family<'a>>::Family) -> Output,
+    {
+        // SAFETY: forwarding the preconditions
+        unsafe { Self::enter_or_complete_with(this, &mut Context::from_waker(&waker::NOOP), f) }
+    }
+
+    /// Same as [`Self::enter_or_complete`], but polls the future with the passed context.
+    ///
+    /// [`ScopeError::NotFrozen`] then means that the future is waiting on another future,
+    /// that will wake the waker of `cx` when the scope can make progress.
+    ///
+    /// # Safety
+    ///
+    /// 1. `this` points to a properly aligned, fully initialized `RawScope<T, F>`.
+    /// 2. `this` verifies the guarantees of `Pin` (one of its fields is pinned in this function)
+    /// 3. No other exclusive reference to the frozen value. In particular, no concurrent calls to this function.
+    /// 4. For all of `'borrow`, the scope is not polled, entered, reset or dropped, as the reference passed to `f`
+    ///    may be kept that long.
+    pub unsafe fn enter_or_complete_with<'borrow, Output, G>(
+        this: NonNull<Self>,
+        cx: &mut Context<'_>,
+        f: G,
+    ) -> Result<Result<Output, Completed<F::Output>>, ScopeError>
+    where
+        G: for<'a> FnOnce(&'borrow mut <T as Family<'a>>::Family) -> Output,
+    {
+        // SAFETY: precondition (1)
+        let _enter_guard = unsafe { Self::enter_guard(this) };
+
+        // SAFETY: forwarding the preconditions
+        let Frozen {
+            mut ptr, shared, ..
+        } = match unsafe { Self::poll_frozen(this, cx) }? {
+            Ok(frozen) => frozen,
+            Err(completed) => return Ok(Err(completed)),
+        };
+        if shared {
+            // SAFETY: precondition (1), the future froze in this call.
+            return Err(unsafe { Self::keep_shared_freeze(this) });
+        }
+
+        // SAFETY:
+        // - aliasing: precondition (3) + `mut_ref` cannot escape this function via `f`.
+        //   The data was frozen behind an exclusive reference, as `shared` is `false`.
         // - lifetime: the value is still live due to the precondition on `Scope::run`,
         //   preventing <https://github.com/dureuill/nolife/issues/8>
-        let mut_ref = unsafe {
-            state
-                .read()
-                .expect("The scope's future did not fill the value")
-                .as_mut()
+        let mut_ref = unsafe { ptr.as_mut() };
+
+        Ok(Ok(f(mut_ref)))
+    }
+
+    /// Polls the future once with the passed context without accessing the data it froze, or returns the output of
+    /// the future if it completed.
+    ///
+    /// Unlike [`Self::enter_or_complete_with`], data frozen behind a shared reference is skipped as well.
+    ///
+    /// # Safety
+    ///
+    /// 1. `this` points to a properly aligned, fully initialized `RawScope<T, F>`.
+    /// 2. `this` verifies the guarantees of `Pin` (one of its fields is pinned in this function)
+    /// 3. No concurrent calls to [`Self::enter`] or [`Self::try_enter`], and no `'borrow` of an earlier call to them
+    ///    is still live.
+    pub(crate) unsafe fn poll_or_complete(
+        this: NonNull<Self>,
+        cx: &mut Context<'_>,
+    ) -> Result<Result<(), Completed<F::Output>>, ScopeError> {
+        // SAFETY: precondition (1)
+        let _enter_guard = unsafe { Self::enter_guard(this) };
+
+        // SAFETY: forwarding the preconditions
+        Ok(unsafe { Self::poll_frozen(this, cx) }?.map(|_| ()))
+    }
+
+    /// # Safety
+    ///
+    /// 1. `this` points to a properly aligned, fully initialized `RawScope<T, F>`.
+    /// 2. `this` verifies the guarantees of `Pin` (one of its fields is pinned in this function)
+    /// 3. No other exclusive reference to the frozen value. In particular, no concurrent calls to this function.
+    /// 4. For all of `'borrow`, the scope is not polled, entered, reset or dropped, as the reference passed to `f`
+    ///    may be kept that long.
+    pub(crate) unsafe fn enter_ref<'borrow, Output, G>(this: NonNull<Self>, f: G) -> Output
+    where
+        G: for<'a> FnOnce(&'borrow <T as Family<'a>>::Family) -> Output,
+    {
+        // SAFETY: precondition (1)
+        let _enter_guard = unsafe { Self::enter_guard(this) };
+
+        // SAFETY: forwarding the preconditions
+        let Frozen { ptr, .. } =
+            match unsafe { Self::poll_frozen(this, &mut Context::from_waker(&waker::NOOP)) } {
+                Ok(Ok(frozen)) => frozen,
+                Ok(Err(Completed(_))) => panics::scope_error(ScopeError::Completed),
+                Err(err) => panics::scope_error(err),
+            };
+
+        // SAFETY:
+        // - aliasing: precondition (3) + `shared_ref` cannot escape this function via `f`.
+        //   Whether the data was frozen behind a shared or an exclusive reference, reading it is fine.
+        // - lifetime: the value is still live due to the precondition on `Scope::run`,
+        //   preventing <https://github.com/dureuill/nolife/issues/8>
+        let shared_ref = unsafe { ptr.as_ref() };
+
+        f(shared_ref)
+    }
+
+    /// # Safety
+    ///
+    /// 1. `this` points to a properly aligned, fully initialized `RawScope<T, F>`.
+    /// 2. `this` verifies the guarantees of `Pin` (one of its fields is pinned in this function)
+    /// 3. No other exclusive reference to the frozen value. In particular, no concurrent calls to this function.
+    /// 4. For all of `'borrow`, the scope is not polled, entered, reset or dropped, as the reference passed to `f`
+    ///    may be kept that long.
+    pub(crate) unsafe fn enter_yield<'borrow, G>(this: NonNull<Self>, f: G)
+    where
+        T: ResumeFamily,
+        G: for<'a> FnOnce(&'borrow mut <T as Family<'a>>::Family) -> T::Resume,
+    {
+        // SAFETY: precondition (1)
+        let _enter_guard = unsafe { Self::enter_guard(this) };
+
+        // SAFETY: forwarding the preconditions
+        let Frozen {
+            mut ptr,
+            shared,
+            resume,
+            ..
+        } = match unsafe { Self::poll_frozen(this, &mut Context::from_waker(&waker::NOOP)) } {
+            Ok(Ok(frozen)) => frozen,
+            Ok(Err(Completed(_))) => panics::scope_error(ScopeError::Completed),
+            Err(err) => panics::scope_error(err),
+        };
+        if shared {
+            // SAFETY: precondition (1), the future froze in this call.
+            panics::scope_error(unsafe { Self::keep_shared_freeze(this) });
+        }
+
+        // SAFETY:
+        // - aliasing: precondition (3) + `mut_ref` cannot escape this function via `f`.
+        //   The data was frozen behind an exclusive reference, as `shared` is `false`.
+        // - lifetime: the value is still live due to the precondition on `Scope::run`,
+        //   preventing <https://github.com/dureuill/nolife/issues/8>
+        let mut_ref = unsafe { ptr.as_mut() };
+
+        let value = f(mut_ref);
+
+        // The value is dropped if the data was not frozen with `TimeCapsule::freeze_yield`.
+        if let Some(resume) = resume {
+            // SAFETY: `resume` points to the slot of the `FreezeYieldFuture` that froze the scope.
+            // This future is pinned and still alive, as the underlying future was not polled since it froze,
+            // and it cannot be accessed concurrently as the underlying future is not being polled.
+            unsafe { *resume.cast::<Option<T::Resume>>().as_ptr() = Some(value) }
+        }
+    }
+
+    /// # Safety
+    ///
+    /// 1. `this` points to a properly aligned, fully initialized `RawScope<T, F>`.
+    /// 2. `this` verifies the guarantees of `Pin` (one of its fields is pinned in this function)
+    /// 3. No other exclusive reference to the frozen value. In particular, no concurrent calls to this function.
+    /// 4. For all of `'borrow`, the scope is not polled, entered, reset or dropped, as the reference passed to `f`
+    ///    may be kept that long.
+    pub(crate) unsafe fn enter_tagged<'borrow, Output, G>(this: NonNull<Self>, f: G) -> Output
+    where
+        T: TagFamily,
+        T::Tag: 'borrow,
+        G: for<'a> FnOnce(&'borrow mut <T as Family<'a>>::Family, &'borrow T::Tag) -> Output,
+    {
+        // SAFETY: precondition (1)
+        let _enter_guard = unsafe { Self::enter_guard(this) };
+
+        // SAFETY: forwarding the preconditions
+        let Frozen {
+            mut ptr,
+            shared,
+            tag,
+            ..
+        } = match unsafe { Self::poll_frozen(this, &mut Context::from_waker(&waker::NOOP)) } {
+            Ok(Ok(frozen)) => frozen,
+            Ok(Err(Completed(_))) => panics::scope_error(ScopeError::Completed),
+            Err(err) => panics::scope_error(err),
         };
+        if shared {
+            // SAFETY: precondition (1), the future froze in this call.
+            panics::scope_error(unsafe { Self::keep_shared_freeze(this) });
+        }
+        let Some(tag) = tag else {
+            panics::frozen_without_tag();
+        };
+
+        // SAFETY:
+        // - aliasing: precondition (3) + `mut_ref` cannot escape this function via `f`.
+        //   The data was frozen behind an exclusive reference, as `shared` is `false`.
+        // - lifetime: the value is still live due to the precondition on `Scope::run`,
+        //   preventing <https://github.com/dureuill/nolife/issues/8>
+        let mut_ref = unsafe { ptr.as_mut() };
+        // SAFETY: `tag` points to the tag of the `FreezeTaggedFuture` that froze the scope.
+        // This future is pinned and still alive, as the underlying future is not polled before `'borrow` ends
+        // by precondition (3), and the tag is only read.
+        let tag = unsafe { tag.cast::<T::Tag>().as_ref() };
 
-        f(mut_ref)
+        f(mut_ref, tag)
+    }
+
+    /// Calls `f` on the data frozen when [`Self::prime`] polled the future, without polling it again.
+    ///
+    /// Returns `None` if the scope is not primed. The scope remains primed.
+    ///
+    /// # Safety
+    ///
+    /// 1. `this` points to a properly aligned, fully initialized `RawScope<T, F>`.
+    /// 2. No other exclusive reference to the frozen value. In particular, no concurrent calls to [`Self::enter`].
+    pub(crate) unsafe fn inspect_primed<Output, G>(this: NonNull<Self>, f: G) -> Option<Output>
+    where
+        G: for<'a> FnOnce(&<T as Family<'a>>::Family) -> Output,
+    {
+        // SAFETY: precondition (1)
+        let _enter_guard = unsafe { Self::enter_guard(this) };
+        // SAFETY: precondition (1)
+        let RawScopeFields { state, status, .. } = unsafe { Self::fields(this.as_ptr()) };
+        // SAFETY: precondition (1)
+        if !unsafe { (*status).primed } {
+            return None;
+        }
+        // SAFETY:
+        // - dereferenceable: precondition (1)
+        // - drop: reading a pointer (no drop glue)
+        let State::Frozen(Frozen { ptr, .. }) = (unsafe { state.read() }) else {
+            return None;
+        };
+        // SAFETY:
+        // - aliasing: precondition (2) + `shared_ref` cannot escape this function via `f`.
+        //   Data frozen behind a shared reference is only read.
+        // - lifetime: the future froze when `Self::prime` polled it, and was not polled since,
+        //   so the value is still live.
+        let shared_ref = unsafe { ptr.as_ref() };
+        Some(f(shared_ref))
+    }
+
+    /// Polls the future once with the passed context, so that the next poll enters the data it froze without polling it.
+    ///
+    /// If the future doesn't freeze in this poll, the next poll polls it as usual, and the error is returned.
+    /// If the future completes in this poll, its output is dropped, and [`ScopeError::Completed`] is returned.
+    ///
+    /// # Safety
+    ///
+    /// 1. `this` points to a properly aligned, fully initialized `RawScope<T, F>`.
+    /// 2. `this` verifies the guarantees of `Pin` (one of its fields is pinned in this function)
+    /// 3. No concurrent calls to [`Self::enter`] or [`Self::try_enter`], and no `'borrow` of an earlier call to them
+    ///    is still live.
+    pub(crate) unsafe fn prime(
+        this: NonNull<Self>,
+        cx: &mut Context<'_>,
+    ) -> Result<(), ScopeError> {
+        // SAFETY: precondition (1)
+        let _enter_guard = unsafe { Self::enter_guard(this) };
+
+        // SAFETY: forwarding the preconditions
+        match unsafe { Self::poll_frozen(this, cx) } {
+            Ok(Ok(_)) => {
+                // SAFETY: precondition (1)
+                let RawScopeFields { status, .. } = unsafe { Self::fields(this.as_ptr()) };
+                // SAFETY: precondition (1)
+                unsafe { (*status).primed = true };
+                Ok(())
+            }
+            Ok(Err(Completed(_))) => Err(ScopeError::Completed),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Polls the future once with the passed context, as [`Self::prime`], skipping the data it froze in a previous
+    /// call to [`Self::prime`] if it was not entered since.
+    ///
+    /// # Safety
+    ///
+    /// 1. `this` points to a properly aligned, fully initialized `RawScope<T, F>`.
+    /// 2. `this` verifies the guarantees of `Pin` (one of its fields is pinned in this function)
+    /// 3. No concurrent calls to [`Self::enter`] or [`Self::try_enter`], and no `'borrow` of an earlier call to them
+    ///    is still live.
+    pub(crate) unsafe fn step(this: NonNull<Self>, cx: &mut Context<'_>) -> Result<(), ScopeError> {
+        // SAFETY: precondition (1)
+        let RawScopeFields { status, .. } = unsafe { Self::fields(this.as_ptr()) };
+        // SAFETY: precondition (1) + (3), the future is resumed past the skipped freeze by the next poll.
+        unsafe { (*status).primed = false };
+        // SAFETY: forwarding the preconditions
+        unsafe { Self::prime(this, cx) }
+    }
+
+    /// Keeps the data that the future froze behind a shared reference for the next call, as [`Self::prime`] does,
+    /// so that rejecting a mutable access to it does not skip the freeze.
+    ///
+    /// # Safety
+    ///
+    /// 1. `this` points to a properly aligned, fully initialized `RawScope<T, F>`.
+    /// 2. The future froze in the current call to [`Self::poll_frozen`], and was not polled since.
+    unsafe fn keep_shared_freeze(this: NonNull<Self>) -> ScopeError {
+        // SAFETY: precondition (1)
+        let RawScopeFields { status, .. } = unsafe { Self::fields(this.as_ptr()) };
+        // SAFETY: precondition (1) + (2), the next poll enters the data frozen in this one.
+        unsafe { (*status).primed = true };
+        ScopeError::FrozenShared
+    }
+
+    /// Polls the future once with the passed context, returning the pointer to the data it froze.
+    ///
+    /// # Safety
+    ///
+    /// 1. `this` points to a properly aligned, fully initialized `RawScope<T, F>`.
+    /// 2. `this` verifies the guarantees of `Pin` (one of its fields is pinned in this function)
+    #[allow(unused_unsafe)]
+    unsafe fn poll_frozen(
+        this: NonNull<Self>,
+        cx: &mut Context<'_>,
+    ) -> Result<Result<Frozen<T>, Completed<F::Output>>, ScopeError> {
+        // SAFETY: precondition (1)
+        let RawScopeFields {
+            state,
+            status,
+            active_fut,
+        } = unsafe { Self::fields(this.as_ptr()) };
+
+        // SAFETY: precondition (1)
+        if unsafe { (*status).cancelled } {
+            return Err(ScopeError::Cancelled);
+        }
+        // SAFETY: precondition (1)
+        if unsafe { (*status).poisoned } {
+            return Err(ScopeError::Poisoned);
+        }
+        // SAFETY: precondition (1)
+        if unsafe { (*status).completed } {
+            return Err(ScopeError::Completed);
+        }
+
+        // SAFETY: precondition (1)
+        if unsafe { (*status).primed } {
+            // SAFETY: precondition (1)
+            unsafe { (*status).primed = false };
+            // the future froze when `Self::prime` polled it, and was not polled since.
+            // SAFETY:
+            // - dereferenceable: precondition (1)
+            // - drop: reading a pointer (no drop glue)
+            if let State::Frozen(frozen) = unsafe { state.read() } {
+                return Ok(Ok(frozen));
+            }
+        }
+
+        // SAFETY: precondition (2)
+        let active_fut: Pin<&mut F> = unsafe { Pin::new_unchecked(&mut *active_fut) };
+
+        struct PoisonGuard<T>
+        where
+            T: for<'a> Family<'a>,
+        {
+            state: *mut State<T>,
+            status: *mut Status,
+        }
+        // guard ensures the scope is poisoned if polling the future panics
+        let poison_guard = PoisonGuard::<T> { state, status };
+        impl<T> Drop for PoisonGuard<T>
+        where
+            T: for<'a> Family<'a>,
+        {
+            fn drop(&mut self) {
+                // SAFETY: defuse below makes sure this only happens on panic,
+                // in this case, the pointer still points to the state of the `RawScope`
+                // by precondition (1).
+                //
+                // The locals of the future were dropped while unwinding, so we make
+                // sure that no reference to them can remain in the state.
+                unsafe {
+                    if (*self.status).abort_on_panic {
+                        abort_while_unwinding();
+                    }
+                    self.state.write(State::Empty);
+                    (*self.status).poisoned = true;
+                    #[cfg(debug_assertions)]
+                    {
+                        (*self.status).polling = false;
+                    }
+                }
+            }
+        }
+
+        #[cfg(debug_assertions)]
+        // SAFETY: precondition (1)
+        unsafe {
+            (*status).polling = true;
+        }
+
+        #[cfg(feature = "std")]
+        // SAFETY: precondition (1)
+        let poll = if unsafe { (*status).catch_future_panic } {
+            match std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| active_fut.poll(cx))) {
+                Ok(poll) => poll,
+                Err(payload) => {
+                    // the guard poisons the scope, as if the panic unwound through it.
+                    drop(poison_guard);
+                    drop(payload);
+                    return Err(ScopeError::Poisoned);
+                }
+            }
+        } else {
+            active_fut.poll(cx)
+        };
+        #[cfg(not(feature = "std"))]
+        let poll = active_fut.poll(cx);
+
+        mem::forget(poison_guard); // defuse guard
+
+        #[cfg(debug_assertions)]
+        // SAFETY: precondition (1)
+        unsafe {
+            (*status).polling = false;
+        }
+
+        if let Poll::Ready(output) = poll {
+            // The future completed without being frozen during this poll. Its state only holds a pointer if it dropped
+            // a `FrozenFuture` that it polled once, which `scope!` prevents. Such a pointer would refer to locals
+            // of the completed future, so it is cleared rather than trusted, and the state is never read again.
+            // SAFETY: precondition (1)
+            unsafe {
+                state.write(State::Empty);
+                (*status).completed = true;
+            }
+            return Ok(Err(Completed(output)));
+        }
+
+        // SAFETY:
+        // - dereferenceable: precondition (1)
+        // - drop: reading a pointer (no drop glue)
+        match unsafe { state.read() } {
+            State::Frozen(frozen) => {
+                // SAFETY: precondition (1)
+                unsafe {
+                    (*status).has_frozen = true;
+                    (*status).freeze_count += 1;
+                }
+                Ok(Ok(frozen))
+            }
+            State::Empty => Err(ScopeError::NotFrozen),
+        }
     }
 }
 
@@ -225,21 +1836,71 @@ where
         mut self: core::pin::Pin<&mut Self>,
         _cx: &mut core::task::Context<'_>,
     ) -> Poll<Self::Output> {
+        // A time capsule can only be moved to the future of another scope by violating the safety precondition
+        // of `scope::new_scope`, for instance by smuggling it through a shared cell. Its state would then be written
+        // while its scope is not polled. This is checked in debug builds, on a best-effort basis:
+        // a time capsule that outlived its scope cannot be detected.
+        #[cfg(debug_assertions)]
+        // SAFETY: the status belongs to the scope of the time capsule, that is alive while its future is polled.
+        // It is only read.
+        if !unsafe { (*self.status.as_ptr()).polling } {
+            panics::foreign_time_capsule();
+        }
+
         // SAFETY:
         // - state was set to a valid value in [`TimeCapsule::freeze`]
         // - the value is still 'live', due to the lifetime in `FrozenFuture`
-        let state: &mut State<T> = unsafe { &mut *self.state };
-        if state.is_none() {
-            let mut_ref = self
-                .mut_ref
-                .take()
-                .expect("poll called several times on the same future");
-
-            *state = Some(mut_ref);
-            Poll::Pending
-        } else {
-            *state = None;
-            Poll::Ready(())
+        let state: &mut State<T> = unsafe { self.state.as_mut() };
+        match state {
+            State::Empty => {
+                let Some(ptr) = self.ptr.take() else {
+                    panics::frozen_future_polled_after_completion()
+                };
+
+                *state = State::Frozen(Frozen {
+                    ptr,
+                    shared: self.shared,
+                    resume: None,
+                    tag: None,
+                });
+                Poll::Pending
+            }
+            State::Frozen(_) => {
+                // Only the future that froze the scope can resume it. Otherwise, several `FrozenFuture`s are
+                // polled at the same time, for instance with a `join`, and this one would complete without
+                // ever freezing the scope.
+                #[cfg(debug_assertions)]
+                if self.ptr.is_some() {
+                    panics::concurrent_freezes();
+                }
+                *state = State::Empty;
+                Poll::Ready(())
+            }
         }
     }
 }
+
+/// Erases the lifetime of a pointer to frozen data, so that it can be stored in the [`State`] of a scope.
+///
+/// Contrary to [`NonNull::cast`], which only accepts sized pointees, this preserves the metadata of the pointer
+/// when the family is unsized, such as the length of a `str` or the vtable of a `dyn Trait`.
+fn erase_lifetime<'b, T>(
+    ptr: NonNull<<T as Family<'b>>::Family>,
+) -> NonNull<<T as Family<'static>>::Family>
+where
+    T: for<'a> Family<'a>,
+{
+    // SAFETY: `T` implements `Family` for all lifetimes, so the two pointees only differ by their lifetime,
+    // and the pointers have the same layout and metadata.
+    unsafe { mem::transmute(ptr) }
+}
+
+/// Aborts the process. Must only be called while unwinding.
+#[cold]
+fn abort_while_unwinding() -> ! {
+    #[cfg(feature = "std")]
+    std::process::abort();
+    // panicking while unwinding aborts the process
+    #[cfg(not(feature = "std"))]
+    panics::panicked_in_abort_scope();
+}
@@ -0,0 +1,161 @@
+use alloc::rc::Rc;
+use core::{
+    cell::{RefCell, RefMut},
+    future::Future,
+};
+
+use crate::{panics, BoxScope, Family, Never, ScopeError, TopScope};
+
+/// A scope tied to a Box, with cheaply clonable handles.
+///
+/// All clones of a `RcScope` refer to the same scope, and can enter it through a shared reference.
+/// This is useful to share the frozen state between several components of a single thread, such as in an event loop.
+///
+/// The underlying [`BoxScope`] is protected by a [`RefCell`]: entering the scope while it is already entered
+/// through another handle panics, much like borrowing a [`RefCell`] that is already mutably borrowed.
+pub struct RcScope<T, F: ?Sized = dyn Future<Output = Never> + 'static>(
+    Rc<RefCell<BoxScope<T, F>>>,
+)
+where
+    T: for<'a> Family<'a>,
+    F: Future<Output = Never>;
+
+impl<T, F: ?Sized> Clone for RcScope<T, F>
+where
+    T: for<'a> Family<'a>,
+    F: Future<Output = Never>,
+{
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T, F: ?Sized> core::fmt::Debug for RcScope<T, F>
+where
+    T: for<'a> Family<'a>,
+    F: Future<Output = Never>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // `RefCell` prints a placeholder if the scope is currently entered.
+        f.debug_tuple("RcScope").field(&self.0).finish()
+    }
+}
+
+impl<T> RcScope<T>
+where
+    T: for<'a> Family<'a>,
+{
+    /// Ties the passed scope to the heap.
+    ///
+    /// This function erased the `Future` generic type of the [`TopScope`], at the cost
+    /// of using a dynamic function call to poll the future.
+    ///
+    /// If the `Future` generic type can be inferred, it can be more efficient to use [`RcScope::new`].
+    ///
+    /// # Panics
+    ///
+    /// - If `scope` panics.
+    pub fn new_dyn<S: TopScope<Family = T>>(scope: S) -> Self
+    where
+        S::Future: 'static,
+    {
+        Self(Rc::new(RefCell::new(BoxScope::new_dyn(scope))))
+    }
+}
+
+impl<T, F> RcScope<T, F>
+where
+    T: for<'a> Family<'a>,
+    F: Future<Output = Never>,
+{
+    /// Ties the passed scope to the heap.
+    ///
+    /// This function retains the `Future` generic type from the [`TopScope`].
+    /// To store the [`RcScope`] in a struct, it can be easier to use [`RcScope::new_dyn`].
+    ///
+    /// # Panics
+    ///
+    /// - If `scope` panics.
+    pub fn new<S: TopScope<Family = T, Future = F>>(scope: S) -> Self {
+        Self(Rc::new(RefCell::new(BoxScope::new(scope))))
+    }
+}
+
+impl<T, F: ?Sized> RcScope<T, F>
+where
+    T: for<'a> Family<'a>,
+    F: Future<Output = Never>,
+{
+    fn borrow_mut(&self) -> RefMut<'_, BoxScope<T, F>> {
+        match self.0.try_borrow_mut() {
+            Ok(scope) => scope,
+            Err(_) => panics::rc_entered_reentrantly(),
+        }
+    }
+
+    /// Enters the scope, making it possible to access the data frozen inside of the scope.
+    ///
+    /// # Panics
+    ///
+    /// - If the scope is already entered, typically from the function passed to [`Self::enter`] through another handle.
+    /// - If the passed function panics.
+    /// - If the underlying future panics.
+    /// - If the underlying future panicked in a previous call to [`Self::enter`] or [`Self::try_enter`].
+    /// - If the underlying future awaits for a future other than the [`crate::FrozenFuture`].
+    pub fn enter<Output, G>(&self, f: G) -> Output
+    where
+        G: for<'a> FnOnce(&mut <T as Family<'a>>::Family) -> Output,
+    {
+        self.borrow_mut().enter(f)
+    }
+
+    /// Enters the scope, making it possible to access the data frozen inside of the scope.
+    ///
+    /// See [`BoxScope::try_enter`] for details.
+    ///
+    /// # Errors
+    ///
+    /// - [`ScopeError::Poisoned`] if the underlying future panicked in a previous call.
+    /// - [`ScopeError::NotFrozen`] if the underlying future awaits for a future other than the [`crate::FrozenFuture`].
+    /// - [`ScopeError::FrozenShared`] if the underlying future froze its data with [`crate::TimeCapsule::freeze_ref`].
+    ///
+    /// # Panics
+    ///
+    /// - If the scope is already entered, typically from the function passed to [`Self::enter`] through another handle.
+    /// - If the passed function panics.
+    /// - If the underlying future panics. The scope is then poisoned.
+    pub fn try_enter<Output, G>(&self, f: G) -> Result<Output, ScopeError>
+    where
+        G: for<'a> FnOnce(&mut <T as Family<'a>>::Family) -> Output,
+    {
+        self.borrow_mut().try_enter(f)
+    }
+
+    /// Whether the underlying future panicked, in which case the scope can no longer be entered.
+    ///
+    /// # Panics
+    ///
+    /// - If the scope is currently entered, typically from the function passed to [`Self::enter`] through another
+    ///   handle. This is the only case where querying the scope panics.
+    pub fn is_poisoned(&self) -> bool {
+        match self.0.try_borrow() {
+            Ok(scope) => scope.is_poisoned(),
+            Err(_) => panics::rc_queried_while_entered(),
+        }
+    }
+
+    /// Whether `self` and `other` are handles to the same scope.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<T, F: ?Sized> From<BoxScope<T, F>> for RcScope<T, F>
+where
+    T: for<'a> Family<'a>,
+    F: Future<Output = Never>,
+{
+    fn from(scope: BoxScope<T, F>) -> Self {
+        Self(Rc::new(RefCell::new(scope)))
+    }
+}
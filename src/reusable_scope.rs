@@ -0,0 +1,151 @@
+use core::future::Future;
+
+use crate::{BoxScope, Family, ScopeError, TopScope};
+
+/// A [`BoxScope`] that retains the producer of its scope, so that it can be reset without passing a new scope.
+///
+/// Created with [`BoxScope::new_reusable`].
+pub struct ReusableScope<T, F, M>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+{
+    scope: BoxScope<T, F>,
+    make: M,
+}
+
+impl<T, F> BoxScope<T, F>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+{
+    /// Ties the scope returned by `make` to the heap, retaining `make` to recreate the scope when it is reset.
+    ///
+    /// `make` is called once now, and once per call to [`ReusableScope::reset`]. Each call must return a fresh scope:
+    /// the state captured by the returned scope is only seen by one instance of the future, whereas the state
+    /// captured by `make` itself persists across resets, and can be cloned or updated by `make` to
+    /// initialize each new scope.
+    ///
+    /// # Panics
+    ///
+    /// - If `make` or the scope it returned panics.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, SingleFamily};
+    ///
+    /// let mut generation = 0;
+    /// let mut scope = BoxScope::<SingleFamily<u32>, _>::new_reusable(move || {
+    ///     generation += 1;
+    ///     let start = generation * 10;
+    ///     scope!({
+    ///         let mut x: u32 = start;
+    ///         loop {
+    ///             freeze!(&mut x);
+    ///             x += 1;
+    ///         }
+    ///     })
+    /// });
+    ///
+    /// assert_eq!(scope.enter(|x| *x), 10);
+    /// assert_eq!(scope.enter(|x| *x), 11);
+    ///
+    /// scope.reset();
+    /// assert_eq!(scope.enter(|x| *x), 20);
+    /// ```
+    pub fn new_reusable<M, S>(mut make: M) -> ReusableScope<T, F, M>
+    where
+        M: FnMut() -> S,
+        S: TopScope<Family = T, Future = F>,
+    {
+        ReusableScope {
+            scope: BoxScope::new(make()),
+            make,
+        }
+    }
+}
+
+impl<T, F, M, S> ReusableScope<T, F, M>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+    M: FnMut() -> S,
+    S: TopScope<Family = T, Future = F>,
+{
+    /// Drops the current future and replaces it with a fresh one, created from a new scope returned by the producer.
+    ///
+    /// See [`BoxScope::reset`].
+    ///
+    /// # Panics
+    ///
+    /// - If dropping the current future panics.
+    /// - If the producer or the scope it returned panics.
+    ///
+    /// If dropping the current future or the scope panics, the scope is left poisoned, and can still be reset.
+    pub fn reset(&mut self) {
+        let scope = (self.make)();
+        self.scope.reset(scope)
+    }
+}
+
+impl<T, F, M> ReusableScope<T, F, M>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+{
+    /// Enters the scope, making it possible to access the data frozen inside of the scope.
+    ///
+    /// # Panics
+    ///
+    /// - Same as [`BoxScope::enter`].
+    pub fn enter<'borrow, Output, G>(&'borrow mut self, f: G) -> Output
+    where
+        G: for<'a> FnOnce(&'borrow mut <T as Family<'a>>::Family) -> Output,
+    {
+        self.scope.enter(f)
+    }
+
+    /// Enters the scope, making it possible to access the data frozen inside of the scope.
+    ///
+    /// # Errors
+    ///
+    /// - Same as [`BoxScope::try_enter`].
+    ///
+    /// # Panics
+    ///
+    /// - Same as [`BoxScope::try_enter`].
+    pub fn try_enter<'borrow, Output, G>(&'borrow mut self, f: G) -> Result<Output, ScopeError>
+    where
+        G: for<'a> FnOnce(&'borrow mut <T as Family<'a>>::Family) -> Output,
+    {
+        self.scope.try_enter(f)
+    }
+
+    /// Returns the underlying scope, dropping the producer.
+    pub fn into_inner(self) -> BoxScope<T, F> {
+        self.scope
+    }
+
+    /// Returns a reference to the underlying scope.
+    pub fn get_ref(&self) -> &BoxScope<T, F> {
+        &self.scope
+    }
+
+    /// Returns a mutable reference to the underlying scope.
+    pub fn get_mut(&mut self) -> &mut BoxScope<T, F> {
+        &mut self.scope
+    }
+}
+
+impl<T, F, M> core::fmt::Debug for ReusableScope<T, F, M>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ReusableScope")
+            .field("scope", &self.scope)
+            .finish_non_exhaustive()
+    }
+}
@@ -90,12 +90,22 @@ where
     Wrapper(producer, PhantomData)
 }
 
+#[doc(hidden)]
+/// Fixes the family of a scope, for `scope!(MyFamily, { ... })`.
+pub fn with_family<T, S>(scope: S) -> S
+where
+    T: for<'a> Family<'a>,
+    S: Scope<Family = T>,
+{
+    scope
+}
+
 /// A macro to open a scope that can be frozen in time.
 ///
 /// You can write code like you normally would in that scope, but you get 3 additional superpowers:
 ///
 /// 1. `freeze!(&mut x)`: interrupts execution of the scope until the next call to [`crate::BoxScope::enter`],
-///   that will resume execution. The passed `&mut x` will be available to the next call to [`crate::BoxScope::enter`].
+///    that will resume execution. The passed `&mut x` will be available to the next call to [`crate::BoxScope::enter`].
 /// 2. `freeze_forever!(&mut x)`: interrupts execution of the scope forever.
 ///    All future calls to [`crate::BoxScope::enter`] will have access to the passed `&mut x`.
 /// 3. `subscope!(some_subscope(...))`: execute an expression that can be another function returning a `scope!` itself.
@@ -158,12 +168,32 @@ where
 /// scope.enter(|x| assert_eq!(*x, 16));
 /// ```
 ///
+/// # Annotating the family
+///
+/// `scope!(MyFamily, { ... })` fixes the `Family` of the scope to `MyFamily`. The family then
+/// flows from the scope to the functions it is passed to, so that they don't need a turbofish.
+///
+/// ```
+/// use nolife::{scope, BoxScope, MutFamily};
+///
+/// let mut scope = BoxScope::new(scope!(MutFamily<String>, {
+///     let mut greeting = String::from("Hello");
+///     freeze_forever!(&mut &mut greeting)
+/// }));
+///
+/// scope.enter(|greeting| greeting.push('!'));
+/// assert_eq!(scope.enter(|greeting| greeting.clone()), "Hello!");
+/// ```
+///
 /// # Panics
 ///
 /// The block passed to `scope` is technically an `async` block, but trying to `await` a future in this block
 /// will always result in a panic.
 #[macro_export]
 macro_rules! scope {
+    ($family:ty, $b:block) => {
+        $crate::scope::with_family::<$family, _>($crate::scope!($b))
+    };
     ($b:block) => {
         match move |#[allow(unused_variables, unused_mut)] mut time_capsule| async move {
             'check_top: {
@@ -185,6 +215,77 @@ macro_rules! scope {
                         $crate::TimeCapsule::freeze(&mut time_capsule, $e).await
                     }
                 }
+                /// `freeze_ref!(&x)` interrupts execution of the scope, making `&x` available to the next call
+                /// to [`nolife::BoxScope::enter_ref`].
+                ///
+                /// Execution will resume after a call to [`nolife::BoxScope::enter_ref`].
+                #[allow(unused_macros)]
+                macro_rules! freeze_ref {
+                    ($e:expr) => {
+                        #[allow(unreachable_code)]
+                        if false {
+                            break 'check_top (loop {});
+                        }
+                        $crate::TimeCapsule::freeze_ref(&mut time_capsule, $e).await
+                    }
+                }
+                /// `freeze_yield!(&mut x)` interrupts execution of the scope, making `&mut x` available to the next call
+                /// to [`nolife::BoxScope::enter_yield`].
+                ///
+                /// Execution will resume after a call to [`nolife::BoxScope::enter_yield`], with the value returned
+                /// by its closure.
+                #[allow(unused_macros)]
+                macro_rules! freeze_yield {
+                    ($e:expr) => {{
+                        #[allow(unreachable_code)]
+                        if false {
+                            break 'check_top (loop {});
+                        }
+                        $crate::TimeCapsule::freeze_yield(&mut time_capsule, $e).await
+                    }}
+                }
+                /// `freeze_tagged!(&mut x, tag)` interrupts execution of the scope, making `&mut x` and `tag` available to the next
+                /// call to [`nolife::BoxScope::enter_tagged`].
+                ///
+                /// Execution will resume after a call to [`nolife::BoxScope::enter`] or one of its variants.
+                #[allow(unused_macros)]
+                macro_rules! freeze_tagged {
+                    ($e:expr, $tag:expr) => {
+                        #[allow(unreachable_code)]
+                        if false {
+                            break 'check_top (loop {});
+                        }
+                        $crate::TimeCapsule::freeze_tagged(&mut time_capsule, $e, $tag).await
+                    }
+                }
+                /// `freeze2!(&mut x)` interrupts execution of a scope of family [`nolife::TwoLifetimes`], making `&mut x`
+                /// available to the next call to [`nolife::BoxScope::enter`] through [`nolife::Frozen2`].
+                ///
+                /// Execution will resume after a call to [`nolife::BoxScope::enter`].
+                #[allow(unused_macros)]
+                macro_rules! freeze2 {
+                    ($e:expr) => {
+                        #[allow(unreachable_code)]
+                        if false {
+                            break 'check_top (loop {});
+                        }
+                        $crate::TimeCapsule::freeze2(&mut time_capsule, $e).await
+                    }
+                }
+                /// `freeze_unit!()` interrupts execution of a scope of family [`nolife::UnitFamily`], without making
+                /// any data available to the next call to [`nolife::BoxScope::enter`].
+                ///
+                /// Execution will resume after a call to [`nolife::BoxScope::enter`].
+                #[allow(unused_macros)]
+                macro_rules! freeze_unit {
+                    () => {
+                        #[allow(unreachable_code)]
+                        if false {
+                            break 'check_top (loop {});
+                        }
+                        $crate::TimeCapsule::freeze_unit(&mut time_capsule).await
+                    }
+                }
                 /// `freeze_forever!(&mut x)` stops execution of the scope forever, making `&mut x` available to all future calls
                 /// to [`$crate::BoxScope::enter`].
                 ///
@@ -199,6 +300,101 @@ macro_rules! scope {
                         $crate::TimeCapsule::freeze_forever(&mut time_capsule, $e).await}
                     }
                 }
+                /// `freeze_loop!(&mut x, |x| ...)` freezes the scope forever, calling the passed closure on `&mut x` after
+                /// each call to [`nolife::BoxScope::enter`], before freezing again.
+                ///
+                /// Execution will never continue past this macro.
+                #[allow(unused_macros)]
+                macro_rules! freeze_loop {
+                    ($e:expr, $step:expr) => {{
+                        #[allow(unreachable_code)]
+                        if false {
+                            break 'check_top (loop {});
+                        }
+                        $crate::TimeCapsule::freeze_loop(&mut time_capsule, $e, $step).await}
+                    }
+                }
+                /// `freeze_owned!(x)` interrupts execution of the scope, lending `x` to the next call to
+                /// [`nolife::BoxScope::enter`], and evaluates to `x` once execution resumes.
+                #[allow(unused_macros)]
+                macro_rules! freeze_owned {
+                    ($e:expr) => {{
+                        #[allow(unreachable_code)]
+                        if false {
+                            break 'check_top (loop {});
+                        }
+                        $crate::TimeCapsule::freeze_owned(&mut time_capsule, $e).await
+                    }}
+                }
+                /// `freeze_windows!(buf, window_len)` freezes the scope once for each window of `window_len` elements of `buf`,
+                /// making the current window available to the next call to [`nolife::BoxScope::enter`].
+                ///
+                /// Execution will continue after the scope was entered with the final window.
+                #[allow(unused_macros)]
+                macro_rules! freeze_windows {
+                    ($buf:expr, $window_len:expr) => {
+                        #[allow(unreachable_code)]
+                        if false {
+                            break 'check_top (loop {});
+                        }
+                        $crate::TimeCapsule::freeze_windows(&mut time_capsule, $buf, $window_len).await
+                    }
+                }
+                /// `freeze_left!(x)` interrupts execution of the scope, making `Either::Left(x)` available to the next call
+                /// to [`nolife::BoxScope::enter`].
+                ///
+                /// Execution will resume after a call to [`nolife::BoxScope::enter`].
+                #[allow(unused_macros)]
+                macro_rules! freeze_left {
+                    ($e:expr) => {
+                        #[allow(unreachable_code)]
+                        if false {
+                            break 'check_top (loop {});
+                        }
+                        $crate::TimeCapsule::freeze_left(&mut time_capsule, $e).await
+                    }
+                }
+                /// `freeze_right!(x)` interrupts execution of the scope, making `Either::Right(x)` available to the next
+                /// call to [`nolife::BoxScope::enter`].
+                ///
+                /// Execution will resume after a call to [`nolife::BoxScope::enter`].
+                #[allow(unused_macros)]
+                macro_rules! freeze_right {
+                    ($e:expr) => {
+                        #[allow(unreachable_code)]
+                        if false {
+                            break 'check_top (loop {});
+                        }
+                        $crate::TimeCapsule::freeze_right(&mut time_capsule, $e).await
+                    }
+                }
+                /// `freeze_static!(x)` interrupts execution of the scope, lending the `'static` mutable reference `x`
+                /// to the next call to [`nolife::BoxScope::enter`], and evaluates to `x` when execution resumes.
+                ///
+                /// See [`nolife::TimeCapsule::freeze_static`].
+                #[allow(unused_macros)]
+                macro_rules! freeze_static {
+                    ($e:expr) => {{
+                        #[allow(unreachable_code)]
+                        if false {
+                            break 'check_top (loop {});
+                        }
+                        $crate::TimeCapsule::freeze_static(&mut time_capsule, $e).await
+                    }}
+                }
+                /// `freeze_index!()` evaluates to the number of times the scope froze so far.
+                ///
+                /// See [`nolife::TimeCapsule::freeze_index`].
+                #[allow(unused_macros)]
+                macro_rules! freeze_index {
+                    () => {{
+                        #[allow(unreachable_code)]
+                        if false {
+                            break 'check_top (loop {});
+                        }
+                        $crate::TimeCapsule::freeze_index(&time_capsule)
+                    }}
+                }
                 /// `sub_scope(some_scope)` runs the sub-scope `some_scope` to completion before continuing execution of the current scope,
                 /// yielding the output value of the sub-scope.
                 ///
@@ -220,3 +416,43 @@ macro_rules! scope {
         } { scope => unsafe { $crate::scope::new_scope(scope) } }
     };
 }
+
+/// Ties a [`scope!`] to the heap, returning a ready-to-enter [`crate::BoxScope`] of the given family.
+///
+/// `box_scope!(MyFamily, { ... })` is a shorthand for `BoxScope::<MyFamily>::new_dyn(scope!({ ... }))`.
+/// The block is the same as the block passed to [`scope!`], so it can use the same macros, like `freeze!`.
+///
+/// As with [`crate::BoxScope::new_dyn`], the block cannot borrow data from outside of the scope,
+/// and the resulting scope is a `BoxScope<MyFamily>` that can be stored in a struct without naming the future type.
+///
+/// # Example
+///
+/// ```
+/// use nolife::{box_scope, family, BoxScope};
+///
+/// family!(SliceFamily<'a> = &'a [u8]);
+///
+/// struct Windows {
+///     scope: BoxScope<SliceFamily>,
+/// }
+///
+/// let mut windows = Windows {
+///     scope: box_scope!(SliceFamily, {
+///         let data = vec![1, 2, 3, 4];
+///         loop {
+///             for window in data.windows(2) {
+///                 freeze!(&mut &*window);
+///             }
+///         }
+///     }),
+/// };
+///
+/// assert_eq!(windows.scope.enter(|window| window.to_vec()), [1, 2]);
+/// assert_eq!(windows.scope.enter(|window| window.to_vec()), [2, 3]);
+/// ```
+#[macro_export]
+macro_rules! box_scope {
+    ($family:ty, $b:block) => {
+        $crate::BoxScope::<$family>::new_dyn($crate::scope!($b))
+    };
+}
@@ -0,0 +1,210 @@
+use core::marker::PhantomData;
+
+use crate::{
+    allocator::{Allocator, Global},
+    raw_scope::RawScope,
+    BoxScope, Family, TopScope,
+};
+
+/// Which panics poison a scope, so that its frozen data can no longer be accessed.
+///
+/// Set with [`ScopeBuilder::on_poison`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum PoisonPolicy {
+    /// Only panics of the underlying future poison the scope.
+    ///
+    /// A panic of the function passed to [`BoxScope::enter`] skips the frozen data, and the next call enters the scope
+    /// as usual. This is the behavior of the scopes created with [`BoxScope::new`].
+    #[default]
+    OnFuturePanic,
+    /// Any panic while the scope is entered poisons the scope, including panics of the function passed to
+    /// [`BoxScope::enter`], as with [`BoxScope::catch_enter`].
+    ///
+    /// This guarantees that frozen data left in an inconsistent state by a panicking function is never observed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, PoisonPolicy, SingleFamily};
+    ///
+    /// let mut scope = BoxScope::<SingleFamily<u32>>::builder()
+    ///     .on_poison(PoisonPolicy::OnAnyPanic)
+    ///     .build(scope!({
+    ///         let mut x = 0u32;
+    ///         loop {
+    ///             freeze!(&mut x);
+    ///             x += 1;
+    ///         }
+    ///     }));
+    ///
+    /// let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| scope.enter(|_| panic!())));
+    /// assert!(panicked.is_err());
+    /// assert!(scope.is_poisoned());
+    /// ```
+    #[cfg(feature = "std")]
+    OnAnyPanic,
+}
+
+/// What happens when the underlying future of a scope panics.
+///
+/// Set with [`ScopeBuilder::on_panic`]. Panics of the function passed to [`BoxScope::enter`] are governed by
+/// [`PoisonPolicy`] instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum PanicPolicy {
+    /// The panic unwinds out of [`BoxScope::enter`] or [`BoxScope::try_enter`], and the scope is then poisoned.
+    ///
+    /// This is the behavior of the scopes created with [`BoxScope::new`].
+    #[default]
+    Unwind,
+    /// The panic is caught, and the scope is poisoned without unwinding.
+    ///
+    /// [`BoxScope::try_enter`] returns [`crate::ScopeError::Poisoned`] from the call that panicked,
+    /// and [`BoxScope::enter`] panics with that error, rather than resuming the panic of the future.
+    /// The payload of the panic is dropped, the panic hook still reports it.
+    #[cfg(feature = "std")]
+    Poison,
+    /// The panic aborts the process, as with [`BoxScope::new_abort_on_panic`].
+    Abort,
+}
+
+/// Configures the creation of a [`BoxScope`].
+///
+/// Created with [`BoxScope::builder`]. Each setter returns the builder, and [`ScopeBuilder::build`] ties the passed
+/// scope to memory allocated with the configured allocator.
+///
+/// The default builder creates the same scopes as [`BoxScope::new`].
+///
+/// # Example
+///
+/// ```
+/// use nolife::{allocator::Global, scope, BoxScope, PanicPolicy, PoisonPolicy, SingleFamily};
+///
+/// let mut scope = BoxScope::<SingleFamily<u32>>::builder()
+///     .on_panic(PanicPolicy::Unwind)
+///     .allocator(Global)
+///     .on_poison(PoisonPolicy::OnFuturePanic)
+///     .build(scope!({
+///         let mut x = 0u32;
+///         loop {
+///             freeze!(&mut x);
+///             x += 1;
+///         }
+///     }));
+///
+/// assert_eq!(scope.enter(|x| *x), 0);
+/// assert_eq!(scope.enter(|x| *x), 1);
+/// ```
+pub struct ScopeBuilder<T, A = Global> {
+    alloc: A,
+    on_panic: PanicPolicy,
+    on_poison: PoisonPolicy,
+    family: PhantomData<fn() -> T>,
+}
+
+impl<T> BoxScope<T>
+where
+    T: for<'a> Family<'a>,
+{
+    /// Returns a [`ScopeBuilder`] to configure the creation of a scope.
+    ///
+    /// The default builder creates the same scopes as [`BoxScope::new`].
+    pub fn builder() -> ScopeBuilder<T> {
+        ScopeBuilder {
+            alloc: Global,
+            on_panic: PanicPolicy::default(),
+            on_poison: PoisonPolicy::default(),
+            family: PhantomData,
+        }
+    }
+}
+
+impl<T, A> ScopeBuilder<T, A>
+where
+    T: for<'a> Family<'a>,
+    A: Allocator,
+{
+    /// What happens when the underlying future panics.
+    ///
+    /// Defaults to [`PanicPolicy::Unwind`].
+    pub fn on_panic(mut self, policy: PanicPolicy) -> Self {
+        self.on_panic = policy;
+        self
+    }
+
+    /// Whether a panic unwinding out of the underlying future aborts the process instead of poisoning the scope.
+    ///
+    /// Shorthand for [`Self::on_panic`] with [`PanicPolicy::Abort`] or [`PanicPolicy::Unwind`].
+    /// See [`BoxScope::new_abort_on_panic`]. Defaults to `false`.
+    pub fn abort_on_panic(self, abort_on_panic: bool) -> Self {
+        self.on_panic(if abort_on_panic {
+            PanicPolicy::Abort
+        } else {
+            PanicPolicy::Unwind
+        })
+    }
+
+    /// Allocates the scope with `alloc`.
+    ///
+    /// See [`BoxScope::new_in`]. Defaults to [`Global`].
+    pub fn allocator<B: Allocator>(self, alloc: B) -> ScopeBuilder<T, B> {
+        ScopeBuilder {
+            alloc,
+            on_panic: self.on_panic,
+            on_poison: self.on_poison,
+            family: PhantomData,
+        }
+    }
+
+    /// Which panics poison the scope.
+    ///
+    /// Defaults to [`PoisonPolicy::OnFuturePanic`].
+    pub fn on_poison(mut self, policy: PoisonPolicy) -> Self {
+        self.on_poison = policy;
+        self
+    }
+
+    /// Ties the passed scope to memory allocated with the configured allocator.
+    ///
+    /// # Panics
+    ///
+    /// - If `scope` panics.
+    /// - If the memory cannot be allocated.
+    pub fn build<S: TopScope<Family = T>>(self, scope: S) -> BoxScope<T, S::Future, A> {
+        let this = BoxScope::new_in(scope, self.alloc);
+        let raw_scope = this.raw_scope();
+        match self.on_panic {
+            PanicPolicy::Unwind => {}
+            #[cfg(feature = "std")]
+            // SAFETY:
+            // 1. `raw_scope` is fully initialized by the constructor.
+            // 2. `this` is not shared yet.
+            PanicPolicy::Poison => unsafe { RawScope::set_catch_future_panic(raw_scope) },
+            // SAFETY: see above
+            PanicPolicy::Abort => unsafe { RawScope::set_abort_on_panic(raw_scope) },
+        }
+        match self.on_poison {
+            PoisonPolicy::OnFuturePanic => {}
+            #[cfg(feature = "std")]
+            // SAFETY:
+            // 1. `raw_scope` is fully initialized by the constructor.
+            // 2. `this` is not shared yet.
+            PoisonPolicy::OnAnyPanic => unsafe { RawScope::set_poison_on_enter_panic(raw_scope) },
+        }
+        this
+    }
+}
+
+impl<T, A> core::fmt::Debug for ScopeBuilder<T, A>
+where
+    A: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ScopeBuilder")
+            .field("alloc", &self.alloc)
+            .field("on_panic", &self.on_panic)
+            .field("on_poison", &self.on_poison)
+            .finish()
+    }
+}
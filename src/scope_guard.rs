@@ -0,0 +1,194 @@
+use core::{
+    future::Future,
+    ops::{Deref, DerefMut},
+    task::Context,
+};
+
+use crate::{
+    allocator::{Allocator, Global},
+    panics,
+    raw_scope::RawScope,
+    waker, BoxScope, Family,
+};
+
+/// Gives access to the data frozen inside of a [`BoxScope`], for as long as it lives.
+///
+/// Created with [`BoxScope::enter_guard`].
+pub struct ScopeGuard<'borrow, T, F: ?Sized, A = Global>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+{
+    scope: &'borrow mut BoxScope<T, F, A>,
+}
+
+impl<T, F: ?Sized, A> BoxScope<T, F, A>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+{
+    /// Enters the scope, returning a guard that gives access to the data frozen inside of the scope.
+    ///
+    /// Contrary to [`Self::enter`], the frozen data can then be accessed several times, across several statements,
+    /// through [`ScopeGuard::with`] and [`ScopeGuard::with_ref`].
+    /// When the family does not depend on its lifetime, such as a [`crate::SingleFamily`], the guard also dereferences
+    /// to the frozen data.
+    ///
+    /// The underlying future stays frozen while the guard lives. Dropping the guard resumes it up to its next freeze,
+    /// as [`Self::step`] does, so that the next call to [`Self::enter`] or one of its variants accesses the data of that
+    /// freeze without polling the future again.
+    /// If the future completes instead of freezing, its output is dropped. If the guard is dropped while unwinding,
+    /// the future is not resumed, and the next call resumes it as usual.
+    ///
+    /// # Panics
+    ///
+    /// - If the underlying future panics.
+    /// - If the underlying future panicked in a previous call.
+    /// - If the underlying future awaits for a future other than the [`crate::FrozenFuture`].
+    /// - If the underlying future completed, see [`Self::enter_or_complete`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, SingleFamily};
+    ///
+    /// let mut scope = BoxScope::<SingleFamily<Vec<u32>>>::new_dyn(scope!({
+    ///     let mut v = Vec::new();
+    ///     loop {
+    ///         freeze!(&mut v);
+    ///         v.push(v.len() as u32);
+    ///     }
+    /// }));
+    ///
+    /// let mut guard = scope.enter_guard();
+    /// guard.push(42);
+    /// assert_eq!(guard.len(), 1);
+    /// drop(guard);
+    ///
+    /// assert_eq!(scope.enter(|v| v.clone()), [42, 1]);
+    /// ```
+    pub fn enter_guard(&mut self) -> ScopeGuard<'_, T, F, A> {
+        self.enter_ref(|_| ());
+        ScopeGuard { scope: self }
+    }
+}
+
+impl<'borrow, T, F: ?Sized, A> ScopeGuard<'borrow, T, F, A>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+{
+    /// Accesses the data frozen inside of the scope.
+    ///
+    /// # Panics
+    ///
+    /// - If the passed function panics.
+    /// - If the underlying future froze its data with [`crate::TimeCapsule::freeze_ref`], see [`Self::with_ref`].
+    pub fn with<'guard, Output, G>(&'guard mut self, f: G) -> Output
+    where
+        G: for<'a> FnOnce(&'guard mut <T as Family<'a>>::Family) -> Output,
+    {
+        // SAFETY:
+        // 1. The raw scope of a `BoxScope` is valid as a post-condition of `new`.
+        // 2. `ScopeGuard::with` takes an exclusive reference and the reference passed to `f` cannot escape `f`.
+        //    The guard borrows the scope, so the future is not polled while it lives.
+        match unsafe { RawScope::access(self.scope.raw_scope(), f) } {
+            Ok(output) => output,
+            Err(err) => panics::scope_error(err),
+        }
+    }
+
+    /// Reads the data frozen inside of the scope.
+    ///
+    /// # Panics
+    ///
+    /// - If the passed function panics.
+    pub fn with_ref<'guard, Output, G>(&'guard self, f: G) -> Output
+    where
+        G: for<'a> FnOnce(&'guard <T as Family<'a>>::Family) -> Output,
+    {
+        // SAFETY:
+        // 1. The raw scope of a `BoxScope` is valid as a post-condition of `new`.
+        // 2. `ScopeGuard::with` takes an exclusive reference, so no exclusive reference to the frozen value exists
+        //    while this shared reference lives. The guard borrows the scope, so the future is not polled while it lives.
+        match unsafe { RawScope::access_ref(self.scope.raw_scope(), f) } {
+            Ok(output) => output,
+            Err(err) => panics::scope_error(err),
+        }
+    }
+}
+
+/// Resumes the underlying future up to its next freeze, see [`BoxScope::enter_guard`].
+///
+/// # Panics
+///
+/// - If the underlying future panics. The scope is then poisoned.
+impl<'borrow, T, F: ?Sized, A> Drop for ScopeGuard<'borrow, T, F, A>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+{
+    fn drop(&mut self) {
+        // a panicking future would abort the process while unwinding.
+        #[cfg(feature = "std")]
+        if std::thread::panicking() {
+            return;
+        }
+        // errors are reported by the next call, that enters the scope as usual if the future did not freeze.
+        // SAFETY:
+        // 1. The raw scope of a `BoxScope` is valid as a post-condition of `new`.
+        // 2. The raw scope of a `BoxScope` is heap allocated and never moved.
+        // 3. The guard borrows the scope exclusively, and the references it handed out do not outlive it.
+        let _ = unsafe {
+            RawScope::prime(
+                self.scope.raw_scope(),
+                &mut Context::from_waker(&waker::NOOP),
+            )
+        };
+    }
+}
+
+/// When the family does not depend on its lifetime, the guard dereferences to the frozen data.
+impl<'borrow, T, F: ?Sized, A, X> Deref for ScopeGuard<'borrow, T, F, A>
+where
+    T: for<'a> Family<'a, Family = X>,
+    F: Future,
+    A: Allocator,
+{
+    type Target = X;
+
+    fn deref(&self) -> &X {
+        self.with_ref(|frozen| frozen)
+    }
+}
+
+/// When the family does not depend on its lifetime, the guard dereferences to the frozen data.
+///
+/// # Panics
+///
+/// - If the underlying future froze its data with [`crate::TimeCapsule::freeze_ref`].
+impl<'borrow, T, F: ?Sized, A, X> DerefMut for ScopeGuard<'borrow, T, F, A>
+where
+    T: for<'a> Family<'a, Family = X>,
+    F: Future,
+    A: Allocator,
+{
+    fn deref_mut(&mut self) -> &mut X {
+        self.with(|frozen| frozen)
+    }
+}
+
+impl<'borrow, T, F: ?Sized, A> core::fmt::Debug for ScopeGuard<'borrow, T, F, A>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ScopeGuard").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,170 @@
+use core::{future::Future, marker::PhantomData, mem::MaybeUninit, ptr::NonNull};
+
+use crate::{raw_scope::RawScope, Family, Never, ScopeError, TopScope};
+
+/// A scope tied to the stack.
+///
+/// This kind of scopes doesn't allocate. In exchange, it cannot be moved and only lives for the duration of
+/// the closure passed to [`stack_scope`].
+pub struct StackScope<'scope, T, F>(NonNull<RawScope<T, F>>, PhantomData<&'scope mut ()>)
+where
+    T: for<'a> Family<'a>,
+    F: Future<Output = Never>;
+
+impl<'scope, T, F> core::fmt::Debug for StackScope<'scope, T, F>
+where
+    T: for<'a> Family<'a>,
+    F: Future<Output = Never>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StackScope")
+            .field("poisoned", &self.is_poisoned())
+            .field("active", &self.is_active())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Ties the passed scope to the stack, and passes it to `body`.
+///
+/// The scope is dropped when `body` returns, and cannot escape `body`.
+///
+/// # Panics
+///
+/// - If `scope` panics.
+/// - If `body` panics.
+///
+/// # Example
+///
+/// ```
+/// use nolife::{scope, stack_scope, SingleFamily};
+///
+/// let sum = stack_scope::<SingleFamily<u32>, _, _>(
+///     scope!({
+///         let mut x = 0u32;
+///         loop {
+///             freeze!(&mut x);
+///             x += 1;
+///         }
+///     }),
+///     |mut scope| (0..3).map(|_| scope.enter(|x| *x)).sum::<u32>(),
+/// );
+/// assert_eq!(sum, 3);
+/// ```
+pub fn stack_scope<T, S, Output>(
+    scope: S,
+    body: impl FnOnce(StackScope<'_, T, S::Future>) -> Output,
+) -> Output
+where
+    T: for<'a> Family<'a>,
+    S: TopScope<Family = T>,
+{
+    let mut raw_scope = RawScope::<T, S::Future>::new_uninit();
+    let raw_scope: *mut RawScope<T, MaybeUninit<S::Future>> = &mut raw_scope;
+    let raw_scope: *mut RawScope<T, S::Future> = raw_scope.cast();
+
+    // SAFETY:
+    // 1. `raw_scope` points to a local variable so is valid memory, although the future is not yet initialized
+    // 2. `raw_scope` was created from a valid `RawScope::<T, MaybeUninit<F>>`, so `state` is fully initialized.
+    //
+    // Note: as a post-condition of `RawScope`, `raw_scope` is fully initialized.
+    unsafe {
+        RawScope::open(raw_scope, scope);
+    }
+
+    struct Guard<Sc> {
+        raw_scope: *mut Sc,
+    }
+    // guard ensures the future is dropped before its storage goes out of scope, including on panic (i.e. if body panics)
+    let _drop_guard = Guard { raw_scope };
+    impl<Sc> Drop for Guard<Sc> {
+        fn drop(&mut self) {
+            // SAFETY: `self.raw_scope` was fully initialized by `RawScope::open` above, and is only dropped here.
+            // The local variable it points to is a `RawScope<T, MaybeUninit<F>>` that doesn't drop the future itself.
+            unsafe { core::ptr::drop_in_place(self.raw_scope) }
+        }
+    }
+
+    // SAFETY: `raw_scope` points to a local variable so is non-null.
+    body(StackScope(
+        unsafe { NonNull::new_unchecked(raw_scope) },
+        PhantomData,
+    ))
+}
+
+impl<'scope, T, F> StackScope<'scope, T, F>
+where
+    T: for<'a> Family<'a>,
+    F: Future<Output = Never>,
+{
+    /// Enters the scope, making it possible to access the data frozen inside of the scope.
+    ///
+    /// # Panics
+    ///
+    /// - If the passed function panics.
+    /// - If the underlying future panics.
+    /// - If the underlying future panicked in a previous call to [`Self::enter`] or [`Self::try_enter`].
+    /// - If the underlying future awaits for a future other than the [`crate::FrozenFuture`].
+    pub fn enter<'borrow, Output, G>(&'borrow mut self, f: G) -> Output
+    where
+        G: for<'a> FnOnce(&'borrow mut <T as Family<'a>>::Family) -> Output,
+    {
+        // SAFETY:
+        // 1. `self.0` is valid as a post-condition of `stack_scope`.
+        // 2. The object pointed to by `self.0` is a local variable of `stack_scope` that doesn't move,
+        //    and is dropped in place after `self` is no longer accessible.
+        // 3. `StackScope::enter` takes an exclusive reference and the reference passed to `f` cannot escape `f`.
+        unsafe { RawScope::enter(self.0, f) }
+    }
+
+    /// Enters the scope, making it possible to access the data frozen inside of the scope.
+    ///
+    /// This is the equivalent of [`crate::BoxScope::try_enter`] for stack scopes.
+    ///
+    /// # Errors
+    ///
+    /// - [`ScopeError::Poisoned`] if the underlying future panicked in a previous call.
+    /// - [`ScopeError::NotFrozen`] if the underlying future awaits for a future other than the [`crate::FrozenFuture`].
+    /// - [`ScopeError::FrozenShared`] if the underlying future froze its data with [`crate::TimeCapsule::freeze_ref`].
+    ///
+    /// # Panics
+    ///
+    /// - If the passed function panics.
+    /// - If the underlying future panics. The scope is then poisoned.
+    pub fn try_enter<'borrow, Output, G>(&'borrow mut self, f: G) -> Result<Output, ScopeError>
+    where
+        G: for<'a> FnOnce(&'borrow mut <T as Family<'a>>::Family) -> Output,
+    {
+        // SAFETY: see `StackScope::enter`
+        unsafe { RawScope::try_enter(self.0, f) }
+    }
+
+    /// Whether the underlying future panicked, in which case the scope can no longer be entered.
+    ///
+    /// This function does not poll the underlying future.
+    pub fn is_poisoned(&self) -> bool {
+        // SAFETY:
+        // 1. `self.0` is valid as a post-condition of `stack_scope`.
+        // 2. `StackScope::enter` and `StackScope::try_enter` take an exclusive reference.
+        unsafe { RawScope::is_poisoned(self.0) }
+    }
+
+    /// Whether the underlying future is currently frozen, with its data available to the next call to [`Self::enter`].
+    ///
+    /// This function does not poll the underlying future.
+    pub fn is_active(&self) -> bool {
+        // SAFETY:
+        // 1. `self.0` is valid as a post-condition of `stack_scope`.
+        // 2. `StackScope::enter` and `StackScope::try_enter` take an exclusive reference.
+        unsafe { RawScope::is_active(self.0) }
+    }
+
+    /// Whether the underlying future froze at least once, even if it was poisoned afterwards.
+    ///
+    /// This function does not poll the underlying future.
+    pub fn has_frozen(&self) -> bool {
+        // SAFETY:
+        // 1. `self.0` is valid as a post-condition of `stack_scope`.
+        // 2. `StackScope::enter` and `StackScope::try_enter` take an exclusive reference.
+        unsafe { RawScope::has_frozen(self.0) }
+    }
+}
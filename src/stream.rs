@@ -0,0 +1,111 @@
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_core::{FusedStream, Stream};
+
+use crate::{
+    allocator::{Allocator, Global},
+    panics, BoxScope, Family, ScopeError,
+};
+
+/// A [`Stream`] of owned values extracted from the data frozen inside of a [`BoxScope`].
+///
+/// Created with [`BoxScope::into_stream`].
+pub struct ScopeStream<T, F: ?Sized, G, A = Global>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+{
+    scope: BoxScope<T, F, A>,
+    extract: G,
+    terminated: bool,
+}
+
+// No field is structurally pinned.
+impl<T, F: ?Sized, G, A> Unpin for ScopeStream<T, F, G, A>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+{
+}
+
+impl<T, F: ?Sized, A> BoxScope<T, F, A>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+{
+    /// Converts this scope to a [`Stream`] yielding the values extracted by `extract` from the frozen data.
+    ///
+    /// Each call to [`Stream::poll_next`] enters the scope exactly once, that is, it runs the underlying future
+    /// until it freezes again, and then calls `extract` on the frozen data.
+    ///
+    /// The stream terminates when the underlying future completes, see [`BoxScope::new_with_output`].
+    /// The output of the future is dropped. The stream also terminates if the underlying future panics.
+    ///
+    /// # Panics
+    ///
+    /// - If `extract` panics.
+    /// - If the underlying future panics.
+    /// - If the underlying future awaits for a future other than the [`crate::FrozenFuture`].
+    pub fn into_stream<O, G>(self, extract: G) -> ScopeStream<T, F, G, A>
+    where
+        G: for<'a> FnMut(&mut <T as Family<'a>>::Family) -> O,
+    {
+        ScopeStream {
+            scope: self,
+            extract,
+            terminated: false,
+        }
+    }
+}
+
+impl<T, F: ?Sized, G, A, O> Stream for ScopeStream<T, F, G, A>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+    G: for<'a> FnMut(&mut <T as Family<'a>>::Family) -> O,
+{
+    type Item = O;
+
+    fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.terminated {
+            return Poll::Ready(None);
+        }
+
+        // terminate if `try_enter` panics
+        this.terminated = true;
+        let extract = &mut this.extract;
+        match this.scope.try_enter(|frozen| extract(frozen)) {
+            Ok(item) => {
+                this.terminated = false;
+                Poll::Ready(Some(item))
+            }
+            Err(ScopeError::Completed | ScopeError::Poisoned | ScopeError::Cancelled) => {
+                Poll::Ready(None)
+            }
+            Err(err @ (ScopeError::NotFrozen | ScopeError::FrozenShared)) => {
+                panics::scope_error(err)
+            }
+        }
+    }
+}
+
+impl<T, F: ?Sized, G, A, O> FusedStream for ScopeStream<T, F, G, A>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+    G: for<'a> FnMut(&mut <T as Family<'a>>::Family) -> O,
+{
+    fn is_terminated(&self) -> bool {
+        self.terminated
+    }
+}
@@ -0,0 +1,156 @@
+use std::sync::PoisonError;
+
+// the `loom` tests check the locking of the scope with the mutex of loom, that it can instrument.
+#[cfg(loom)]
+use loom::sync::Mutex;
+#[cfg(not(loom))]
+use std::sync::Mutex;
+
+use core::future::Future;
+
+use crate::{BoxScope, Family, Never, ScopeError, TopScope};
+
+/// A thread-safe scope tied to a Box.
+///
+/// This kind of scopes can be entered through a shared reference, so it can be shared between threads,
+/// for instance by wrapping it in an [`std::sync::Arc`].
+///
+/// The underlying [`BoxScope`] is protected by a [`Mutex`]: concurrent calls to [`Self::enter`] are serialized,
+/// each one blocking until the previous ones returned.
+pub struct SyncScope<T, F: ?Sized = dyn Future<Output = Never> + Send + 'static>(
+    Mutex<BoxScope<T, F>>,
+)
+where
+    T: for<'a> Family<'a>,
+    F: Future<Output = Never>;
+
+impl<T, F: ?Sized> core::fmt::Debug for SyncScope<T, F>
+where
+    T: for<'a> Family<'a>,
+    F: Future<Output = Never>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // `Mutex` does not block, and prints a placeholder if the scope is currently entered.
+        f.debug_tuple("SyncScope").field(&self.0).finish()
+    }
+}
+
+impl<T> SyncScope<T>
+where
+    T: for<'a> Family<'a>,
+{
+    /// Ties the passed scope to the heap.
+    ///
+    /// This function erased the `Future` generic type of the [`TopScope`], at the cost
+    /// of using a dynamic function call to poll the future.
+    ///
+    /// If the `Future` generic type can be inferred, it can be more efficient to use [`SyncScope::new`].
+    ///
+    /// # Panics
+    ///
+    /// - If `scope` panics.
+    pub fn new_dyn<S: TopScope<Family = T>>(scope: S) -> Self
+    where
+        S::Future: Send + 'static,
+    {
+        Self(Mutex::new(BoxScope::new_dyn_send(scope)))
+    }
+}
+
+impl<T, F> SyncScope<T, F>
+where
+    T: for<'a> Family<'a>,
+    F: Future<Output = Never>,
+{
+    /// Ties the passed scope to the heap.
+    ///
+    /// This function retains the `Future` generic type from the [`TopScope`].
+    /// To store the [`SyncScope`] in a struct, it can be easier to use [`SyncScope::new_dyn`].
+    ///
+    /// # Panics
+    ///
+    /// - If `scope` panics.
+    pub fn new<S: TopScope<Family = T, Future = F>>(scope: S) -> Self {
+        Self(Mutex::new(BoxScope::new(scope)))
+    }
+}
+
+impl<T, F: ?Sized> SyncScope<T, F>
+where
+    T: for<'a> Family<'a>,
+    F: Future<Output = Never>,
+{
+    /// Enters the scope, making it possible to access the data frozen inside of the scope.
+    ///
+    /// This function blocks the current thread until no other thread is entering the scope.
+    ///
+    /// Unlike the [`Mutex`] it uses internally, a panic while entering the scope does not prevent
+    /// other threads to enter the scope again. A panic in the underlying future poisons the scope, though,
+    /// see [`BoxScope::try_enter`].
+    ///
+    /// # Panics
+    ///
+    /// - If the passed function panics.
+    /// - If the underlying future panics.
+    /// - If the underlying future panicked in a previous call to [`Self::enter`] or [`Self::try_enter`].
+    /// - If the underlying future awaits for a future other than the [`crate::FrozenFuture`].
+    pub fn enter<Output, G>(&self, f: G) -> Output
+    where
+        G: for<'a> FnOnce(&mut <T as Family<'a>>::Family) -> Output,
+    {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .enter(f)
+    }
+
+    /// Enters the scope, making it possible to access the data frozen inside of the scope.
+    ///
+    /// This function blocks the current thread until no other thread is entering the scope.
+    /// See [`BoxScope::try_enter`] for details.
+    ///
+    /// # Errors
+    ///
+    /// - [`ScopeError::Poisoned`] if the underlying future panicked in a previous call.
+    /// - [`ScopeError::NotFrozen`] if the underlying future awaits for a future other than the [`crate::FrozenFuture`].
+    /// - [`ScopeError::FrozenShared`] if the underlying future froze its data with [`crate::TimeCapsule::freeze_ref`].
+    ///
+    /// # Panics
+    ///
+    /// - If the passed function panics.
+    /// - If the underlying future panics. The scope is then poisoned.
+    pub fn try_enter<Output, G>(&self, f: G) -> Result<Output, ScopeError>
+    where
+        G: for<'a> FnOnce(&mut <T as Family<'a>>::Family) -> Output,
+    {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .try_enter(f)
+    }
+
+    /// Whether the underlying future panicked, in which case the scope can no longer be entered.
+    ///
+    /// This function blocks the current thread until no other thread is entering the scope.
+    pub fn is_poisoned(&self) -> bool {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_poisoned()
+    }
+
+    /// Consumes this scope, returning the underlying [`BoxScope`].
+    pub fn into_inner(self) -> BoxScope<T, F> {
+        self.0.into_inner().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T, F: ?Sized> From<BoxScope<T, F>> for SyncScope<T, F>
+where
+    T: for<'a> Family<'a>,
+    F: Future<Output = Never>,
+{
+    fn from(scope: BoxScope<T, F>) -> Self {
+        Self(Mutex::new(scope))
+    }
+}
@@ -1,9 +1,40 @@
 use core::task::{RawWaker, RawWakerVTable, Waker};
 
+/// A waker that does nothing, shared by all scopes so that it is not created nor dropped at each poll.
+// Safety: The waker points to a vtable with functions that do nothing. Doing
+// nothing is memory-safe.
+pub static NOOP: Waker = unsafe { Waker::from_raw(RAW_WAKER) };
+
+/// Returns the waker that the scopes of this crate use to poll their underlying future.
+///
+/// This waker does nothing: waking it never schedules anything, and neither cloning nor dropping it allocates.
+/// Scopes are polled synchronously by [`crate::BoxScope::enter`] and its variants, which never wait for a wake-up,
+/// so the waker only exists to build the [`Context`](core::task::Context) passed to the underlying future.
+///
+/// Adapters that drive a scope with [`crate::BoxScope::poll_enter`] in a loop of their own can reuse this waker
+/// to poll the scope with the same semantics as [`crate::BoxScope::enter`]. A scope whose future awaits for a future
+/// other than the [`crate::FrozenFuture`] is then never woken up, and each poll must be retried explicitly.
+///
+/// # Example
+///
+/// ```
+/// use core::task::{Context, Poll};
+/// use nolife::{noop_waker, scope, BoxScope, SingleFamily};
+///
+/// let mut scope = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+///     freeze_forever!(&mut 42)
+/// }));
+///
+/// let mut cx = Context::from_waker(noop_waker());
+/// assert_eq!(scope.poll_enter(&mut cx, |x| *x), Poll::Ready(Ok(Ok(42))));
+/// ```
+pub fn noop_waker() -> &'static Waker {
+    &NOOP
+}
+
+#[cfg(test)]
 pub fn create() -> Waker {
-    // Safety: The waker points to a vtable with functions that do nothing. Doing
-    // nothing is memory-safe.
-    unsafe { Waker::from_raw(RAW_WAKER) }
+    NOOP.clone()
 }
 
 const RAW_WAKER: RawWaker = RawWaker::new(core::ptr::null(), &VTABLE);
@@ -0,0 +1,216 @@
+use core::future::Future;
+
+use crate::{
+    allocator::{Allocator, Global},
+    BoxScope, Completed, Family, ScopeError,
+};
+
+/// Two [`BoxScope`]s entered together, advancing in lockstep.
+///
+/// Created with [`BoxScope::zip`].
+///
+/// # Termination
+///
+/// Each call polls the first scope, then the second scope, once each.
+/// The zipped scope completes as soon as one of the scopes completes, which is reported by
+/// [`ZipScope::enter_or_complete`] as a [`ZipCompleted`]:
+///
+/// - If the first scope completes, the second scope is not polled during this call.
+/// - If the second scope completes, the first scope was already resumed to its next freeze during this call,
+///   but its frozen data is not passed to any function.
+///
+/// In both cases, the passed function is not called, and the scope that did not complete can still be retrieved
+/// with [`ZipScope::unzip`] and entered on its own.
+pub struct ZipScope<T, U, F: ?Sized, G: ?Sized, A = Global, B = Global>
+where
+    T: for<'a> Family<'a>,
+    U: for<'a> Family<'a>,
+    F: Future,
+    G: Future,
+    A: Allocator,
+    B: Allocator,
+{
+    first: BoxScope<T, F, A>,
+    second: BoxScope<U, G, B>,
+}
+
+/// The output of the scope that completed first in a [`ZipScope`].
+///
+/// See [`ZipScope::enter_or_complete`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZipCompleted<O1, O2> {
+    /// The first scope completed with this output, the second scope was not polled.
+    First(O1),
+    /// The second scope completed with this output, after the first scope froze.
+    Second(O2),
+}
+
+impl<T, F: ?Sized, A> BoxScope<T, F, A>
+where
+    T: for<'a> Family<'a>,
+    F: Future,
+    A: Allocator,
+{
+    /// Zips this scope with `other`, so that both are entered together and advance in lockstep.
+    ///
+    /// See [`ZipScope`] for the termination semantics.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, SingleFamily};
+    ///
+    /// let numbers = BoxScope::<SingleFamily<u32>>::new_dyn(scope!({
+    ///     let mut x = 0;
+    ///     loop {
+    ///         freeze!(&mut x);
+    ///         x += 1;
+    ///     }
+    /// }));
+    /// let letters = BoxScope::<SingleFamily<char>>::new_dyn(scope!({
+    ///     let mut c = 'a';
+    ///     loop {
+    ///         freeze!(&mut c);
+    ///         c = char::from(c as u8 + 1);
+    ///     }
+    /// }));
+    ///
+    /// let mut zipped = numbers.zip(letters);
+    /// assert_eq!(zipped.enter(|x, c| (*x, *c)), (0, 'a'));
+    /// assert_eq!(zipped.enter(|x, c| (*x, *c)), (1, 'b'));
+    /// ```
+    pub fn zip<U, G, B>(self, other: BoxScope<U, G, B>) -> ZipScope<T, U, F, G, A, B>
+    where
+        U: for<'a> Family<'a>,
+        G: Future + ?Sized,
+        B: Allocator,
+    {
+        ZipScope {
+            first: self,
+            second: other,
+        }
+    }
+}
+
+impl<T, U, F: ?Sized, G: ?Sized, A, B> ZipScope<T, U, F, G, A, B>
+where
+    T: for<'a> Family<'a>,
+    U: for<'a> Family<'a>,
+    F: Future,
+    G: Future,
+    A: Allocator,
+    B: Allocator,
+{
+    /// Enters both scopes, making it possible to access the data frozen inside of each of them.
+    ///
+    /// # Panics
+    ///
+    /// - Same as [`BoxScope::enter`], for either scope.
+    pub fn enter<'borrow, Output, H>(&'borrow mut self, f: H) -> Output
+    where
+        H: for<'a, 'b> FnOnce(
+            &'borrow mut <T as Family<'a>>::Family,
+            &'borrow mut <U as Family<'b>>::Family,
+        ) -> Output,
+    {
+        let second = &mut self.second;
+        self.first
+            .enter(|first| second.enter(|second| f(first, second)))
+    }
+
+    /// Enters both scopes, making it possible to access the data frozen inside of each of them.
+    ///
+    /// # Errors
+    ///
+    /// - Same as [`BoxScope::try_enter`], for either scope.
+    ///   The second scope is not polled if the first one returns an error.
+    ///
+    /// # Panics
+    ///
+    /// - Same as [`BoxScope::try_enter`], for either scope.
+    pub fn try_enter<'borrow, Output, H>(&'borrow mut self, f: H) -> Result<Output, ScopeError>
+    where
+        H: for<'a, 'b> FnOnce(
+            &'borrow mut <T as Family<'a>>::Family,
+            &'borrow mut <U as Family<'b>>::Family,
+        ) -> Output,
+    {
+        let second = &mut self.second;
+        self.first
+            .try_enter(|first| second.try_enter(|second| f(first, second)))?
+    }
+
+    /// Enters both scopes, or returns the output of the first scope that completes.
+    ///
+    /// See [`ZipScope`] for the termination semantics.
+    ///
+    /// # Panics
+    ///
+    /// - Same as [`BoxScope::enter_or_complete`], for either scope.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nolife::{scope, BoxScope, SingleFamily, ZipCompleted};
+    ///
+    /// let short = BoxScope::<SingleFamily<u32>, _>::new_with_output(scope!({
+    ///     freeze!(&mut 0);
+    ///     "short"
+    /// }));
+    /// let long = BoxScope::<SingleFamily<u32>, _>::new_with_output(scope!({
+    ///     for mut x in 0..10 {
+    ///         freeze!(&mut x);
+    ///     }
+    ///     "long"
+    /// }));
+    ///
+    /// let mut zipped = short.zip(long);
+    /// assert_eq!(zipped.enter_or_complete(|x, y| *x + *y), Ok(0));
+    /// assert_eq!(zipped.enter_or_complete(|x, y| *x + *y), Err(ZipCompleted::First("short")));
+    ///
+    /// let (_, mut long) = zipped.unzip();
+    /// assert_eq!(long.enter(|y| *y), 1);
+    /// ```
+    pub fn enter_or_complete<'borrow, Output, H>(
+        &'borrow mut self,
+        f: H,
+    ) -> Result<Output, ZipCompleted<F::Output, G::Output>>
+    where
+        H: for<'a, 'b> FnOnce(
+            &'borrow mut <T as Family<'a>>::Family,
+            &'borrow mut <U as Family<'b>>::Family,
+        ) -> Output,
+    {
+        let second = &mut self.second;
+        match self
+            .first
+            .enter_or_complete(|first| second.enter_or_complete(|second| f(first, second)))
+        {
+            Ok(Ok(output)) => Ok(output),
+            Ok(Err(Completed(second))) => Err(ZipCompleted::Second(second)),
+            Err(Completed(first)) => Err(ZipCompleted::First(first)),
+        }
+    }
+
+    /// Returns both underlying scopes.
+    pub fn unzip(self) -> (BoxScope<T, F, A>, BoxScope<U, G, B>) {
+        (self.first, self.second)
+    }
+}
+
+impl<T, U, F: ?Sized, G: ?Sized, A, B> core::fmt::Debug for ZipScope<T, U, F, G, A, B>
+where
+    T: for<'a> Family<'a>,
+    U: for<'a> Family<'a>,
+    F: Future,
+    G: Future,
+    A: Allocator,
+    B: Allocator,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ZipScope")
+            .field("first", &self.first)
+            .field("second", &self.second)
+            .finish()
+    }
+}
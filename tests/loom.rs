@@ -0,0 +1,86 @@
+//! Model checking of the locking of [`SyncScope`] with `loom`.
+//!
+//! Run with `RUSTFLAGS="--cfg loom" cargo test --test loom --release`.
+//!
+//! The data frozen by the scopes below and the state of their futures are kept in the cells of `loom`,
+//! so that `loom` reports any access to them that is not serialized by the lock of the scope.
+#![cfg(loom)]
+
+use loom::{cell::UnsafeCell, sync::Arc, thread};
+use nolife::{scope, SingleFamily, SyncScope};
+
+/// A counter whose accesses are tracked by `loom`.
+struct Counter(UnsafeCell<u32>);
+
+impl Counter {
+    fn increment(&mut self) -> u32 {
+        self.0.with_mut(|count| {
+            // SAFETY: `loom` checks that the accesses to the cell are not concurrent.
+            unsafe {
+                *count += 1;
+                *count
+            }
+        })
+    }
+
+    fn get(&self) -> u32 {
+        // SAFETY: `loom` checks that the accesses to the cell are not concurrent.
+        self.0.with(|count| unsafe { *count })
+    }
+}
+
+fn counter_scope() -> SyncScope<SingleFamily<Counter>> {
+    SyncScope::new_dyn(scope!({
+        let mut entered = Counter(UnsafeCell::new(0));
+        let mut resumed = Counter(UnsafeCell::new(0));
+        loop {
+            freeze!(&mut entered);
+            // the future accesses its own state between two calls to `enter`
+            resumed.increment();
+            assert_eq!(resumed.get(), entered.get());
+        }
+    }))
+}
+
+#[test]
+fn concurrent_enter() {
+    loom::model(|| {
+        let scope = Arc::new(counter_scope());
+
+        let threads: Vec<_> = (0..2)
+            .map(|_| {
+                let scope = scope.clone();
+                thread::spawn(move || scope.enter(|entered| entered.increment()))
+            })
+            .collect();
+        let mut counts: Vec<u32> = threads
+            .into_iter()
+            .map(|thread| thread.join().unwrap())
+            .collect();
+        counts.sort_unstable();
+
+        // each call observed the changes of the previous ones
+        assert_eq!(counts, [1, 2]);
+        assert_eq!(scope.enter(|entered| entered.get()), 2);
+    });
+}
+
+#[test]
+fn concurrent_enter_and_inspect() {
+    loom::model(|| {
+        let scope = Arc::new(counter_scope());
+
+        let entering = {
+            let scope = scope.clone();
+            thread::spawn(move || scope.try_enter(|entered| entered.increment()))
+        };
+        assert!(!scope.is_poisoned());
+        let count = scope.try_enter(|entered| entered.increment());
+        let other = entering.join().unwrap();
+
+        let mut counts = [count.unwrap(), other.unwrap()];
+        counts.sort_unstable();
+        assert_eq!(counts, [1, 2]);
+        assert!(!scope.is_poisoned());
+    });
+}